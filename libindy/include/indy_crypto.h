@@ -109,7 +109,7 @@ extern "C" {
     /// wallet_handle: wallet handler (created by open_wallet).
     /// signer_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
     /// message_raw: a pointer to first byte of message to be signed
-    /// message_len: a message length
+    /// message_len: a message length. Empty message is accepted
     /// cb: Callback that takes command result as parameter.
     ///
     /// #Returns
@@ -131,6 +131,71 @@ extern "C" {
                                                               indy_u32_t       signature_len)
                                         );
 
+    /// Signs several messages with the same key.
+    ///
+    /// The signer key is loaded from the wallet once for the whole batch.
+    /// A message that can't be decoded doesn't abort the batch, its slot in the result reports the error.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// signer_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// messages_json: json array of base64 encoded messages to be signed. Example:
+    ///   ["bWVzc2FnZTE=", "bWVzc2FnZTI="]
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// signatures_json: json array with one entry per message, in the same order as messages:
+    ///   [{"ok": "<base58 signature>"}, {"err": {"code": <error code>, "message": "<string>"}}]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_sign_multiple(indy_handle_t      command_handle,
+                                                  indy_handle_t      wallet_handle,
+                                                  const char *       signer_vk,
+                                                  const char *       messages_json,
+
+                                                  void           (*cb)(indy_handle_t    command_handle_,
+                                                                       indy_error_t     err,
+                                                                       const char *     signatures_json)
+                                                 );
+
+    /// Signs a message with several keys.
+    ///
+    /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+    /// for specific DID.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// signer_vks_json: json array of ids (verkeys) of message signers. The keys must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// message_raw: a pointer to first byte of message to be signed
+    /// message_len: a message length. Empty message is accepted
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// signatures_json: json object that maps each signer verkey to its base58 encoded signature. Example:
+    ///   {"<verkey1>": "<signature1>", "<verkey2>": "<signature2>"}
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_crypto_multi_sign(indy_handle_t      command_handle,
+                                               indy_handle_t      wallet_handle,
+                                               const char *       signer_vks_json,
+                                               const indy_u8_t *  message_raw,
+                                               indy_u32_t         message_len,
+
+                                               void           (*cb)(indy_handle_t    command_handle_,
+                                                                    indy_error_t     err,
+                                                                    const char *     signatures_json)
+                                              );
+
     /// Verify a signature with a verkey.
     ///
     /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
@@ -140,7 +205,7 @@ extern "C" {
     /// command_handle: command handle to map callback to user context.
     /// signer_vk: verkey of signer of the message
     /// message_raw: a pointer to first byte of message that has been signed
-    /// message_len: a message length
+    /// message_len: a message length. Empty message is accepted
     /// signature_raw: a pointer to first byte of signature to be verified
     /// signature_len: a signature length
     /// cb: Callback that takes command result as parameter.
@@ -165,6 +230,203 @@ extern "C" {
                                                                 indy_bool_t   valid )
                                           );
 
+    /// Verify a signature with a verkey and report why verification failed.
+    /// Intended for diagnostics, indy_crypto_verify keeps returning plain bool.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// signer_vk: verkey of the message signer
+    /// message_raw: a pointer to first byte of message that has been signed
+    /// message_len: a message length. Empty message is accepted
+    /// signature_raw: a pointer to first byte of signature to be verified
+    /// signature_len: a signature length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// result_json: json
+    ///   {
+    ///     "verified": bool, - true if signature is valid
+    ///     "reason": optional<string>, - why verification failed, one of:
+    ///         "key not resolved" - signer_vk is abbreviated, malformed or of unknown crypto type
+    ///         "malformed signature" - signature has wrong length
+    ///         "signature mismatch" - signature doesn't match message and signer_vk
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_crypto_verify_detailed(indy_handle_t      command_handle,
+                                                    const char *       signer_vk,
+                                                    const indy_u8_t *  message_raw,
+                                                    indy_u32_t         message_len,
+                                                    const indy_u8_t *  signature_raw,
+                                                    indy_u32_t         signature_len,
+
+                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                         indy_error_t  err,
+                                                                         const char *  result_json)
+                                                   );
+
+    /// Verify a signature against a list of verkeys and report which one matched.
+    /// Useful during key rotation, when a message may still be signed with the old key.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// signer_vks_json: json array of verkeys to try, in order of preference
+    /// message_raw: a pointer to first byte of message that has been signed
+    /// message_len: a message length. Empty message is accepted
+    /// signature_raw: a pointer to first byte of signature to be verified
+    /// signature_len: a signature length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// result_json: json
+    ///   {
+    ///     "verified": bool, - true if signature is valid for any of the verkeys
+    ///     "verkey": optional<string>, - the first verkey the signature is valid for
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_crypto_verify_any_key(indy_handle_t      command_handle,
+                                                   const char *       signer_vks_json,
+                                                   const indy_u8_t *  message_raw,
+                                                   indy_u32_t         message_len,
+                                                   const indy_u8_t *  signature_raw,
+                                                   indy_u32_t         signature_len,
+
+                                                   void           (*cb)(indy_handle_t command_handle_,
+                                                                        indy_error_t  err,
+                                                                        const char *  result_json)
+                                                  );
+
+    /// Signs a JSON message with a key.
+    ///
+    /// The message is canonicalized before signing (object keys sorted, no insignificant whitespace),
+    /// so logically equal JSON objects produce the same signature. Use indy_crypto_verify_json to verify it.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// signer_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// message_json: JSON message to be signed
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// a signature string
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_sign_json(indy_handle_t      command_handle,
+                                              indy_handle_t      wallet_handle,
+                                              const char *       signer_vk,
+                                              const char *       message_json,
+
+                                              void           (*cb)(indy_handle_t    command_handle_,
+                                                                   indy_error_t     err,
+                                                                   const indy_u8_t* signature_raw,
+                                                                   indy_u32_t       signature_len)
+                                             );
+
+    /// Verify a signature made by indy_crypto_sign_json.
+    ///
+    /// The message is canonicalized the same way as for signing, so any JSON logically equal
+    /// to the signed one (different key order or formatting) verifies.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// signer_vk: verkey of the message signer
+    /// message_json: JSON message that has been signed
+    /// signature_raw: a pointer to first byte of signature to be verified
+    /// signature_len: a signature length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// valid: true - if signature is valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_crypto_verify_json(indy_handle_t      command_handle,
+                                                const char *       signer_vk,
+                                                const char *       message_json,
+                                                const indy_u8_t *  signature_raw,
+                                                indy_u32_t         signature_len,
+
+                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                     indy_error_t  err,
+                                                                     indy_bool_t   valid )
+                                               );
+
+    /// Generates a fresh random nonce (24 bytes) suitable for challenge-response.
+    /// Doesn't require wallet or DID.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// nonce: base58 encoded nonce
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_crypto_get_nonce(indy_handle_t command_handle,
+
+                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                   indy_error_t  err,
+                                                                   const char*   nonce)
+                                             );
+
+    /// Runs known-answer tests of the crypto backend against embedded vectors
+    /// (seed derivation, sign/verify and box/unbox).
+    ///
+    /// Applications can call it at startup and refuse to run if it fails, as this detects
+    /// a miscompiled or substituted crypto library.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// report_json: pass/fail result per primitive
+    /// {
+    ///     "seed_derivation": bool,
+    ///     "sign_verify": bool,
+    ///     "box_open": bool,
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// CommonInvalidState if any of the known-answer tests fails
+    extern indy_error_t indy_crypto_self_test(indy_handle_t command_handle,
+
+                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                   indy_error_t  err,
+                                                                   const char*   report_json)
+                                             );
+
+    /// Lists the crypto types this build supports, so wrappers can detect
+    /// whether a `crypto_type` can be passed to indy_create_key or indy_create_and_store_my_did.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// crypto_types_json: json array of supported crypto type names, e.g. ["ed25519"]
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_crypto_get_supported_types(indy_handle_t command_handle,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             const char*   crypto_types_json)
+                                                       );
+
     /// **** THIS FUNCTION WILL BE DEPRECATED USE indy_pack_message() INSTEAD ****
     /// Encrypt a message by authenticated-encryption scheme.
     ///
@@ -323,6 +585,37 @@ extern "C" {
                                                                       indy_u32_t        decrypted_msg_len)
                                                  );
 
+    /// Derives a raw shared secret between my key and their key (Curve25519 ECDH).
+    ///
+    /// Both keys are converted from Ed25519 to Curve25519 before the key agreement, so
+    /// deriving with (my key, their verkey) and (their key, my verkey) gives the same secret.
+    /// The result is not hashed; callers are expected to pass it through a KDF before using it
+    /// as a symmetric key.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// my_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// their_vk: verkey of the other party
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// shared_secret: base58 encoded 32-byte shared secret
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_crypto_derive_shared_secret(indy_handle_t      command_handle,
+                                                         indy_handle_t      wallet_handle,
+                                                         const char *       my_vk,
+                                                         const char *       their_vk,
+
+                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                              indy_error_t  err,
+                                                                              const char*   shared_secret)
+                                                         );
+
 
     /// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
     ///
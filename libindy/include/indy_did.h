@@ -26,6 +26,8 @@ extern "C" {
     ///               currently only 'ed25519' value is supported for this field)
     ///     "cid": bool, (optional; if not set then false is used;)
     ///     "method_name": string, method name to create fully qualified did (Example:  `did:method_name:NcYxiDXkpYi6ov5FcYDi1e`).
+    ///     "strict_seed": bool, (optional; if not set then false is used;
+    ///             if true then a seed with too low entropy, e.g. of one repeated character, is rejected)
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -117,8 +119,50 @@ extern "C" {
                                                                      indy_error_t  err)
                                                );
 
+    /// Generates a new key for an existing DID (owned by the caller of the library),
+    /// publishes it to the ledger with a NYM transaction and applies it as main key once
+    /// the ledger has accepted the transaction.
+    ///
+    /// If the ledger rejects the transaction the new key stays pending (as after "indy_replace_keys_start"),
+    /// so the call can be retried or the pending key applied later with "indy_replace_keys_apply".
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// submitter_did: DID of the NYM transaction submitter. Must be stored in the wallet.
+    /// target_did: DID stored in the wallet whose key is rotated.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - verkey: The new verkey applied for the target DID.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Ledger*
+    /// Crypto*
+
+    extern indy_error_t indy_rotate_and_publish_key(indy_handle_t command_handle,
+                                                    indy_handle_t pool_handle,
+                                                    indy_handle_t wallet_handle,
+                                                    const char *  submitter_did,
+                                                    const char *  target_did,
+
+                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                         indy_error_t  err,
+                                                                         const char *  verkey)
+                                                   );
+
     /// Saves their DID for a pairwise connection in a secured Wallet,
     /// so that it can be used to verify transaction.
+    /// Storing the same DID with the same verkey again is a no-op.
+    /// A verkey can be added later to a DID stored without one.
+    /// Storing the same DID with another verkey fails with CommonInvalidState unless "overwrite" is set.
     ///
     /// #Params
     /// wallet_handle: wallet handler (created by open_wallet).
@@ -129,6 +173,7 @@ extern "C" {
     ///        "verkey": string
     ///             - optional is case of adding a new DID, and DID is cryptonym: did == verkey,
     ///             - mandatory in case of updating an existing DID
+    ///        "overwrite": bool (optional, false by default) - replace verkey of already stored DID
     ///     }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -151,6 +196,48 @@ extern "C" {
                                                                  indy_error_t  err)
                                            );
 
+    /// Saves several their DIDs in a secured Wallet at once.
+    /// Every entry is stored the same way as indy_store_their_did does it.
+    /// A malformed or conflicting entry doesn't abort the batch: its slot in the result reports
+    /// the error while the other entries are still stored.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handler (created by open_wallet).
+    /// identities_json: JSON array of identities in the indy_store_their_did format:
+    ///     [{
+    ///        "did": string, (required)
+    ///        "verkey": string (optional),
+    ///        "overwrite": bool (optional)
+    ///     }]
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - results_json: JSON array with one entry per identity, in the same order:
+    ///     [
+    ///        {"ok": string}, - did of the stored entry
+    ///        {"err": {
+    ///            "code": int, - error code
+    ///            "message": string
+    ///        }}
+    ///     ]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    extern indy_error_t indy_store_their_dids(indy_handle_t command_handle,
+                                              indy_handle_t wallet_handle,
+                                              const char *  identities_json,
+
+                                              void           (*cb)(indy_handle_t command_handle_,
+                                                                   indy_error_t  err,
+                                                                   const char *const results_json)
+                                             );
+
     /// Returns ver key (key id) for the given DID.
     ///
     /// "indy_key_for_did" call follow the idea that we resolve information about their DID from
@@ -191,6 +278,46 @@ extern "C" {
                                                                  const char *const key)
                                         );
 
+    /// Returns ver key (key id) for the given DID together with the place it was resolved from.
+    ///
+    /// The key is resolved the same way as "indy_key_for_did" does it. The source lets the caller
+    /// decide how much to trust and how long to cache the returned key.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// pool_handle:   Pool handle (created by open_pool).
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did - The DID to resolve key.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - key_json - The DIDs ver key with its source:
+    ///     {
+    ///         "verkey": string, - The DIDs ver key (key id).
+    ///         "source": string - one of:
+    ///             "my" - DID owned by the caller of the library,
+    ///             "their_cached" - their DID already stored in the wallet,
+    ///             "ledger" - their DID fetched from the ledger by this call
+    ///     }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_key_for_did_detailed(indy_handle_t     command_handle,
+                                                  indy_handle_t     pool_handle,
+                                                  indy_handle_t     wallet_handle,
+                                                  const char *const did,
+
+                                                  void              (*cb)(indy_handle_t     command_handle,
+                                                                          indy_error_t      err,
+                                                                          const char *const key_json)
+                                                 );
+
     /// Returns ver key (key id) for the given DID.
     ///
     /// "indy_key_for_local_did" call looks data stored in the local wallet only and skips freshness
@@ -278,6 +405,7 @@ extern "C" {
     /// Common*
     /// Wallet*
     /// Crypto*
+    /// LedgerNotFound if no endpoint is set for DID on the ledger
     extern indy_error_t indy_get_endpoint_for_did(indy_handle_t     command_handle,
                                                   indy_handle_t     wallet_handle,
                                                   indy_handle_t     pool_handle,
@@ -289,6 +417,158 @@ extern "C" {
                                                                           const char *const transport_vk)
                                                  );
 
+    /// Replaces the transport key of the endpoint stored for the given DID.
+    /// The endpoint address is left untouched, so transport keys can be rotated
+    /// independently from the DID signing key.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did - The DID whose endpoint must be rekeyed.
+    /// transport_key - The new transport key (ver key, key id).
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    /// WalletItemNotFound if no endpoint is stored for DID in the wallet
+    extern indy_error_t indy_rekey_endpoint_for_did(indy_handle_t     command_handle,
+                                                    indy_handle_t     wallet_handle,
+                                                    const char *const did,
+                                                    const char *const transport_key,
+
+                                                    void              (*cb)(indy_handle_t     command_handle,
+                                                                            indy_error_t      err)
+                                                   );
+
+    /// Builds a connection request for pairing with another agent and anoncrypts it to their verkey.
+    ///
+    /// The request carries my DID, its verkey and the endpoint stored for it (if any):
+    /// {
+    ///     "did": string,
+    ///     "verkey": string,
+    ///     "endpoint": Option<{"ha": string, "verkey": Option<string>}>
+    /// }
+    /// The other party opens it with indy_parse_connection_request.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// their_vk: verkey of the agent the request is sent to.
+    /// my_did: my DID stored in the wallet.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - request_raw: a pointer to first byte of the anoncrypted request.
+    /// - request_len: the anoncrypted request length.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_prepare_anoncrypted_connection_request(indy_handle_t     command_handle,
+                                                                    indy_handle_t     wallet_handle,
+                                                                    const char *const their_vk,
+                                                                    const char *const my_did,
+
+                                                                    void              (*cb)(indy_handle_t        command_handle,
+                                                                                            indy_error_t         err,
+                                                                                            const indy_u8_t*     request_raw,
+                                                                                            indy_u32_t           request_len)
+                                                                   );
+
+    /// Opens a connection request created by indy_prepare_anoncrypted_connection_request.
+    ///
+    /// The sender's DID and verkey are stored in the wallet as their DID, and the sender's
+    /// endpoint (if present) is stored for that DID, so the connection can be used right away.
+    /// Fails with CommonInvalidStructure if the sender's DID isn't derived from its verkey,
+    /// and with WalletItemAlreadyExists if the sender's DID is already stored with another verkey.
+    /// Parsing a request for a known DID with the same verkey again succeeds, the endpoint stored
+    /// for that DID is kept.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// my_vk: verkey the request was anoncrypted to. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+    /// request_raw: a pointer to first byte of the anoncrypted request.
+    /// request_len: the anoncrypted request length.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - request_json: the opened request, see indy_prepare_anoncrypted_connection_request.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_parse_connection_request(indy_handle_t     command_handle,
+                                                      indy_handle_t     wallet_handle,
+                                                      const char *const my_vk,
+                                                      const indy_u8_t*  request_raw,
+                                                      indy_u32_t        request_len,
+
+                                                      void              (*cb)(indy_handle_t     command_handle,
+                                                                              indy_error_t      err,
+                                                                              const char *const request_json)
+                                                     );
+
+    /// Re-encrypts a message authcrypted to my DID so it can be forwarded to another recipient.
+    ///
+    /// The message is decrypted with the key of my DID, checked to come from the expected sender
+    /// and anoncrypted to the new recipient's verkey. The decrypted message is never returned.
+    /// The new recipient opens the result with indy_crypto_anon_decrypt.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// my_did: my DID stored in the wallet, the message was authcrypted to its verkey.
+    /// sender_vk: verkey of the original sender of the message.
+    /// recipient_vk: verkey of the new recipient.
+    /// encrypted_msg_raw: a pointer to first byte of the authcrypted message.
+    /// encrypted_msg_len: the authcrypted message length.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - encrypted_msg_raw: a pointer to first byte of the anoncrypted message.
+    /// - encrypted_msg_len: the anoncrypted message length.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_re_encrypt(indy_handle_t     command_handle,
+                                        indy_handle_t     wallet_handle,
+                                        const char *const my_did,
+                                        const char *const sender_vk,
+                                        const char *const recipient_vk,
+                                        const indy_u8_t*  encrypted_msg_raw,
+                                        indy_u32_t        encrypted_msg_len,
+
+                                        void              (*cb)(indy_handle_t        command_handle,
+                                                                indy_error_t         err,
+                                                                const indy_u8_t*     encrypted_msg_raw,
+                                                                indy_u32_t           encrypted_msg_len)
+                                       );
+
     /// Saves/replaces the meta information for the giving DID in the wallet.
     ///
     /// #Params
@@ -401,6 +681,38 @@ extern "C" {
                                                     void          (*fn)(indy_handle_t command_handle_, indy_error_t err, const char *const dids)
                                                    );
 
+    /// Retrieves their DIDs stored in the wallet together with their endpoints.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// filter_json: (optional) filter to apply to the listing:
+    /// {
+    ///     "has_endpoint": Option<bool> - keep only DIDs with (true) or without (false) a stored endpoint
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   dids: array sorted by DID [{
+    ///     "did": string - their DID stored in the wallet,
+    ///     "verkey": string - their verkey,
+    ///     "endpoint": Option<{"ha": string, "verkey": Option<string>}> - endpoint stored for their DID
+    ///   }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_list_their_dids(indy_handle_t command_handle,
+                                             indy_handle_t wallet_handle,
+                                             const char *const filter_json,
+                                             void          (*fn)(indy_handle_t command_handle_, indy_error_t err, const char *const dids)
+                                            );
+
     /// Retrieves abbreviated verkey if it is possible otherwise return full verkey.
     ///
     /// #Params
@@ -427,6 +739,390 @@ extern "C" {
                                                                  const char *const verkey)
                                             );
 
+    /// Retrieves full verkey for the given DID and verkey in either abbreviated or full form.
+    /// Full verkey is returned as is.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// did: DID.
+    /// verkey: The DIDs verification key in either abbreviated or full form,
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   full_verkey: The DIDs verification key in full form
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_expand_verkey(indy_handle_t command_handle,
+                                           const char *const did,
+                                           const char *const verkey,
+                                           void          (*fn)(indy_handle_t command_handle_,
+                                                               indy_error_t err,
+                                                               const char *const full_verkey)
+                                          );
+
+    /// Derives DID from the full verkey without storing anything in the wallet.
+    /// DID is the base58 of the first 16 bytes of the verkey.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// verkey: The verification key in full form. Abbreviated verkey can't be used here.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   did: DID derived from the verkey
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_did_from_verkey(indy_handle_t command_handle,
+                                             const char *const verkey,
+                                             void          (*fn)(indy_handle_t command_handle_,
+                                                                 indy_error_t err,
+                                                                 const char *const did)
+                                            );
+
+    /// Computes a short fingerprint of the full verkey for display and out-of-band key confirmation.
+    /// The fingerprint is the hex of the first 8 bytes of SHA-256 over the raw verkey bytes,
+    /// so it is stable across runs and doesn't depend on a crypto type suffix.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// verkey: The verification key in full form. Abbreviated verkey can't be used here.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   fingerprint: 16 hex chars fingerprint of the verkey
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_compute_did_fingerprint(indy_handle_t command_handle,
+                                                     const char *const verkey,
+                                                     void          (*fn)(indy_handle_t command_handle_,
+                                                                         indy_error_t err,
+                                                                         const char *const fingerprint)
+                                                    );
+
+    /// Checks that a DID string is well-formed before it is passed to other functions.
+    ///
+    /// A DID is well-formed if it is a base58 encoded 16 or 32 byte number,
+    /// optionally prefixed with `did:<method>:`.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// did: DID string to check.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - result_json: {
+    ///     "valid": bool,
+    ///     "kind": Option<"short" | "full" | "qualified"> - null if the DID is malformed
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    extern indy_error_t indy_validate_did_string(indy_handle_t command_handle,
+                                                 const char *const did,
+                                                 void          (*fn)(indy_handle_t command_handle_,
+                                                                     indy_error_t err,
+                                                                     const char *const result_json)
+                                                );
+
+    /// Converts the full Ed25519 verkey to the Curve25519 public key used for encryption,
+    /// e.g. for interop with non-Indy systems. Nothing is read from or stored in the wallet.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// verkey: The verification key in full form. Abbreviated verkey can't be used here.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   curve25519_key: base58 encoded Curve25519 public key
+    ///
+    /// #Errors
+    /// Common*
+    /// Crypto*
+    extern indy_error_t indy_verkey_to_curve25519(indy_handle_t command_handle,
+                                                  const char *const verkey,
+                                                  void          (*fn)(indy_handle_t command_handle_,
+                                                                      indy_error_t err,
+                                                                      const char *const curve25519_key)
+                                                 );
+
+    /// Converts the signing key of a DID owned by the caller of the library to the
+    /// Curve25519 private key used for encryption. The Ed25519 signing key itself is never returned.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did: DID stored in the wallet.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///   curve25519_key: base58 encoded Curve25519 private key
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_signkey_to_curve25519(indy_handle_t command_handle,
+                                                   indy_handle_t wallet_handle,
+                                                   const char *const did,
+                                                   void          (*fn)(indy_handle_t command_handle_,
+                                                                       indy_error_t err,
+                                                                       const char *const curve25519_key)
+                                                  );
+
+    /// Removes a DID owned by the caller of the library from the wallet,
+    /// together with its metadata, endpoint and not yet applied temporary keys.
+    /// Keys stored for the DID stay in the wallet: they can still be used on their own,
+    /// for example by indy_crypto_sign, or by other DIDs created from the same seed.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did: DID stored in the wallet.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_forget_did(indy_handle_t command_handle,
+                                        indy_handle_t wallet_handle,
+                                        const char *  did,
+
+                                        void           (*cb)(indy_handle_t command_handle_,
+                                                             indy_error_t  err)
+                                       );
+
+    /// Exports the keys of a DID owned by the caller of the library in a portable form
+    /// to move the DID to another wallet (see indy_import_did_key).
+    ///
+    /// The exported data is encrypted with a key derived from the passphrase,
+    /// so signkey never leaves the wallet in plain form.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did: DID stored in the wallet.
+    /// passphrase: passphrase used to derive the encryption key.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - exported: base64 encoded encrypted DID key.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_export_did_key(indy_handle_t command_handle,
+                                            indy_handle_t wallet_handle,
+                                            const char *  did,
+                                            const char *  passphrase,
+
+                                            void           (*cb)(indy_handle_t command_handle_,
+                                                                 indy_error_t  err,
+                                                                 const char *const exported)
+                                           );
+
+    /// Imports DID keys exported by indy_export_did_key and stores the DID in the wallet
+    /// as owned by the caller of the library.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// passphrase: passphrase used on export.
+    /// exported: base64 encoded encrypted DID key returned by indy_export_did_key.
+    /// policy: (optional) what to do if the DID is already stored in the wallet:
+    ///     "fail" - return DidAlreadyExistsError (default)
+    ///     "overwrite" - replace stored verkey with the imported one and delete the replaced key
+    ///     "skip" - keep stored DID untouched
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - did: imported DID.
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    /// WalletAccessFailed if passphrase is wrong
+
+    extern indy_error_t indy_import_did_key(indy_handle_t command_handle,
+                                            indy_handle_t wallet_handle,
+                                            const char *  passphrase,
+                                            const char *  exported,
+                                            const char *  policy,
+
+                                            void           (*cb)(indy_handle_t command_handle_,
+                                                                 indy_error_t  err,
+                                                                 const char *const did)
+                                           );
+
+    /// Checks that keys of a DID owned by the caller of the library are consistent.
+    /// Intended for diagnostics of damaged wallets.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// did: DID stored in the wallet.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - report: json
+    ///   {
+    ///     "did": string, - checked DID
+    ///     "verkey_ok": bool, - stored signkey belongs to stored verkey
+    ///     "did_ok": bool, - DID is derived from the first 16 bytes of verkey
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+
+    extern indy_error_t indy_check_did_integrity(indy_handle_t command_handle,
+                                                 indy_handle_t wallet_handle,
+                                                 const char *  did,
+
+                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                      indy_error_t  err,
+                                                                      const char *const report)
+                                                );
+
+    /// Verifies a bundle of signatures made by several DIDs over the same message (m-of-n check).
+    ///
+    /// Every DID is resolved to its verkey from the wallet (my DIDs and their DIDs, see "indy_key_for_local_did").
+    /// DIDs that can't be resolved and malformed signatures count as invalid but don't abort verification.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: Wallet handle (created by open_wallet).
+    /// signatures_json: map of signer DIDs to base58 encoded signatures
+    ///     {
+    ///         "<did>": "<signature>",
+    ///         ...
+    ///     }
+    /// message_raw: a pointer to first byte of message that was signed
+    /// message_len: a message length. Empty message is accepted
+    /// threshold: minimal number of valid signatures by distinct keys (at least 1)
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - valid: true - if at least `threshold` signatures are valid, false - otherwise
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_verify_multi_did_signature(indy_handle_t      command_handle,
+                                                        indy_handle_t      wallet_handle,
+                                                        const char *       signatures_json,
+                                                        const indy_u8_t *  message_raw,
+                                                        indy_u32_t         message_len,
+                                                        indy_u32_t         threshold,
+
+                                                        void           (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err,
+                                                                             indy_bool_t   valid)
+                                                       );
+
+    /// Generates a random seed together with its BIP39 mnemonic (24 English words) for human-friendly backup.
+    /// The seed can be passed as "seed" to "indy_create_and_store_my_did" and recovered from the
+    /// mnemonic with "indy_seed_from_mnemonic".
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - mnemonic: 24 words mnemonic phrase.
+    /// - seed: 32 bytes seed as HEX string.
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_generate_mnemonic_seed(indy_handle_t command_handle,
+
+                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                         indy_error_t  err,
+                                                                         const char *const mnemonic,
+                                                                         const char *const seed)
+                                                   );
+
+    /// Reconstructs the seed from a BIP39 mnemonic (24 English words) generated by "indy_generate_mnemonic_seed".
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// mnemonic: 24 words mnemonic phrase.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error Code
+    /// cb:
+    /// - command_handle_: Command handle to map callback to caller context.
+    /// - err: Error code.
+    /// - seed: 32 bytes seed as HEX string.
+    ///
+    /// #Errors
+    /// Common* (CommonInvalidStructure for unknown words, wrong number of words or bad checksum)
+
+    extern indy_error_t indy_seed_from_mnemonic(indy_handle_t command_handle,
+                                                const char *  mnemonic,
+
+                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                     indy_error_t  err,
+                                                                     const char *const seed)
+                                               );
+
     /// Update DID stored in the wallet to make fully qualified, or to do other DID maintenance.
     ///     - If the DID has no prefix, a prefix will be appended (prepend did:peer to a legacy did)
     ///     - If the DID has a prefix, a prefix will be updated (migrate did:peer to did:peer-new)
@@ -186,6 +186,14 @@ extern "C" {
     ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
     ///         Capturing of backtrace can affect library performance.
     ///         NOTE: must be set before invocation of any other API functions.
+    ///     "strict_messages": Optional<bool> - whether signing or verifying a message that consists of
+    ///         whitespace only must fail. (false by default)
+    ///     "ledger_fallback_max_retries": Optional<int> - how many times a DID lookup that falls back to the ledger
+    ///         is resubmitted after the pool timed out. (2 by default)
+    ///     "ledger_fallback_base_delay_ms": Optional<int> - delay before the first resubmission in milliseconds,
+    ///         every next one waits twice as long. (1000 by default)
+    ///     "verkey_cache_ttl_secs": Optional<int> - how long a verkey resolved from the ledger is used
+    ///         without asking the pool again, in seconds. (300 by default)
     /// }
     ///
     /// #Errors
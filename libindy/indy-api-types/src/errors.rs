@@ -516,3 +516,65 @@ pub fn get_current_error_c_json() -> *const c_char {
 pub fn string_to_cstring(s: String) -> CString {
     CString::new(s).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indy_error_kind_display_works() {
+        assert_eq!("Invalid structure", IndyErrorKind::InvalidStructure.to_string());
+        assert_eq!("Unknown crypto", IndyErrorKind::UnknownCrypto.to_string());
+        assert_eq!("Wallet item not found", IndyErrorKind::WalletItemNotFound.to_string());
+        assert_eq!("IO error", IndyErrorKind::IOError.to_string());
+    }
+
+    #[test]
+    fn indy_error_display_works_for_msg() {
+        let err = IndyError::from_msg(IndyErrorKind::WalletItemNotFound, "DID not found");
+
+        assert_eq!(IndyErrorKind::WalletItemNotFound, err.kind());
+        assert_eq!("Error: Wallet item not found\n  Caused by: DID not found\n", err.to_string());
+    }
+
+    #[test]
+    fn to_indy_works_for_json_error() {
+        let res: IndyResult<serde_json::Value> = serde_json::from_str("{\"did\":")
+            .to_indy(IndyErrorKind::InvalidStructure, "Can't deserialize MyDidInfo");
+
+        let err = res.unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+        assert_eq!(ErrorCode::CommonInvalidStructure, ErrorCode::from(err.kind()));
+    }
+
+    #[test]
+    fn from_io_error_works() {
+        let err: IndyError = io::Error::new(io::ErrorKind::Other, "disk failure").into();
+
+        assert_eq!(IndyErrorKind::IOError, err.kind());
+        assert_eq!(ErrorCode::CommonIOError, ErrorCode::from(err.kind()));
+    }
+
+    #[test]
+    fn error_code_works_for_did_related_kinds() {
+        assert_eq!(113, ErrorCode::from(IndyErrorKind::InvalidStructure) as i32);
+        assert_eq!(114, ErrorCode::from(IndyErrorKind::IOError) as i32);
+        assert_eq!(112, ErrorCode::from(IndyErrorKind::InvalidState) as i32);
+        assert_eq!(200, ErrorCode::from(IndyErrorKind::InvalidWalletHandle) as i32);
+        assert_eq!(212, ErrorCode::from(IndyErrorKind::WalletItemNotFound) as i32);
+        assert_eq!(213, ErrorCode::from(IndyErrorKind::WalletItemAlreadyExists) as i32);
+        assert_eq!(500, ErrorCode::from(IndyErrorKind::UnknownCrypto) as i32);
+        assert_eq!(600, ErrorCode::from(IndyErrorKind::DIDAlreadyExists) as i32);
+    }
+
+    #[test]
+    fn error_code_round_trip_works_for_did_related_kinds() {
+        for kind in &[IndyErrorKind::InvalidStructure,
+                      IndyErrorKind::IOError,
+                      IndyErrorKind::WalletItemNotFound,
+                      IndyErrorKind::UnknownCrypto,
+                      IndyErrorKind::DIDAlreadyExists] {
+            assert_eq!(*kind, IndyErrorKind::from(ErrorCode::from(*kind)));
+        }
+    }
+}
@@ -36,6 +36,15 @@ pub fn derive_key(passphrase: &str, salt: &pwhash_argon2i13::Salt, key_derivatio
     Ok(Key::new(key_bytes))
 }
 
+pub fn derive_key_with_limits(passphrase: &str, salt: &pwhash_argon2i13::Salt, opslimit: u64, memlimit: usize) -> Result<Key, IndyError> {
+    let mut key_bytes = [0u8; chacha20poly1305_ietf::KEYBYTES];
+
+    pwhash_argon2i13::pwhash_with_limits(&mut key_bytes, passphrase.as_bytes(), salt, opslimit, memlimit)
+        .map_err(|err| err.extend("Can't derive key"))?;
+
+    Ok(Key::new(key_bytes))
+}
+
 pub fn gen_nonce() -> Nonce {
     Nonce(chacha20poly1305_ietf::gen_nonce())
 }
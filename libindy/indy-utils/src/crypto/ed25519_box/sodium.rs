@@ -2,6 +2,7 @@ extern crate sodiumoxide;
 
 use indy_api_types::errors::prelude::*;
 use self::sodiumoxide::crypto::box_;
+use self::sodiumoxide::crypto::scalarmult::curve25519;
 
 
 pub const NONCEBYTES: usize = box_::curve25519xsalsa20poly1305::NONCEBYTES;
@@ -35,6 +36,15 @@ pub fn gen_nonce() -> Nonce {
     Nonce(box_::gen_nonce())
 }
 
+pub fn derive_shared_secret(secret_key: &SecretKey, public_key: &PublicKey) -> Result<Vec<u8>, IndyError> {
+    let scalar = curve25519::Scalar((secret_key.0).0);
+    let group_element = curve25519::GroupElement((public_key.0).0);
+
+    curve25519::scalarmult(&scalar, &group_element)
+        .map(|shared_secret| shared_secret[..].to_vec())
+        .map_err(|_| IndyError::from_msg(IndyErrorKind::InvalidStructure, "Unable to derive shared secret from low order public key"))
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -42,6 +52,16 @@ mod tests {
     use crate::crypto::ed25519_sign;
     use crate::crypto::randombytes::randombytes;
 
+    #[test]
+    fn gen_nonce_works() {
+        let nonce_1 = gen_nonce();
+        let nonce_2 = gen_nonce();
+
+        assert_eq!(24, NONCEBYTES);
+        assert_eq!(NONCEBYTES, nonce_1[..].len());
+        assert_ne!(nonce_1, nonce_2);
+    }
+
     #[test]
     fn encrypt_decrypt_works() {
         let text = randombytes(16);
@@ -66,4 +86,30 @@ mod tests {
         assert!(alice_decrypted_text.is_ok());
         assert_eq!(text, alice_decrypted_text.unwrap());
     }
+
+    #[test]
+    fn derive_shared_secret_works_in_both_directions() {
+        let alice_seed = ed25519_sign::Seed::from_slice(&randombytes(32)).unwrap();
+        let bob_seed = ed25519_sign::Seed::from_slice(&randombytes(32)).unwrap();
+
+        let (alice_ver_key, alice_sign_key) = ed25519_sign::create_key_pair_for_signature(Some(&alice_seed)).unwrap();
+        let (bob_ver_key, bob_sign_key) = ed25519_sign::create_key_pair_for_signature(Some(&bob_seed)).unwrap();
+
+        let alice_secret = derive_shared_secret(&ed25519_sign::sk_to_curve25519(&alice_sign_key).unwrap(),
+                                                &ed25519_sign::vk_to_curve25519(&bob_ver_key).unwrap()).unwrap();
+        let bob_secret = derive_shared_secret(&ed25519_sign::sk_to_curve25519(&bob_sign_key).unwrap(),
+                                              &ed25519_sign::vk_to_curve25519(&alice_ver_key).unwrap()).unwrap();
+
+        assert_eq!(32, alice_secret.len());
+        assert_eq!(alice_secret, bob_secret);
+    }
+
+    #[test]
+    fn derive_shared_secret_not_works_for_zero_public_key() {
+        let seed = ed25519_sign::Seed::from_slice(&randombytes(32)).unwrap();
+        let (_, sign_key) = ed25519_sign::create_key_pair_for_signature(Some(&seed)).unwrap();
+
+        let res = derive_shared_secret(&ed25519_sign::sk_to_curve25519(&sign_key).unwrap(), &PublicKey::new([0; PUBLICKEYBYTES]));
+        assert!(res.is_err());
+    }
 }
@@ -75,6 +75,17 @@ pub fn vk_to_curve25519(pk: &PublicKey) -> Result<ed25519_box::PublicKey, IndyEr
     ed25519_box::PublicKey::from_slice(&to)
 }
 
+pub fn validate_key(pk: &PublicKey) -> Result<(), IndyError> {
+    let mut to: [u8; ENC_PUBLICKEYBYTES] = [0; ENC_PUBLICKEYBYTES];
+    let res = unsafe {
+        crypto_sign_ed25519_pk_to_curve25519(&mut to, &(pk.0).0)
+    };
+    if res != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid verkey: not a valid Ed25519 point"));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,6 +103,31 @@ mod tests {
         assert!(verified);
     }
 
+    #[test]
+    fn create_key_pair_for_signature_works_for_same_seed() {
+        let seed = Seed::from_slice(&[1u8; SEEDBYTES]).unwrap();
+
+        let (public_key_1, secret_key_1) = create_key_pair_for_signature(Some(&seed)).unwrap();
+        let (public_key_2, secret_key_2) = create_key_pair_for_signature(Some(&seed)).unwrap();
+
+        assert_eq!(public_key_1, public_key_2);
+        assert_eq!(secret_key_1, secret_key_2);
+    }
+
+    #[test]
+    fn create_key_pair_for_signature_works_for_different_seeds() {
+        let (public_key_1, _) = create_key_pair_for_signature(Some(&Seed::from_slice(&[1u8; SEEDBYTES]).unwrap())).unwrap();
+        let (public_key_2, _) = create_key_pair_for_signature(Some(&Seed::from_slice(&[2u8; SEEDBYTES]).unwrap())).unwrap();
+
+        assert_ne!(public_key_1, public_key_2);
+    }
+
+    #[test]
+    fn seed_from_slice_fails_for_invalid_len() {
+        assert!(Seed::from_slice(&[1u8; SEEDBYTES - 1]).is_err());
+        assert!(Seed::from_slice(&[1u8; SEEDBYTES + 1]).is_err());
+    }
+
     #[test]
     fn pk_to_curve25519_works() {
         let pk = vec!(236, 191, 114, 144, 108, 87, 211, 244, 148, 23, 20, 175, 122, 6, 159, 254, 85, 99, 145, 152, 178, 133, 230, 236, 192, 69, 35, 136, 141, 194, 243, 134);
@@ -111,4 +147,16 @@ mod tests {
         let skc_exp = ed25519_box::SecretKey::from_slice(&skc_exp).unwrap();
         assert_eq!(skc_exp, skc_test);
     }
+
+    #[test]
+    fn validate_key_works() {
+        let (pk, _) = create_key_pair_for_signature(None).unwrap();
+        validate_key(&pk).unwrap();
+    }
+
+    #[test]
+    fn validate_key_fails_for_not_a_point() {
+        let pk = PublicKey::from_slice(&[2u8; SIG_PUBLICKEYBYTES]).unwrap();
+        assert!(validate_key(&pk).is_err());
+    }
 }
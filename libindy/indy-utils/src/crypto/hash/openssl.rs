@@ -14,6 +14,11 @@ pub fn hash(input: &[u8]) -> Result<Vec<u8>, IndyError> {
     Ok(hasher.finish().map(|b| b.to_vec())?)
 }
 
+// Compares digests without leaking the position of the first mismatch through timing
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && openssl::memcmp::eq(a, b)
+}
+
 pub struct Hash {}
 
 impl Hash {
@@ -70,4 +75,31 @@ impl<T: AsRef<[u8]>> Hashable for T {
             .update(self.as_ref())
             .to_indy(IndyErrorKind::InvalidState, "Internal OpenSSL error")
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_works_for_empty_input() {
+        assert_eq!(EMPTY_HASH_BYTES.to_vec(), hash(&[]).unwrap());
+    }
+
+    #[test]
+    fn constant_time_eq_works() {
+        assert!(constant_time_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(constant_time_eq(&[], &[]));
+    }
+
+    #[test]
+    fn constant_time_eq_works_for_different_bytes() {
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2, 4]));
+    }
+
+    #[test]
+    fn constant_time_eq_works_for_different_len() {
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2]));
+        assert!(!constant_time_eq(&[], &[1]));
+    }
+}
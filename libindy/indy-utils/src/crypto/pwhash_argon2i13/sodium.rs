@@ -14,6 +14,14 @@ pub fn gen_salt() -> Salt {
     Salt(pwhash::gen_salt())
 }
 
+pub fn opslimit_interactive() -> u64 {
+    unsafe { crypto_pwhash_argon2i_opslimit_interactive() as u64 }
+}
+
+pub fn memlimit_interactive() -> usize {
+    unsafe { crypto_pwhash_argon2i_memlimit_interactive() }
+}
+
 pub fn pwhash<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, key_derivation_method: &KeyDerivationMethod) -> Result<&'a [u8], IndyError> {
     let (opslimit, memlimit) = unsafe {
         match key_derivation_method {
@@ -23,6 +31,10 @@ pub fn pwhash<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, key_derivation_
         }
     };
 
+    pwhash_with_limits(key, passwd, salt, opslimit as u64, memlimit)
+}
+
+pub fn pwhash_with_limits<'a>(key: &'a mut [u8], passwd: &[u8], salt: &Salt, opslimit: u64, memlimit: usize) -> Result<&'a [u8], IndyError> {
     let alg = unsafe { crypto_pwhash_alg_argon2i13() };
 
     let res = unsafe {
@@ -105,4 +117,19 @@ mod tests {
 
         assert_ne!(key_moderate, key_interactive);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn pwhash_with_limits_works_for_interactive_limits() {
+        let passwd = b"Correct Horse Battery Staple";
+
+        let salt = gen_salt();
+
+        let mut key = [0u8; 64];
+        let key_interactive = pwhash(&mut key, passwd, &salt, &KeyDerivationMethod::ARGON2I_INT).unwrap().to_vec();
+
+        let mut key = [0u8; 64];
+        let key_limits = pwhash_with_limits(&mut key, passwd, &salt, opslimit_interactive(), memlimit_interactive()).unwrap();
+
+        assert_eq!(key_interactive.as_slice(), key_limits);
+    }
+}
@@ -218,6 +218,22 @@ macro_rules! check_useful_c_byte_array {
     }
 }
 
+// Zero length is accepted and doesn't touch the pointer, so C callers may pass NULL for an empty array.
+#[macro_export]
+macro_rules! check_useful_c_possibly_empty_byte_array {
+    ($ptr:ident, $len:expr, $err1:expr) => {
+        let $ptr = if $len == 0 {
+            Vec::new()
+        } else {
+            if $ptr.is_null() {
+                return err_msg($err1.into(), "Invalid pointer has been passed").into();
+            }
+
+            unsafe { ::std::slice::from_raw_parts($ptr, $len as usize) }.to_vec()
+        };
+    }
+}
+
 //Returnable pointer is valid only before first vector modification
 pub fn vec_to_pointer(v: &Vec<u8>) -> (*const u8, u32) {
     let len = v.len() as u32;
@@ -10,7 +10,7 @@ use indy_api_types::domain::wallet::KeyDerivationMethod;
 use indy_api_types::errors::prelude::*;
 use crate::encryption::KeyDerivationData;
 use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
-use indy_utils::crypto::hash::{hash, HASHBYTES};
+use indy_utils::crypto::hash::{constant_time_eq, hash, HASHBYTES};
 
 use super::{Wallet, WalletRecord};
 
@@ -207,7 +207,7 @@ pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chach
     let mut header_hash = vec![0u8; HASHBYTES];
     reader.read_exact(&mut header_hash).map_err(_map_io_err)?;
 
-    if hash(&header_bytes)? != header_hash {
+    if !constant_time_eq(&hash(&header_bytes)?, &header_hash) {
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header hash"));
     }
 
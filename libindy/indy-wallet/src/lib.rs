@@ -50,6 +50,14 @@ pub struct WalletService {
     wallet_ids: RefCell<HashSet<String>>,
     pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
     pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    transactions: RefCell<HashMap<WalletHandle, Vec<UndoAction>>>,
+}
+
+// Reverts a single write made inside a transaction. Journaling the inverse of each write keeps
+// transactions independent of the storage, so plugged storages get them for free.
+enum UndoAction {
+    Delete { type_: String, name: String },
+    Restore { type_: String, name: String, value: String, tags: Tags },
 }
 
 impl WalletService {
@@ -66,6 +74,7 @@ impl WalletService {
             wallet_ids: RefCell::new(HashSet::new()),
             pending_for_open: RefCell::new(HashMap::new()),
             pending_for_import: RefCell::new(HashMap::new()),
+            transactions: RefCell::new(HashMap::new()),
         }
     }
 
@@ -247,6 +256,7 @@ impl WalletService {
         match self.wallets.borrow_mut().remove(&handle) {
             Some(mut wallet) => {
                 self.wallet_ids.borrow_mut().remove(wallet.get_id());
+                self.transactions.borrow_mut().remove(&handle);
                 wallet.close()
             },
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
@@ -266,8 +276,12 @@ impl WalletService {
 
     pub fn add_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str, value: &str, tags: &Tags) -> IndyResult<()> {
         match self.wallets.borrow_mut().get_mut(&wallet_handle) {
-            Some(wallet) => wallet.add(type_, name, value, tags)
-                .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
+            Some(wallet) => {
+                wallet.add(type_, name, value, tags)
+                    .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name))?;
+                self._journal(wallet_handle, UndoAction::Delete { type_: type_.to_string(), name: name.to_string() });
+                Ok(())
+            }
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
         }
     }
@@ -289,7 +303,7 @@ impl WalletService {
     pub fn update_record_value(&self, wallet_handle: WalletHandle, type_: &str, name: &str, value: &str) -> IndyResult<()> {
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) =>
-                wallet.update(type_, name, value)
+                self._journaled(wallet_handle, wallet, type_, name, || wallet.update(type_, name, value))
                     .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
         }
@@ -301,7 +315,8 @@ impl WalletService {
             Some(wallet) => {
                 let object_json = serde_json::to_string(object)
                     .to_indy(IndyErrorKind::InvalidState, format!("Cannot serialize {:?}", type_))?;
-                wallet.update(&self.add_prefix(type_), name, &object_json)?;
+                let type_ = self.add_prefix(type_);
+                self._journaled(wallet_handle, wallet, &type_, name, || wallet.update(&type_, name, &object_json))?;
                 Ok(object_json)
             }
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
@@ -310,7 +325,7 @@ impl WalletService {
 
     pub fn add_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
         match self.wallets.borrow_mut().get_mut(&wallet_handle) {
-            Some(wallet) => wallet.add_tags(type_, name, tags)
+            Some(wallet) => self._journaled(wallet_handle, wallet, type_, name, || wallet.add_tags(type_, name, tags))
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
         }
@@ -318,7 +333,7 @@ impl WalletService {
 
     pub fn update_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
         match self.wallets.borrow_mut().get_mut(&wallet_handle) {
-            Some(wallet) => wallet.update_tags(type_, name, tags)
+            Some(wallet) => self._journaled(wallet_handle, wallet, type_, name, || wallet.update_tags(type_, name, tags))
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
         }
@@ -326,7 +341,7 @@ impl WalletService {
 
     pub fn delete_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tag_names: &[&str]) -> IndyResult<()> {
         match self.wallets.borrow().get(&wallet_handle) {
-            Some(wallet) => wallet.delete_tags(type_, name, tag_names)
+            Some(wallet) => self._journaled(wallet_handle, wallet, type_, name, || wallet.delete_tags(type_, name, tag_names))
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
         }
@@ -334,7 +349,7 @@ impl WalletService {
 
     pub fn delete_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str) -> IndyResult<()> {
         match self.wallets.borrow().get(&wallet_handle) {
-            Some(wallet) => wallet.delete(type_, name)
+            Some(wallet) => self._journaled(wallet_handle, wallet, type_, name, || wallet.delete(type_, name))
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
         }
@@ -436,6 +451,127 @@ impl WalletService {
         }
     }
 
+    pub fn begin_transaction(&self, wallet_handle: WalletHandle) -> IndyResult<()> {
+        trace!("begin_transaction >>> wallet_handle: {:?}", wallet_handle);
+
+        self.check(wallet_handle)?;
+
+        let mut transactions = self.transactions.borrow_mut();
+
+        if transactions.contains_key(&wallet_handle) {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Wallet transaction is already started"));
+        }
+
+        transactions.insert(wallet_handle, Vec::new());
+
+        trace!("begin_transaction <<<");
+        Ok(())
+    }
+
+    pub fn commit_transaction(&self, wallet_handle: WalletHandle) -> IndyResult<()> {
+        trace!("commit_transaction >>> wallet_handle: {:?}", wallet_handle);
+
+        self.transactions.borrow_mut().remove(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No wallet transaction is started"))?;
+
+        trace!("commit_transaction <<<");
+        Ok(())
+    }
+
+    pub fn rollback_transaction(&self, wallet_handle: WalletHandle) -> IndyResult<()> {
+        trace!("rollback_transaction >>> wallet_handle: {:?}", wallet_handle);
+
+        let journal = self.transactions.borrow_mut().remove(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No wallet transaction is started"))?;
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets.get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        // Keep undoing after a failure so as much state as possible is restored, report the first error
+        let mut res = Ok(());
+
+        for action in journal.into_iter().rev() {
+            if let Err(err) = WalletService::_undo(wallet, action) {
+                warn!("rollback_transaction: unable to undo wallet write: {:?}", err);
+                if res.is_ok() {
+                    res = Err(err);
+                }
+            }
+        }
+
+        trace!("rollback_transaction <<< res: {:?}", res);
+        res
+    }
+
+    /// Runs `f` in a transaction: writes made by `f` are kept if it succeeds and rolled back if it fails.
+    pub fn transaction<T, F>(&self, wallet_handle: WalletHandle, f: F) -> IndyResult<T> where F: FnOnce() -> IndyResult<T> {
+        self.begin_transaction(wallet_handle)?;
+
+        match f() {
+            Ok(res) => {
+                self.commit_transaction(wallet_handle)?;
+                Ok(res)
+            }
+            Err(err) => {
+                if let Err(rollback_err) = self.rollback_transaction(wallet_handle) {
+                    error!("transaction: rollback failed, wallet may contain partial state: {:?}", rollback_err);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    fn _journal(&self, wallet_handle: WalletHandle, action: UndoAction) {
+        if let Some(journal) = self.transactions.borrow_mut().get_mut(&wallet_handle) {
+            journal.push(action);
+        }
+    }
+
+    // Snapshots the record before `write` touches it, so the write can be reverted on rollback.
+    fn _journaled<F>(&self, wallet_handle: WalletHandle, wallet: &Wallet, type_: &str, name: &str, write: F) -> IndyResult<()> where F: FnOnce() -> IndyResult<()> {
+        if !self.transactions.borrow().contains_key(&wallet_handle) {
+            return write();
+        }
+
+        let record = match wallet.get(type_, name, &RecordOptions::id_value_tags()) {
+            Ok(record) => Some(record),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => None,
+            Err(err) => return Err(err)
+        };
+
+        write()?;
+
+        if let Some(record) = record {
+            self._journal(wallet_handle, UndoAction::Restore {
+                type_: type_.to_string(),
+                name: name.to_string(),
+                value: record.value.unwrap_or_default(),
+                tags: record.tags.unwrap_or_default(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn _undo(wallet: &Wallet, action: UndoAction) -> IndyResult<()> {
+        let (type_, name) = match action {
+            UndoAction::Delete { ref type_, ref name } | UndoAction::Restore { ref type_, ref name, .. } => (type_.clone(), name.clone())
+        };
+
+        match wallet.delete(&type_, &name) {
+            Ok(()) => {}
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {}
+            Err(err) => return Err(err)
+        }
+
+        if let UndoAction::Restore { value, tags, .. } = action {
+            wallet.add(&type_, &name, &value, &tags)?;
+        }
+
+        Ok(())
+    }
+
     pub fn export_wallet(&self, wallet_handle: WalletHandle, export_config: &ExportConfig, version: u32, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
         trace!("export_wallet >>> wallet_handle: {:?}, export_config: {:?}, version: {:?}", wallet_handle, secret!(export_config), version);
 
@@ -735,6 +871,16 @@ impl RecordOptions {
 
         serde_json::to_string(&options).unwrap()
     }
+
+    fn id_value_tags() -> String {
+        let options = RecordOptions {
+            retrieve_type: false,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        serde_json::to_string(&options).unwrap()
+    }
 }
 
 impl Default for RecordOptions {
@@ -1283,6 +1429,35 @@ mod tests {
         test::cleanup_wallet("wallet_service_close_wallet_returns_appropriate_error_if_wrong_handle");
     }
 
+    #[test]
+    fn wallet_service_close_wallet_works_for_stale_handle() {
+        test::cleanup_wallet("wallet_service_close_wallet_works_for_stale_handle");
+        {
+            let config: &Config = &_config("wallet_service_close_wallet_works_for_stale_handle");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let res = wallet_service.check(wallet_handle);
+            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+
+            let res = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, false));
+            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+
+            let res = wallet_service.close_wallet(wallet_handle);
+            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+
+            let new_wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+            assert_ne!(wallet_handle, new_wallet_handle);
+            wallet_service.close_wallet(new_wallet_handle).unwrap();
+
+            wallet_service.delete_wallet(config, &RAW_CREDENTIAL).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_close_wallet_works_for_stale_handle");
+    }
+
     #[test]
     fn wallet_service_add_record_works() {
         test::cleanup_wallet("wallet_service_add_record_works");
@@ -1519,6 +1694,113 @@ mod tests {
         assert_eq!(new_value, record.get_value().unwrap());
     }
 
+    /**
+     * Transaction tests
+    */
+    fn _multi_record_write_with_failure(wallet_service: &WalletService, wallet_handle: WalletHandle) {
+        let tags: Tags = serde_json::from_str(r#"{"tag1": "value1"}"#).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "updated", "value", &tags).unwrap();
+        wallet_service.add_record(wallet_handle, "type", "deleted", "value", &tags).unwrap();
+
+        let res = wallet_service.transaction(wallet_handle, || {
+            wallet_service.add_record(wallet_handle, "type", "added", "value", &HashMap::new())?;
+            wallet_service.update_record_value(wallet_handle, "type", "updated", "new_value")?;
+            wallet_service.update_record_tags(wallet_handle, "type", "updated", &HashMap::new())?;
+            wallet_service.delete_record(wallet_handle, "type", "deleted")?;
+            // injected failure: the record was added above
+            wallet_service.add_record(wallet_handle, "type", "added", "value", &HashMap::new())
+        });
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+        let res = wallet_service.get_record(wallet_handle, "type", "added", &_fetch_options(false, true, false));
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let record = wallet_service.get_record(wallet_handle, "type", "updated", &_fetch_options(false, true, true)).unwrap();
+        assert_eq!("value", record.get_value().unwrap());
+        assert_eq!(&tags, record.get_tags().unwrap());
+
+        let record = wallet_service.get_record(wallet_handle, "type", "deleted", &_fetch_options(false, true, true)).unwrap();
+        assert_eq!("value", record.get_value().unwrap());
+        assert_eq!(&tags, record.get_tags().unwrap());
+    }
+
+    #[test]
+    fn wallet_service_transaction_rolls_back_on_failure() {
+        test::cleanup_wallet("wallet_service_transaction_rolls_back_on_failure");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_transaction_rolls_back_on_failure"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_transaction_rolls_back_on_failure"), &RAW_CREDENTIAL).unwrap();
+
+            _multi_record_write_with_failure(&wallet_service, wallet_handle);
+        }
+        test::cleanup_wallet("wallet_service_transaction_rolls_back_on_failure");
+    }
+
+    #[test]
+    fn wallet_service_transaction_rolls_back_on_failure_for_plugged() {
+        _cleanup("wallet_service_transaction_rolls_back_on_failure_for_plugged");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        _multi_record_write_with_failure(&wallet_service, wallet_handle);
+    }
+
+    #[test]
+    fn wallet_service_transaction_keeps_writes_on_success() {
+        test::cleanup_wallet("wallet_service_transaction_keeps_writes_on_success");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_transaction_keeps_writes_on_success"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_transaction_keeps_writes_on_success"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.transaction(wallet_handle, || {
+                wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new())?;
+                wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new())
+            }).unwrap();
+
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key2", "{}").unwrap();
+
+            // nothing is left to roll back once the transaction is committed
+            let res = wallet_service.rollback_transaction(wallet_handle);
+            assert_kind!(IndyErrorKind::InvalidState, res);
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_transaction_keeps_writes_on_success");
+    }
+
+    #[test]
+    fn wallet_service_begin_transaction_works_for_started_transaction() {
+        test::cleanup_wallet("wallet_service_begin_transaction_works_for_started_transaction");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_begin_transaction_works_for_started_transaction"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_begin_transaction_works_for_started_transaction"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.begin_transaction(wallet_handle).unwrap();
+            let res = wallet_service.begin_transaction(wallet_handle);
+            assert_kind!(IndyErrorKind::InvalidState, res);
+
+            wallet_service.commit_transaction(wallet_handle).unwrap();
+            let res = wallet_service.commit_transaction(wallet_handle);
+            assert_kind!(IndyErrorKind::InvalidState, res);
+        }
+        test::cleanup_wallet("wallet_service_begin_transaction_works_for_started_transaction");
+    }
+
+    #[test]
+    fn wallet_service_begin_transaction_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.begin_transaction(INVALID_WALLET_HANDLE);
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
     /**
      * Delete tests
     */
@@ -1788,6 +2070,128 @@ mod tests {
         assert!(search.fetch_next_record().unwrap().is_none());
     }
 
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct MyTestObject {
+        value: String
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct TheirTestObject {
+        value: String
+    }
+
+    #[test]
+    fn wallet_service_indy_objects_works_for_same_name_different_types() {
+        test::cleanup_wallet("wallet_service_indy_objects_works_for_same_name_different_types");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_indy_objects_works_for_same_name_different_types"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_indy_objects_works_for_same_name_different_types"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_indy_object(wallet_handle, "key1", &MyTestObject { value: "my".to_string() }, &HashMap::new()).unwrap();
+            wallet_service.add_indy_object(wallet_handle, "key1", &TheirTestObject { value: "their".to_string() }, &HashMap::new()).unwrap();
+
+            let my_object: MyTestObject = wallet_service.get_indy_object(wallet_handle, "key1", &RecordOptions::id_value()).unwrap();
+            let their_object: TheirTestObject = wallet_service.get_indy_object(wallet_handle, "key1", &RecordOptions::id_value()).unwrap();
+
+            assert_eq!(MyTestObject { value: "my".to_string() }, my_object);
+            assert_eq!(TheirTestObject { value: "their".to_string() }, their_object);
+        }
+        test::cleanup_wallet("wallet_service_indy_objects_works_for_same_name_different_types");
+    }
+
+    #[test]
+    fn wallet_service_get_indy_object_works_for_not_found() {
+        test::cleanup_wallet("wallet_service_get_indy_object_works_for_not_found");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_get_indy_object_works_for_not_found"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_get_indy_object_works_for_not_found"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_indy_object(wallet_handle, "key1", &MyTestObject { value: "my".to_string() }, &HashMap::new()).unwrap();
+
+            let res = wallet_service.get_indy_object::<TheirTestObject>(wallet_handle, "key1", &RecordOptions::id_value());
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = wallet_service.get_indy_opt_object::<TheirTestObject>(wallet_handle, "key1", &RecordOptions::id_value()).unwrap();
+            assert!(res.is_none());
+        }
+        test::cleanup_wallet("wallet_service_get_indy_object_works_for_not_found");
+    }
+
+    #[test]
+    fn wallet_service_upsert_indy_object_works() {
+        test::cleanup_wallet("wallet_service_upsert_indy_object_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_upsert_indy_object_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_upsert_indy_object_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.upsert_indy_object(wallet_handle, "key1", &MyTestObject { value: "value1".to_string() }).unwrap();
+            wallet_service.upsert_indy_object(wallet_handle, "key1", &MyTestObject { value: "value2".to_string() }).unwrap();
+
+            let object: MyTestObject = wallet_service.get_indy_object(wallet_handle, "key1", &RecordOptions::id_value()).unwrap();
+            assert_eq!(MyTestObject { value: "value2".to_string() }, object);
+        }
+        test::cleanup_wallet("wallet_service_upsert_indy_object_works");
+    }
+
+    #[test]
+    fn wallet_service_delete_indy_record_works() {
+        test::cleanup_wallet("wallet_service_delete_indy_record_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_delete_indy_record_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_delete_indy_record_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_indy_object(wallet_handle, "key1", &MyTestObject { value: "my".to_string() }, &HashMap::new()).unwrap();
+            wallet_service.add_indy_object(wallet_handle, "key1", &TheirTestObject { value: "their".to_string() }, &HashMap::new()).unwrap();
+
+            wallet_service.delete_indy_record::<MyTestObject>(wallet_handle, "key1").unwrap();
+
+            assert!(!wallet_service.record_exists::<MyTestObject>(wallet_handle, "key1").unwrap());
+            assert!(wallet_service.record_exists::<TheirTestObject>(wallet_handle, "key1").unwrap());
+        }
+        test::cleanup_wallet("wallet_service_delete_indy_record_works");
+    }
+
+    #[test]
+    fn wallet_service_delete_indy_record_works_for_not_found() {
+        test::cleanup_wallet("wallet_service_delete_indy_record_works_for_not_found");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_delete_indy_record_works_for_not_found"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_delete_indy_record_works_for_not_found"), &RAW_CREDENTIAL).unwrap();
+
+            let res = wallet_service.delete_indy_record::<MyTestObject>(wallet_handle, "key1");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_delete_indy_record_works_for_not_found");
+    }
+
+    #[test]
+    fn wallet_service_search_indy_records_works_for_type() {
+        test::cleanup_wallet("wallet_service_search_indy_records_works_for_type");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_indy_records_works_for_type"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_indy_records_works_for_type"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_indy_object(wallet_handle, "key1", &MyTestObject { value: "my1".to_string() }, &HashMap::new()).unwrap();
+            wallet_service.add_indy_object(wallet_handle, "key2", &MyTestObject { value: "my2".to_string() }, &HashMap::new()).unwrap();
+            wallet_service.add_indy_object(wallet_handle, "key3", &TheirTestObject { value: "their".to_string() }, &HashMap::new()).unwrap();
+
+            let mut search = wallet_service.search_indy_records::<TheirTestObject>(wallet_handle, "{}", &_fetch_options(true, true, false)).unwrap();
+
+            let record = search.fetch_next_record().unwrap().unwrap();
+            assert_eq!("key3", record.get_id());
+            assert_eq!(json!({"value": "their"}).to_string(), record.get_value().unwrap());
+
+            assert!(search.fetch_next_record().unwrap().is_none());
+        }
+        test::cleanup_wallet("wallet_service_search_indy_records_works_for_type");
+    }
+
     /**
         Key rotation test
     */
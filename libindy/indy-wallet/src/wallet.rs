@@ -326,6 +326,35 @@ mod tests {
         test::cleanup_wallet("wallet_add_works_for_already_existing");
     }
 
+    #[test]
+    fn wallet_add_works_for_encrypting_signkey_at_rest() {
+        test::cleanup_wallet("wallet_add_works_for_encrypting_signkey_at_rest");
+        {
+            let mut wallet = _wallet("wallet_add_works_for_encrypting_signkey_at_rest");
+
+            let signkey = "xt19s1sp2UZCGhy9rNyb1FtxdKiDGZZPNFnc1KiM9jYYEuHxuwNeFf1oQKsn8zv6yvYBGhXa83288eF4MqN1oDq";
+            let value = jsonstr!({"verkey": "Gs6r7zL6k3sBqCdKrSUNdc8SnFrgNWNJ3Pn6zPUmMbZd", "signkey": signkey});
+
+            wallet.add("Indy::Key", "Gs6r7zL6k3sBqCdKrSUNdc8SnFrgNWNJ3Pn6zPUmMbZd", &value, &HashMap::new()).unwrap();
+
+            let encrypted_type = encrypt_as_searchable("Indy::Key".as_bytes(), &wallet.keys.type_key, &wallet.keys.item_hmac_key);
+            let encrypted_name = encrypt_as_searchable("Gs6r7zL6k3sBqCdKrSUNdc8SnFrgNWNJ3Pn6zPUmMbZd".as_bytes(), &wallet.keys.name_key, &wallet.keys.item_hmac_key);
+
+            let raw_record = wallet.storage.get(&encrypted_type, &encrypted_name, &_fetch_options(false, true, false)).unwrap();
+            let raw_value = raw_record.value.unwrap();
+
+            assert_ne!(value.as_bytes().to_vec(), raw_value.data);
+            assert!(!raw_value.data.windows(signkey.len()).any(|window| window == signkey.as_bytes()));
+            assert_ne!("Gs6r7zL6k3sBqCdKrSUNdc8SnFrgNWNJ3Pn6zPUmMbZd".as_bytes().to_vec(), raw_record.id);
+
+            let record = wallet.get("Indy::Key", "Gs6r7zL6k3sBqCdKrSUNdc8SnFrgNWNJ3Pn6zPUmMbZd", &_fetch_options(false, true, false)).unwrap();
+            assert_eq!(value, record.value.unwrap());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_add_works_for_encrypting_signkey_at_rest");
+    }
+
     #[test]
     fn wallet_update_works() {
         test::cleanup_wallet("wallet_update_works");
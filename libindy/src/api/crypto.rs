@@ -173,7 +173,7 @@ pub  extern fn indy_get_key_metadata(command_handle: CommandHandle,
 /// wallet_handle: wallet handler (created by open_wallet).
 /// signer_vk: id (verkey) of message signer. The key must be created by calling indy_create_key or indy_create_and_store_my_did
 /// message_raw: a pointer to first byte of message to be signed
-/// message_len: a message length
+/// message_len: a message length. Empty message is accepted
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
@@ -197,7 +197,7 @@ pub  extern fn indy_crypto_sign(command_handle: CommandHandle,
            wallet_handle, signer_vk, message_raw, message_len);
 
     check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam3);
-    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_possibly_empty_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
     trace!("indy_crypto_sign: entities >>> wallet_handle: {:?}, signer_vk: {:?}, message_raw: {:?}, message_len: {:?}",
@@ -223,6 +223,119 @@ pub  extern fn indy_crypto_sign(command_handle: CommandHandle,
     res
 }
 
+/// Signs several messages with the same key.
+///
+/// The signer key is loaded from the wallet once for the whole batch.
+/// A message that can't be decoded doesn't abort the batch, its slot in the result reports the error.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// signer_vk: id (verkey) of message signer. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// messages_json: json array of base64 encoded messages to be signed. Example:
+///   ["bWVzc2FnZTE=", "bWVzc2FnZTI="]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// signatures_json: json array with one entry per message, in the same order as messages:
+///   [{"ok": "<base58 signature>"}, {"err": {"code": <error code>, "message": "<string>"}}]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_sign_multiple(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         signer_vk: *const c_char,
+                                         messages_json: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              signatures_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_sign_multiple: >>> wallet_handle: {:?}, signer_vk: {:?}, messages_json: {:?}",
+           wallet_handle, signer_vk, messages_json);
+
+    check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(messages_json, ErrorCode::CommonInvalidParam4, Vec<String>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_sign_multiple: entities >>> wallet_handle: {:?}, signer_vk: {:?}, messages_json: {:?}",
+           wallet_handle, signer_vk, messages_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoSignMultiple(
+            wallet_handle,
+            signer_vk,
+            messages_json,
+            boxed_callback_string!("indy_crypto_sign_multiple", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_sign_multiple: <<< res: {:?}", res);
+
+    res
+}
+
+/// Signs a message with several keys.
+///
+/// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
+/// for specific DID.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// signer_vks_json: json array of ids (verkeys) of message signers. The keys must be created by calling indy_create_key or indy_create_and_store_my_did
+/// message_raw: a pointer to first byte of message to be signed
+/// message_len: a message length. Empty message is accepted
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// signatures_json: json object that maps each signer verkey to its base58 encoded signature. Example:
+///   {"<verkey1>": "<signature1>", "<verkey2>": "<signature2>"}
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_multi_sign(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      signer_vks_json: *const c_char,
+                                      message_raw: *const u8,
+                                      message_len: u32,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode,
+                                                           signatures_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_multi_sign: >>> wallet_handle: {:?}, signer_vks_json: {:?}, message_raw: {:?}, message_len: {:?}",
+           wallet_handle, signer_vks_json, message_raw, message_len);
+
+    check_useful_json!(signer_vks_json, ErrorCode::CommonInvalidParam3, Vec<String>);
+    check_useful_c_possibly_empty_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_multi_sign: entities >>> wallet_handle: {:?}, signer_vks_json: {:?}, message_raw: {:?}, message_len: {:?}",
+           wallet_handle, signer_vks_json, message_raw, message_len);
+
+    if signer_vks_json.is_empty() {
+        return IndyError::from_msg(IndyErrorKind::InvalidParam(3), "Empty list of signer keys has been passed").into();
+    }
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoMultiSign(
+            wallet_handle,
+            signer_vks_json,
+            message_raw,
+            boxed_callback_string!("indy_crypto_multi_sign", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_multi_sign: <<< res: {:?}", res);
+
+    res
+}
+
 /// Verify a signature with a verkey.
 ///
 /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
@@ -232,7 +345,7 @@ pub  extern fn indy_crypto_sign(command_handle: CommandHandle,
 /// command_handle: command handle to map callback to user context.
 /// signer_vk: verkey of the message signer
 /// message_raw: a pointer to first byte of message that has been signed
-/// message_len: a message length
+/// message_len: a message length. Empty message is accepted
 /// signature_raw: a pointer to first byte of signature to be verified
 /// signature_len: a signature length
 /// cb: Callback that takes command result as parameter.
@@ -259,7 +372,7 @@ pub  extern fn indy_crypto_verify(command_handle: CommandHandle,
            signer_vk, message_raw, message_len, signature_raw, signature_len);
 
     check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam2);
-    check_useful_c_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
+    check_useful_c_possibly_empty_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3);
     check_useful_c_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
 
@@ -285,6 +398,353 @@ pub  extern fn indy_crypto_verify(command_handle: CommandHandle,
     res
 }
 
+/// Verify a signature with a verkey and report why verification failed.
+/// Intended for diagnostics, indy_crypto_verify keeps returning plain bool.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// signer_vk: verkey of the message signer
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length. Empty message is accepted
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// result_json: json
+///   {
+///     "verified": bool, - true if signature is valid
+///     "reason": optional<string>, - why verification failed, one of:
+///         "key not resolved" - signer_vk is abbreviated, malformed or of unknown crypto type
+///         "malformed signature" - signature has wrong length
+///         "signature mismatch" - signature doesn't match message and signer_vk
+///   }
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_verify_detailed(command_handle: CommandHandle,
+                                           signer_vk: *const c_char,
+                                           message_raw: *const u8,
+                                           message_len: u32,
+                                           signature_raw: *const u8,
+                                           signature_len: u32,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_verify_detailed: >>> signer_vk: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           signer_vk, message_raw, message_len, signature_raw, signature_len);
+
+    check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam2);
+    check_useful_c_possibly_empty_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_crypto_verify_detailed: entities >>> signer_vk: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           signer_vk, message_raw, message_len, signature_raw, signature_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoVerifyDetailed(
+            signer_vk,
+            message_raw,
+            signature_raw,
+            boxed_callback_string!("indy_crypto_verify_detailed", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_verify_detailed: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verify a signature against a list of verkeys and report which one matched.
+/// Useful during key rotation, when a message may still be signed with the old key.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// signer_vks_json: json array of verkeys to try, in order of preference
+/// message_raw: a pointer to first byte of message that has been signed
+/// message_len: a message length. Empty message is accepted
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// result_json: json
+///   {
+///     "verified": bool, - true if signature is valid for any of the verkeys
+///     "verkey": optional<string>, - the first verkey the signature is valid for
+///   }
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_crypto_verify_any_key(command_handle: CommandHandle,
+                                          signer_vks_json: *const c_char,
+                                          message_raw: *const u8,
+                                          message_len: u32,
+                                          signature_raw: *const u8,
+                                          signature_len: u32,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_verify_any_key: >>> signer_vks_json: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           signer_vks_json, message_raw, message_len, signature_raw, signature_len);
+
+    check_useful_json!(signer_vks_json, ErrorCode::CommonInvalidParam2, Vec<String>);
+    check_useful_c_possibly_empty_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_crypto_verify_any_key: entities >>> signer_vks_json: {:?}, message_raw: {:?}, message_len: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           signer_vks_json, message_raw, message_len, signature_raw, signature_len);
+
+    if signer_vks_json.is_empty() {
+        return IndyError::from_msg(IndyErrorKind::InvalidParam(2), "Empty list of signer keys has been passed").into();
+    }
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoVerifyAnyKey(
+            signer_vks_json,
+            message_raw,
+            signature_raw,
+            boxed_callback_string!("indy_crypto_verify_any_key", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_verify_any_key: <<< res: {:?}", res);
+
+    res
+}
+
+/// Signs a JSON message with a key.
+///
+/// The message is canonicalized before signing (object keys sorted, no insignificant whitespace),
+/// so logically equal JSON objects produce the same signature. Use indy_crypto_verify_json to verify it.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// signer_vk: id (verkey) of message signer. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// message_json: JSON message to be signed
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// a signature string
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_sign_json(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    signer_vk: *const c_char,
+                                    message_json: *const c_char,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         signature_raw: *const u8,
+                                                         signature_len: u32)>) -> ErrorCode {
+    trace!("indy_crypto_sign_json: >>> wallet_handle: {:?}, signer_vk: {:?}, message_json: {:?}",
+           wallet_handle, signer_vk, message_json);
+
+    check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(message_json, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_sign_json: entities >>> wallet_handle: {:?}, signer_vk: {:?}, message_json: {:?}",
+           wallet_handle, signer_vk, message_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoSignJson(
+            wallet_handle,
+            signer_vk,
+            message_json,
+            Box::new(move |result| {
+                let (err, signature) = prepare_result_1!(result, Vec::new());
+                trace!("indy_crypto_sign_json: signature: {:?}", signature);
+                let (signature_raw, signature_len) = ctypes::vec_to_pointer(&signature);
+                cb(command_handle, err, signature_raw, signature_len)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_sign_json: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verify a signature made by indy_crypto_sign_json.
+///
+/// The message is canonicalized the same way as for signing, so any JSON logically equal
+/// to the signed one (different key order or formatting) verifies.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// signer_vk: verkey of the message signer
+/// message_json: JSON message that has been signed
+/// signature_raw: a pointer to first byte of signature to be verified
+/// signature_len: a signature length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// valid: true - if signature is valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_verify_json(command_handle: CommandHandle,
+                                      signer_vk: *const c_char,
+                                      message_json: *const c_char,
+                                      signature_raw: *const u8,
+                                      signature_len: u32,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode,
+                                                           valid: bool)>) -> ErrorCode {
+    trace!("indy_crypto_verify_json: >>> signer_vk: {:?}, message_json: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           signer_vk, message_json, signature_raw, signature_len);
+
+    check_useful_c_str!(signer_vk, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(message_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(signature_raw, signature_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_crypto_verify_json: entities >>> signer_vk: {:?}, message_json: {:?}, signature_raw: {:?}, signature_len: {:?}",
+           signer_vk, message_json, signature_raw, signature_len);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::CryptoVerifyJson(
+            signer_vk,
+            message_json,
+            signature_raw,
+            Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_crypto_verify_json: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_verify_json: <<< res: {:?}", res);
+
+    res
+}
+
+/// Generates a fresh random nonce (24 bytes) suitable for challenge-response.
+/// Doesn't require wallet or DID.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// nonce: base58 encoded nonce
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub  extern fn indy_crypto_get_nonce(command_handle: CommandHandle,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          nonce: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_get_nonce: >>> ");
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::GetNonce(
+            boxed_callback_string!("indy_crypto_get_nonce", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_get_nonce: <<< res: {:?}", res);
+
+    res
+}
+
+/// Runs known-answer tests of the crypto backend against embedded vectors
+/// (seed derivation, sign/verify and box/unbox).
+///
+/// Applications can call it at startup and refuse to run if it fails, as this detects
+/// a miscompiled or substituted crypto library.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: pass/fail result per primitive
+/// {
+///     "seed_derivation": bool,
+///     "sign_verify": bool,
+///     "box_open": bool,
+/// }
+///
+/// #Errors
+/// Common*
+/// CommonInvalidState if any of the known-answer tests fails
+#[no_mangle]
+pub extern fn indy_crypto_self_test(command_handle: CommandHandle,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_self_test: >>> ");
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::SelfTest(
+            boxed_callback_string!("indy_crypto_self_test", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_self_test: <<< res: {:?}", res);
+
+    res
+}
+
+/// Lists the crypto types this build supports, so wrappers can detect
+/// whether a `crypto_type` can be passed to indy_create_key or indy_create_and_store_my_did.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// crypto_types_json: json array of supported crypto type names, e.g. ["ed25519"]
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_crypto_get_supported_types(command_handle: CommandHandle,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode,
+                                                                   crypto_types_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_get_supported_types: >>> ");
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::GetCryptoTypesSupported(
+            boxed_callback_string!("indy_crypto_get_supported_types", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_get_supported_types: <<< res: {:?}", res);
+
+    res
+}
+
 /// **** THIS FUNCTION WILL BE DEPRECATED USE indy_pack_message() INSTEAD ****
 /// Encrypt a message by authenticated-encryption scheme.
 ///
@@ -334,7 +794,7 @@ pub  extern fn indy_crypto_auth_crypt(command_handle: CommandHandle,
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
 
     trace!("indy_crypto_auth_crypt: entities >>> wallet_handle: {:?}, sender_vk: {:?}, recipient_vk: {:?}, msg_data: {:?}, msg_len: {:?}",
-           wallet_handle, sender_vk, recipient_vk, msg_data, msg_len);
+           wallet_handle, sender_vk, recipient_vk, secret!(&msg_data), msg_len);
 
     let result = CommandExecutor::instance()
         .send(Command::Crypto(CryptoCommand::AuthenticatedEncrypt(
@@ -411,8 +871,8 @@ pub  extern fn indy_crypto_auth_decrypt(command_handle: CommandHandle,
             recipient_vk,
             encrypted_msg,
             Box::new(move |result| {
-                let (err, sender_vk, msg) = prepare_result_2!(result, String::new(), Vec::new());
-                trace!("indy_crypto_auth_decrypt: sender_vk: {:?}, msg: {:?}", sender_vk, msg);
+                let (err, sender_vk, msg) = prepare_secret_result_2!(result, String::new(), Vec::new());
+                trace!("indy_crypto_auth_decrypt: sender_vk: {:?}, msg: {:?}", sender_vk, secret!(&msg));
                 let (msg_data, msg_len) = ctypes::vec_to_pointer(&msg);
                 let sender_vk = ctypes::string_to_cstring(sender_vk);
                 cb(command_handle, err, sender_vk.as_ptr(), msg_data, msg_len)
@@ -467,7 +927,7 @@ pub  extern fn indy_crypto_anon_crypt(command_handle: CommandHandle,
     check_useful_c_byte_array!(msg_data, msg_len, ErrorCode::CommonInvalidParam3, ErrorCode::CommonInvalidParam4);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_crypto_anon_crypt: entities >>> recipient_vk: {:?}, msg_data: {:?}, msg_len: {:?}", recipient_vk, msg_data, msg_len);
+    trace!("indy_crypto_anon_crypt: entities >>> recipient_vk: {:?}, msg_data: {:?}, msg_len: {:?}", recipient_vk, secret!(&msg_data), msg_len);
 
     let result = CommandExecutor::instance()
         .send(Command::Crypto(CryptoCommand::AnonymousEncrypt(
@@ -540,8 +1000,8 @@ pub  extern fn indy_crypto_anon_decrypt(command_handle: CommandHandle,
             recipient_vk,
             encrypted_msg,
             Box::new(move |result| {
-                let (err, msg) = prepare_result_1!(result, Vec::new());
-                trace!("indy_crypto_anon_decrypt: msg: {:?}", msg);
+                let (err, msg) = prepare_secret_result_1!(result, Vec::new());
+                trace!("indy_crypto_anon_decrypt: msg: {:?}", secret!(&msg));
                 let (msg_data, msg_len) = ctypes::vec_to_pointer(&msg);
                 cb(command_handle, err, msg_data, msg_len)
             })
@@ -554,6 +1014,65 @@ pub  extern fn indy_crypto_anon_decrypt(command_handle: CommandHandle,
     res
 }
 
+/// Derives a raw shared secret between my key and their key (Curve25519 ECDH).
+///
+/// Both keys are converted from Ed25519 to Curve25519 before the key agreement, so
+/// deriving with (my key, their verkey) and (their key, my verkey) gives the same secret.
+/// The result is not hashed; callers are expected to pass it through a KDF before using it
+/// as a symmetric key.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// my_vk: id (verkey) of my key. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// their_vk: verkey of the other party
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// shared_secret: base58 encoded 32-byte shared secret
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_crypto_derive_shared_secret(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               my_vk: *const c_char,
+                                               their_vk: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode,
+                                                                    shared_secret: *const c_char)>) -> ErrorCode {
+    trace!("indy_crypto_derive_shared_secret: >>> wallet_handle: {:?}, my_vk: {:?}, their_vk: {:?}",
+           wallet_handle, my_vk, their_vk);
+
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(their_vk, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_crypto_derive_shared_secret: entities >>> wallet_handle: {:?}, my_vk: {:?}, their_vk: {:?}",
+           wallet_handle, my_vk, their_vk);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::DeriveSharedSecret(
+            wallet_handle,
+            my_vk,
+            their_vk,
+            Box::new(move |result| {
+                let (err, shared_secret) = prepare_secret_result_1!(result, String::new());
+                trace!("indy_crypto_derive_shared_secret: shared_secret: {:?}", secret!(&shared_secret));
+                let shared_secret = ctypes::string_to_cstring(shared_secret);
+                cb(command_handle, err, shared_secret.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_crypto_derive_shared_secret: <<< res: {:?}", res);
+
+    res
+}
+
 /// Packs a message by encrypting the message and serializes it in a JWE-like format (Experimental)
 ///
 /// Note to use DID keys with this function you can call indy_key_for_did to get key id (verkey)
@@ -639,7 +1158,7 @@ pub extern fn indy_pack_message(
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
     trace!("indy_pack_message: entities >>> wallet_handle: {:?}, message: {:?}, message_len {:?},\
-            receiver_keys: {:?}, sender: {:?}", wallet_handle, message, message_len, receiver_keys, sender);
+            receiver_keys: {:?}, sender: {:?}", wallet_handle, secret!(&message), message_len, receiver_keys, sender);
 
     //parse json array of keys
     let receiver_list = match serde_json::from_str::<Vec<String>>(&receiver_keys) {
@@ -748,9 +1267,9 @@ pub extern fn indy_unpack_message(
         jwe_struct,
         wallet_handle,
         Box::new(move |result| {
-            let (err, res_json) = prepare_result_1!(result, Vec::new());
+            let (err, res_json) = prepare_secret_result_1!(result, Vec::new());
             trace!("indy_unpack_message: cb command_handle: {:?}, err: {:?}, res_json: {:?}",
-                command_handle, err, res_json
+                command_handle, err, secret!(&res_json)
             );
             let (res_json_data, res_json_len) = ctypes::vec_to_pointer(&res_json);
             cb(command_handle, err, res_json_data, res_json_len)
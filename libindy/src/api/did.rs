@@ -1,7 +1,7 @@
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, PoolHandle};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::did::DidCommand;
-use crate::domain::crypto::did::{TheirDidInfo, DidValue, MyDidInfo, DidMethod};
+use crate::domain::crypto::did::{TheirDidInfo, DidValue, MyDidInfo, DidMethod, TheirDidFilter};
 use crate::domain::crypto::key::KeyInfo;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -10,6 +10,7 @@ use indy_api_types::validation::Validatable;
 use serde_json;
 use libc::c_char;
 
+use std::collections::HashMap;
 use std::ptr;
 use crate::domain::ledger::attrib::Endpoint;
 
@@ -36,6 +37,8 @@ use crate::domain::ledger::attrib::Endpoint;
 ///               currently only 'ed25519' value is supported for this field)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///     "strict_seed": bool, (optional; if not set then false is used;
+///             if true then a seed with too low entropy, e.g. of one repeated character, is rejected)
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -194,9 +197,73 @@ pub  extern fn indy_replace_keys_apply(command_handle: CommandHandle,
     res
 }
 
+/// Generates a new key for an existing DID (owned by the caller of the library),
+/// publishes it to the ledger with a NYM transaction and applies it as main key once
+/// the ledger has accepted the transaction.
+///
+/// If the ledger rejects the transaction the new key stays pending (as after "indy_replace_keys_start"),
+/// so the call can be retried or the pending key applied later with "indy_replace_keys_apply".
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handler (created by open_wallet).
+/// submitter_did: DID of the NYM transaction submitter. Must be stored in the wallet.
+/// target_did: DID stored in the wallet whose key is rotated.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - verkey: The new verkey applied for the target DID.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Ledger*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_rotate_and_publish_key(command_handle: CommandHandle,
+                                          pool_handle: PoolHandle,
+                                          wallet_handle: WalletHandle,
+                                          submitter_did: *const c_char,
+                                          target_did: *const c_char,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_rotate_and_publish_key: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}",
+           pool_handle, wallet_handle, submitter_did, target_did);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_string!(target_did, ErrorCode::CommonInvalidParam5, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_rotate_and_publish_key: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}",
+           pool_handle, wallet_handle, submitter_did, target_did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::RotateAndPublish(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            target_did,
+            boxed_callback_string!("indy_rotate_and_publish_key", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_rotate_and_publish_key: <<< res: {:?}", res);
+
+    res
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
-/// Updates DID associated verkey in case DID already exists in the Wallet.
+/// Storing the same DID with the same verkey again is a no-op.
+/// A verkey can be added later to a DID stored without one.
+/// Storing the same DID with another verkey fails with CommonInvalidState unless "overwrite" is set.
 ///
 /// #Params
 /// wallet_handle: wallet handler (created by open_wallet).
@@ -207,6 +274,7 @@ pub  extern fn indy_replace_keys_apply(command_handle: CommandHandle,
 ///        "verkey": string
 ///             - optional is case of adding a new DID, and DID is cryptonym: did == verkey,
 ///             - mandatory in case of updating an existing DID
+///        "overwrite": bool (optional, false by default) - replace verkey of already stored DID
 ///     }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -251,6 +319,67 @@ pub  extern fn indy_store_their_did(command_handle: CommandHandle,
     res
 }
 
+/// Saves several their DIDs in a secured Wallet at once.
+/// Every entry is stored the same way as indy_store_their_did does it.
+/// A malformed or conflicting entry doesn't abort the batch: its slot in the result reports
+/// the error while the other entries are still stored.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handler (created by open_wallet).
+/// identities_json: JSON array of identities in the indy_store_their_did format:
+///     [{
+///        "did": string, (required)
+///        "verkey": string (optional),
+///        "overwrite": bool (optional)
+///     }]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - results_json: JSON array with one entry per identity, in the same order:
+///     [
+///        {"ok": string}, - did of the stored entry
+///        {"err": {
+///            "code": int, - error code
+///            "message": string
+///        }}
+///     ]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub  extern fn indy_store_their_dids(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     identities_json: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          results_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_store_their_dids: >>> wallet_handle: {:?}, identities_json: {:?}", wallet_handle, identities_json);
+
+    check_useful_json!(identities_json, ErrorCode::CommonInvalidParam3, Vec<serde_json::Value>);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_store_their_dids: entities >>> wallet_handle: {:?}, identities_json: {:?}", wallet_handle, identities_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::StoreTheirDids(
+            wallet_handle,
+            identities_json,
+            boxed_callback_string!("indy_store_their_dids", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_store_their_dids: <<< res: {:?}", res);
+
+    res
+}
+
 /// Returns ver key (key id) for the given DID.
 ///
 /// "indy_key_for_did" call follow the idea that we resolve information about their DID from
@@ -312,6 +441,66 @@ pub extern fn indy_key_for_did(command_handle: CommandHandle,
     res
 }
 
+/// Returns ver key (key id) for the given DID together with the place it was resolved from.
+///
+/// The key is resolved the same way as "indy_key_for_did" does it. The source lets the caller
+/// decide how much to trust and how long to cache the returned key.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// pool_handle:   Pool handle (created by open_pool).
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - The DID to resolve key.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - key_json - The DIDs ver key with its source:
+///     {
+///         "verkey": string, - The DIDs ver key (key id).
+///         "source": string - one of:
+///             "my" - DID owned by the caller of the library,
+///             "their_cached" - their DID already stored in the wallet,
+///             "ledger" - their DID fetched from the ledger by this call
+///     }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_key_for_did_detailed(command_handle: CommandHandle,
+                                        pool_handle: PoolHandle,
+                                        wallet_handle: WalletHandle,
+                                        did: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             key_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_key_for_did_detailed: >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_key_for_did_detailed: entities >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::KeyForDidDetailed(
+            pool_handle,
+            wallet_handle,
+            did,
+            boxed_callback_string!("indy_key_for_did_detailed", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_key_for_did_detailed: <<< res: {:?}", res);
+
+    res
+}
+
 /// Returns ver key (key id) for the given DID.
 ///
 /// "indy_key_for_local_did" call looks data stored in the local wallet only and skips freshness
@@ -447,6 +636,7 @@ pub extern fn indy_set_endpoint_for_did(command_handle: CommandHandle,
 /// Common*
 /// Wallet*
 /// Crypto*
+/// LedgerNotFound if no endpoint is set for DID on the ledger
 #[no_mangle]
 pub extern fn indy_get_endpoint_for_did(command_handle: CommandHandle,
                                         wallet_handle: WalletHandle,
@@ -485,65 +675,78 @@ pub extern fn indy_get_endpoint_for_did(command_handle: CommandHandle,
     res
 }
 
-/// Saves/replaces the meta information for the giving DID in the wallet.
+/// Replaces the transport key of the endpoint stored for the given DID.
+/// The endpoint address is left untouched, so transport keys can be rotated
+/// independently from the DID signing key.
 ///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// wallet_handle: Wallet handle (created by open_wallet).
-/// did - the DID to store metadata.
-/// metadata - the meta information that will be store with the DID.
+/// did - The DID whose endpoint must be rekeyed.
+/// transport_key - The new transport key (ver key, key id).
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
 /// Error Code
 /// cb:
-/// - command_handle_: command handle to map callback to caller context.
+/// - command_handle_: Command handle to map callback to caller context.
 /// - err: Error code.
 ///
 /// #Errors
 /// Common*
 /// Wallet*
 /// Crypto*
+/// WalletItemNotFound if no endpoint is stored for DID in the wallet
 #[no_mangle]
-pub extern fn indy_set_did_metadata(command_handle: CommandHandle,
-                                    wallet_handle: WalletHandle,
-                                    did: *const c_char,
-                                    metadata: *const c_char,
-                                    cb: Option<extern fn(command_handle_: CommandHandle,
-                                                         err: ErrorCode)>) -> ErrorCode {
-    trace!("indy_set_did_metadata: >>> wallet_handle: {:?}, did: {:?}, metadata: {:?}", wallet_handle, did, metadata);
+pub extern fn indy_rekey_endpoint_for_did(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          did: *const c_char,
+                                          transport_key: *const c_char,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_rekey_endpoint_for_did: >>> wallet_handle: {:?}, did: {:?}, transport_key: {:?}", wallet_handle, did, transport_key);
 
     check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
-    check_useful_c_str_empty_accepted!(metadata, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(transport_key, ErrorCode::CommonInvalidParam4);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_set_did_metadata: entities >>> wallet_handle: {:?}, did: {:?}, metadata: {:?}", wallet_handle, did, metadata);
+    trace!("indy_rekey_endpoint_for_did: entities >>> wallet_handle: {:?}, did: {:?}, transport_key: {:?}",
+           wallet_handle, did, transport_key);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::SetDidMetadata(
+        .send(Command::Did(DidCommand::RekeyEndpoint(
             wallet_handle,
             did,
-            metadata,
+            transport_key,
             Box::new(move |result| {
                 let err = prepare_result!(result);
-                trace!("indy_set_did_metadata:");
+                trace!("indy_rekey_endpoint_for_did:");
                 cb(command_handle, err)
             })
         )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_set_did_metadata: <<< res: {:?}", res);
+    trace!("indy_rekey_endpoint_for_did: <<< res: {:?}", res);
 
     res
 }
 
-/// Retrieves the meta information for the giving DID in the wallet.
+/// Builds a connection request for pairing with another agent and anoncrypts it to their verkey.
+///
+/// The request carries my DID, its verkey and the endpoint stored for it (if any):
+/// {
+///     "did": string,
+///     "verkey": string,
+///     "endpoint": Option<{"ha": string, "verkey": Option<string>}>
+/// }
+/// The other party opens it with indy_parse_connection_request.
 ///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// wallet_handle: Wallet handle (created by open_wallet).
-/// did - The DID to retrieve metadata.
+/// their_vk: verkey of the agent the request is sent to.
+/// my_did: my DID stored in the wallet.
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
@@ -551,45 +754,66 @@ pub extern fn indy_set_did_metadata(command_handle: CommandHandle,
 /// cb:
 /// - command_handle_: Command handle to map callback to caller context.
 /// - err: Error code.
-/// - metadata - The meta information stored with the DID; Can be null if no metadata was saved for this DID.
+/// - request_raw: a pointer to first byte of the anoncrypted request.
+/// - request_len: the anoncrypted request length.
 ///
 /// #Errors
 /// Common*
 /// Wallet*
 /// Crypto*
 #[no_mangle]
-pub extern fn indy_get_did_metadata(command_handle: CommandHandle,
-                                    wallet_handle: WalletHandle,
-                                    did: *const c_char,
-                                    cb: Option<extern fn(command_handle_: CommandHandle,
-                                                         err: ErrorCode,
-                                                         metadata: *const c_char)>) -> ErrorCode {
-    trace!("indy_get_did_metadata: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
-
-    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+pub extern fn indy_prepare_anoncrypted_connection_request(command_handle: CommandHandle,
+                                                          wallet_handle: WalletHandle,
+                                                          their_vk: *const c_char,
+                                                          my_did: *const c_char,
+                                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                               err: ErrorCode,
+                                                                               request_raw: *const u8,
+                                                                               request_len: u32)>) -> ErrorCode {
+    trace!("indy_prepare_anoncrypted_connection_request: >>> wallet_handle: {:?}, their_vk: {:?}, my_did: {:?}", wallet_handle, their_vk, my_did);
+
+    check_useful_c_str!(their_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_string!(my_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_get_did_metadata: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+    trace!("indy_prepare_anoncrypted_connection_request: entities >>> wallet_handle: {:?}, their_vk: {:?}, my_did: {:?}",
+           wallet_handle, their_vk, my_did);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::GetDidMetadata(
+        .send(Command::Did(DidCommand::PrepareAnoncryptedConnectionRequest(
             wallet_handle,
-            did,
-            boxed_callback_string!("indy_get_did_metadata", cb, command_handle))));
+            their_vk,
+            my_did,
+            Box::new(move |result| {
+                let (err, request) = prepare_result_1!(result, Vec::new());
+                trace!("indy_prepare_anoncrypted_connection_request: request: {:?}", request);
+                let (request_raw, request_len) = ctypes::vec_to_pointer(&request);
+                cb(command_handle, err, request_raw, request_len)
+            })
+        )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_get_did_metadata: <<< res: {:?}", res);
+    trace!("indy_prepare_anoncrypted_connection_request: <<< res: {:?}", res);
 
     res
 }
 
-/// Retrieves the information about the giving DID in the wallet.
+/// Opens a connection request created by indy_prepare_anoncrypted_connection_request.
+///
+/// The sender's DID and verkey are stored in the wallet as their DID, and the sender's
+/// endpoint (if present) is stored for that DID, so the connection can be used right away.
+/// Fails with CommonInvalidStructure if the sender's DID isn't derived from its verkey,
+/// and with WalletItemAlreadyExists if the sender's DID is already stored with another verkey.
+/// Parsing a request for a known DID with the same verkey again succeeds, the endpoint stored
+/// for that DID is kept.
 ///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// wallet_handle: Wallet handle (created by open_wallet).
-/// did - The DID to retrieve information.
+/// my_vk: verkey the request was anoncrypted to. The key must be created by calling indy_create_key or indy_create_and_store_my_did
+/// request_raw: a pointer to first byte of the anoncrypted request.
+/// request_len: the anoncrypted request length.
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
@@ -597,51 +821,60 @@ pub extern fn indy_get_did_metadata(command_handle: CommandHandle,
 /// cb:
 /// - command_handle_: Command handle to map callback to caller context.
 /// - err: Error code.
-///   did_with_meta:  {
-///     "did": string - DID stored in the wallet,
-///     "verkey": string - The DIDs transport key (ver key, key id),
-///     "tempVerkey": string - Temporary DIDs transport key (ver key, key id), exist only during the rotation of the keys.
-///                            After rotation is done, it becomes a new verkey.
-///     "metadata": string - The meta information stored with the DID
-///   }
+/// - request_json: the opened request, see indy_prepare_anoncrypted_connection_request.
 ///
 /// #Errors
 /// Common*
 /// Wallet*
 /// Crypto*
 #[no_mangle]
-pub extern fn indy_get_my_did_with_meta(command_handle: CommandHandle,
-                                        wallet_handle: WalletHandle,
-                                        my_did: *const c_char,
-                                        cb: Option<extern fn(command_handle_: CommandHandle,
-                                                             err: ErrorCode,
-                                                             did_with_meta: *const c_char)>) -> ErrorCode {
-    trace!("indy_get_my_did_with_meta: >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+pub extern fn indy_parse_connection_request(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            my_vk: *const c_char,
+                                            request_raw: *const u8,
+                                            request_len: u32,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 request_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_parse_connection_request: >>> wallet_handle: {:?}, my_vk: {:?}, request_raw: {:?}, request_len: {:?}",
+           wallet_handle, my_vk, request_raw, request_len);
 
-    check_useful_validatable_string!(my_did, ErrorCode::CommonInvalidParam3, DidValue);
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(my_vk, ErrorCode::CommonInvalidParam3);
+    check_useful_c_byte_array!(request_raw, request_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
 
-    trace!("indy_get_my_did_with_meta: entities >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+    trace!("indy_parse_connection_request: entities >>> wallet_handle: {:?}, my_vk: {:?}, request_raw: {:?}",
+           wallet_handle, my_vk, request_raw);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::GetMyDidWithMeta(
+        .send(Command::Did(DidCommand::ParseConnectionRequest(
             wallet_handle,
-            my_did,
-            boxed_callback_string!("indy_get_my_did_with_meta", cb, command_handle)
+            my_vk,
+            request_raw,
+            boxed_callback_string!("indy_parse_connection_request", cb, command_handle)
         )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_get_my_did_with_meta: <<< res: {:?}", res);
+    trace!("indy_parse_connection_request: <<< res: {:?}", res);
 
     res
 }
 
-/// Retrieves the information about all DIDs stored in the wallet.
+/// Re-encrypts a message authcrypted to my DID so it can be forwarded to another recipient.
+///
+/// The message is decrypted with the key of my DID, checked to come from the expected sender
+/// and anoncrypted to the new recipient's verkey. The decrypted message is never returned.
+/// The new recipient opens the result with indy_crypto_anon_decrypt.
 ///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// wallet_handle: Wallet handle (created by open_wallet).
+/// my_did: my DID stored in the wallet, the message was authcrypted to its verkey.
+/// sender_vk: verkey of the original sender of the message.
+/// recipient_vk: verkey of the new recipient.
+/// encrypted_msg_raw: a pointer to first byte of the authcrypted message.
+/// encrypted_msg_len: the authcrypted message length.
 /// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
@@ -649,84 +882,1017 @@ pub extern fn indy_get_my_did_with_meta(command_handle: CommandHandle,
 /// cb:
 /// - command_handle_: Command handle to map callback to caller context.
 /// - err: Error code.
-///   dids:  [{
-///     "did": string - DID stored in the wallet,
-///     "verkey": string - The DIDs transport key (ver key, key id).,
-///     "metadata": string - The meta information stored with the DID
-///   }]
+/// - encrypted_msg_raw: a pointer to first byte of the anoncrypted message.
+/// - encrypted_msg_len: the anoncrypted message length.
 ///
 /// #Errors
 /// Common*
 /// Wallet*
 /// Crypto*
 #[no_mangle]
-pub extern fn indy_list_my_dids_with_meta(command_handle: CommandHandle,
-                                          wallet_handle: WalletHandle,
-                                          cb: Option<extern fn(command_handle_: CommandHandle,
-                                                               err: ErrorCode,
-                                                               dids: *const c_char)>) -> ErrorCode {
-    trace!("indy_list_my_dids_with_meta: >>> wallet_handle: {:?}", wallet_handle);
+pub extern fn indy_re_encrypt(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              my_did: *const c_char,
+                              sender_vk: *const c_char,
+                              recipient_vk: *const c_char,
+                              encrypted_msg_raw: *const u8,
+                              encrypted_msg_len: u32,
+                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                   err: ErrorCode,
+                                                   encrypted_msg_raw: *const u8,
+                                                   encrypted_msg_len: u32)>) -> ErrorCode {
+    trace!("indy_re_encrypt: >>> wallet_handle: {:?}, my_did: {:?}, sender_vk: {:?}, recipient_vk: {:?}, encrypted_msg_raw: {:?}, encrypted_msg_len: {:?}",
+           wallet_handle, my_did, sender_vk, recipient_vk, encrypted_msg_raw, encrypted_msg_len);
 
-    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+    check_useful_validatable_string!(my_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_str!(sender_vk, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str!(recipient_vk, ErrorCode::CommonInvalidParam5);
+    check_useful_c_byte_array!(encrypted_msg_raw, encrypted_msg_len, ErrorCode::CommonInvalidParam6, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
 
-    trace!("indy_list_my_dids_with_meta: entities >>> wallet_handle: {:?}", wallet_handle);
+    trace!("indy_re_encrypt: entities >>> wallet_handle: {:?}, my_did: {:?}, sender_vk: {:?}, recipient_vk: {:?}, encrypted_msg_raw: {:?}",
+           wallet_handle, my_did, sender_vk, recipient_vk, encrypted_msg_raw);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::ListMyDidsWithMeta(
+        .send(Command::Did(DidCommand::ReEncrypt(
             wallet_handle,
-            boxed_callback_string!("indy_list_my_dids_with_meta", cb, command_handle)
+            my_did,
+            sender_vk,
+            recipient_vk,
+            encrypted_msg_raw,
+            Box::new(move |result| {
+                let (err, encrypted_msg) = prepare_result_1!(result, Vec::new());
+                trace!("indy_re_encrypt: encrypted_msg: {:?}", encrypted_msg);
+                let (encrypted_msg_raw, encrypted_msg_len) = ctypes::vec_to_pointer(&encrypted_msg);
+                cb(command_handle, err, encrypted_msg_raw, encrypted_msg_len)
+            })
         )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_list_my_dids_with_meta: <<< res: {:?}", res);
+    trace!("indy_re_encrypt: <<< res: {:?}", res);
 
     res
 }
 
-/// Retrieves abbreviated verkey if it is possible otherwise return full verkey.
+/// Saves/replaces the meta information for the giving DID in the wallet.
 ///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
-/// did: DID.
-/// full_verkey: The DIDs verification key,
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - the DID to store metadata.
+/// metadata - the meta information that will be store with the DID.
+/// cb: Callback that takes command result as parameter.
 ///
 /// #Returns
 /// Error Code
 /// cb:
-/// - command_handle_: Command handle to map callback to caller context.
+/// - command_handle_: command handle to map callback to caller context.
 /// - err: Error code.
-///   verkey: The DIDs verification key in either abbreviated or full form
 ///
 /// #Errors
 /// Common*
 /// Wallet*
 /// Crypto*
 #[no_mangle]
-pub  extern fn indy_abbreviate_verkey(command_handle: CommandHandle,
-                                      did: *const c_char,
-                                      full_verkey: *const c_char,
-                                      cb: Option<extern fn(command_handle_: CommandHandle,
-                                                           err: ErrorCode,
-                                                           verkey: *const c_char)>) -> ErrorCode {
-    trace!("indy_abbreviate_verkey: >>> did: {:?}, full_verkey: {:?}", did, full_verkey);
+pub extern fn indy_set_did_metadata(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    did: *const c_char,
+                                    metadata: *const c_char,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_did_metadata: >>> wallet_handle: {:?}, did: {:?}, metadata: {:?}", wallet_handle, did, metadata);
 
     check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
-    check_useful_c_str!(full_verkey, ErrorCode::CommonInvalidParam4);
+    check_useful_c_str_empty_accepted!(metadata, ErrorCode::CommonInvalidParam4);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
 
-    trace!("indy_abbreviate_verkey: entities >>> did: {:?}, full_verkey: {:?}", did, full_verkey);
+    trace!("indy_set_did_metadata: entities >>> wallet_handle: {:?}, did: {:?}, metadata: {:?}", wallet_handle, did, metadata);
 
     let result = CommandExecutor::instance()
-        .send(Command::Did(DidCommand::AbbreviateVerkey(
+        .send(Command::Did(DidCommand::SetDidMetadata(
+            wallet_handle,
             did,
-            full_verkey,
-            boxed_callback_string!("indy_abbreviate_verkey", cb, command_handle)
+            metadata,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_set_did_metadata:");
+                cb(command_handle, err)
+            })
         )));
 
     let res = prepare_result!(result);
 
-    trace!("indy_abbreviate_verkey: <<< res: {:?}", res);
+    trace!("indy_set_did_metadata: <<< res: {:?}", res);
+
+    res
+}
+
+/// Retrieves the meta information for the giving DID in the wallet.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - The DID to retrieve metadata.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - metadata - The meta information stored with the DID; Can be null if no metadata was saved for this DID.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_get_did_metadata(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    did: *const c_char,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         metadata: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_did_metadata: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_did_metadata: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::GetDidMetadata(
+            wallet_handle,
+            did,
+            boxed_callback_string!("indy_get_did_metadata", cb, command_handle))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_did_metadata: <<< res: {:?}", res);
+
+    res
+}
+
+/// Retrieves the information about the giving DID in the wallet.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did - The DID to retrieve information.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   did_with_meta:  {
+///     "did": string - DID stored in the wallet,
+///     "verkey": string - The DIDs transport key (ver key, key id),
+///     "tempVerkey": string - Temporary DIDs transport key (ver key, key id), exist only during the rotation of the keys.
+///                            After rotation is done, it becomes a new verkey.
+///     "metadata": string - The meta information stored with the DID
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_get_my_did_with_meta(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        my_did: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             did_with_meta: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_my_did_with_meta: >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+    check_useful_validatable_string!(my_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_my_did_with_meta: entities >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::GetMyDidWithMeta(
+            wallet_handle,
+            my_did,
+            boxed_callback_string!("indy_get_my_did_with_meta", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_my_did_with_meta: <<< res: {:?}", res);
+
+    res
+}
+
+/// Retrieves the information about all DIDs stored in the wallet.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   dids:  [{
+///     "did": string - DID stored in the wallet,
+///     "verkey": string - The DIDs transport key (ver key, key id).,
+///     "metadata": string - The meta information stored with the DID
+///   }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_list_my_dids_with_meta(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               dids: *const c_char)>) -> ErrorCode {
+    trace!("indy_list_my_dids_with_meta: >>> wallet_handle: {:?}", wallet_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_list_my_dids_with_meta: entities >>> wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ListMyDidsWithMeta(
+            wallet_handle,
+            boxed_callback_string!("indy_list_my_dids_with_meta", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_list_my_dids_with_meta: <<< res: {:?}", res);
+
+    res
+}
+
+/// Retrieves their DIDs stored in the wallet together with their endpoints.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// filter_json: (optional) filter to apply to the listing:
+/// {
+///     "has_endpoint": Option<bool> - keep only DIDs with (true) or without (false) a stored endpoint
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   dids: array sorted by DID [{
+///     "did": string - their DID stored in the wallet,
+///     "verkey": string - their verkey,
+///     "endpoint": Option<{"ha": string, "verkey": Option<string>}> - endpoint stored for their DID
+///   }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_list_their_dids(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   filter_json: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        dids: *const c_char)>) -> ErrorCode {
+    trace!("indy_list_their_dids: >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
+
+    check_useful_opt_json!(filter_json, ErrorCode::CommonInvalidParam3, TheirDidFilter);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_list_their_dids: entities >>> wallet_handle: {:?}, filter_json: {:?}", wallet_handle, filter_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ListTheirDids(
+            wallet_handle,
+            filter_json,
+            boxed_callback_string!("indy_list_their_dids", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_list_their_dids: <<< res: {:?}", res);
+
+    res
+}
+
+/// Retrieves abbreviated verkey if it is possible otherwise return full verkey.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// did: DID.
+/// full_verkey: The DIDs verification key,
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   verkey: The DIDs verification key in either abbreviated or full form
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_abbreviate_verkey(command_handle: CommandHandle,
+                                      did: *const c_char,
+                                      full_verkey: *const c_char,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode,
+                                                           verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_abbreviate_verkey: >>> did: {:?}, full_verkey: {:?}", did, full_verkey);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_str!(full_verkey, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_abbreviate_verkey: entities >>> did: {:?}, full_verkey: {:?}", did, full_verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::AbbreviateVerkey(
+            did,
+            full_verkey,
+            boxed_callback_string!("indy_abbreviate_verkey", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_abbreviate_verkey: <<< res: {:?}", res);
+
+    res
+}
+
+/// Retrieves full verkey for the given DID and verkey in either abbreviated or full form.
+/// Full verkey is returned as is.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// did: DID.
+/// verkey: The DIDs verification key in either abbreviated or full form,
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   full_verkey: The DIDs verification key in full form
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_expand_verkey(command_handle: CommandHandle,
+                                  did: *const c_char,
+                                  verkey: *const c_char,
+                                  cb: Option<extern fn(command_handle_: CommandHandle,
+                                                       err: ErrorCode,
+                                                       full_verkey: *const c_char)>) -> ErrorCode {
+    trace!("indy_expand_verkey: >>> did: {:?}, verkey: {:?}", did, verkey);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam2, DidValue);
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_expand_verkey: entities >>> did: {:?}, verkey: {:?}", did, verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ExpandVerkey(
+            did,
+            verkey,
+            boxed_callback_string!("indy_expand_verkey", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_expand_verkey: <<< res: {:?}", res);
+
+    res
+}
+
+/// Derives DID from the full verkey without storing anything in the wallet.
+/// DID is the base58 of the first 16 bytes of the verkey.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// verkey: The verification key in full form. Abbreviated verkey can't be used here.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   did: DID derived from the verkey
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_did_from_verkey(command_handle: CommandHandle,
+                                    verkey: *const c_char,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         did: *const c_char)>) -> ErrorCode {
+    trace!("indy_did_from_verkey: >>> verkey: {:?}", verkey);
+
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_did_from_verkey: entities >>> verkey: {:?}", verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::DidFromVerkey(
+            verkey,
+            boxed_callback_string!("indy_did_from_verkey", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_did_from_verkey: <<< res: {:?}", res);
+
+    res
+}
+
+/// Computes a short fingerprint of the full verkey for display and out-of-band key confirmation.
+/// The fingerprint is the hex of the first 8 bytes of SHA-256 over the raw verkey bytes,
+/// so it is stable across runs and doesn't depend on a crypto type suffix.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// verkey: The verification key in full form. Abbreviated verkey can't be used here.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   fingerprint: 16 hex chars fingerprint of the verkey
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_compute_did_fingerprint(command_handle: CommandHandle,
+                                           verkey: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                fingerprint: *const c_char)>) -> ErrorCode {
+    trace!("indy_compute_did_fingerprint: >>> verkey: {:?}", verkey);
+
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_compute_did_fingerprint: entities >>> verkey: {:?}", verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ComputeDidFingerprint(
+            verkey,
+            boxed_callback_string!("indy_compute_did_fingerprint", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_compute_did_fingerprint: <<< res: {:?}", res);
+
+    res
+}
+
+/// Checks that a DID string is well-formed before it is passed to other functions.
+///
+/// A DID is well-formed if it is a base58 encoded 16 or 32 byte number,
+/// optionally prefixed with `did:<method>:`.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// did: DID string to check.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - result_json: {
+///     "valid": bool,
+///     "kind": Option<"short" | "full" | "qualified"> - null if the DID is malformed
+/// }
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_validate_did_string(command_handle: CommandHandle,
+                                       did: *const c_char,
+                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                            err: ErrorCode,
+                                                            result_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_validate_did_string: >>> did: {:?}", did);
+
+    check_useful_c_str!(did, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_validate_did_string: entities >>> did: {:?}", did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ValidateDidString(
+            did,
+            boxed_callback_string!("indy_validate_did_string", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_validate_did_string: <<< res: {:?}", res);
+
+    res
+}
+
+/// Converts the full Ed25519 verkey to the Curve25519 public key used for encryption,
+/// e.g. for interop with non-Indy systems. Nothing is read from or stored in the wallet.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// verkey: The verification key in full form. Abbreviated verkey can't be used here.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   curve25519_key: base58 encoded Curve25519 public key
+///
+/// #Errors
+/// Common*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_verkey_to_curve25519(command_handle: CommandHandle,
+                                        verkey: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             curve25519_key: *const c_char)>) -> ErrorCode {
+    trace!("indy_verkey_to_curve25519: >>> verkey: {:?}", verkey);
+
+    check_useful_c_str!(verkey, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_verkey_to_curve25519: entities >>> verkey: {:?}", verkey);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::VerkeyToCurve25519(
+            verkey,
+            boxed_callback_string!("indy_verkey_to_curve25519", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verkey_to_curve25519: <<< res: {:?}", res);
+
+    res
+}
+
+/// Converts the signing key of a DID owned by the caller of the library to the
+/// Curve25519 private key used for encryption. The Ed25519 signing key itself is never returned.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did: DID stored in the wallet.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///   curve25519_key: base58 encoded Curve25519 private key
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_signkey_to_curve25519(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         did: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode,
+                                                              curve25519_key: *const c_char)>) -> ErrorCode {
+    trace!("indy_signkey_to_curve25519: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_signkey_to_curve25519: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::SignkeyToCurve25519(
+            wallet_handle,
+            did,
+            Box::new(move |result| {
+                let (err, curve25519_key) = prepare_secret_result_1!(result, String::new());
+                trace!("indy_signkey_to_curve25519:");
+                let curve25519_key = ctypes::string_to_cstring(curve25519_key);
+                cb(command_handle, err, curve25519_key.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_signkey_to_curve25519: <<< res: {:?}", res);
+
+    res
+}
+
+/// Removes a DID owned by the caller of the library from the wallet,
+/// together with its metadata, endpoint and not yet applied temporary keys.
+/// Keys stored for the DID stay in the wallet: they can still be used on their own,
+/// for example by indy_crypto_sign, or by other DIDs created from the same seed.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did: DID stored in the wallet.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_forget_did(command_handle: CommandHandle,
+                               wallet_handle: WalletHandle,
+                               did: *const c_char,
+                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_forget_did: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_forget_did: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ForgetDid(
+            wallet_handle,
+            did,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_forget_did:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_forget_did: <<< res: {:?}", res);
+
+    res
+}
+
+/// Exports the keys of a DID owned by the caller of the library in a portable form
+/// to move the DID to another wallet (see indy_import_did_key).
+///
+/// The exported data is encrypted with a key derived from the passphrase,
+/// so signkey never leaves the wallet in plain form.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did: DID stored in the wallet.
+/// passphrase: passphrase used to derive the encryption key.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - exported: base64 encoded encrypted DID key.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_export_did_key(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   did: *const c_char,
+                                   passphrase: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        exported: *const c_char)>) -> ErrorCode {
+    trace!("indy_export_did_key: >>> wallet_handle: {:?}, did: {:?}, passphrase: {:?}", wallet_handle, did, passphrase);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_str!(passphrase, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_export_did_key: entities >>> wallet_handle: {:?}, did: {:?}, passphrase: {:?}", wallet_handle, did, secret!(&passphrase));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ExportDidKey(
+            wallet_handle,
+            did,
+            passphrase,
+            Box::new(move |result| {
+                let (err, exported) = prepare_secret_result_1!(result, String::new());
+                trace!("indy_export_did_key: exported: {:?}", secret!(&exported));
+                let exported = ctypes::string_to_cstring(exported);
+                cb(command_handle, err, exported.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_export_did_key: <<< res: {:?}", res);
+
+    res
+}
+
+/// Imports DID keys exported by indy_export_did_key and stores the DID in the wallet
+/// as owned by the caller of the library.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// passphrase: passphrase used on export.
+/// exported: base64 encoded encrypted DID key returned by indy_export_did_key.
+/// policy: (optional) what to do if the DID is already stored in the wallet:
+///     "fail" - return DidAlreadyExistsError (default)
+///     "overwrite" - replace stored verkey with the imported one and delete the replaced key
+///     "skip" - keep stored DID untouched
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - did: imported DID.
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+/// WalletAccessFailed if passphrase is wrong
+#[no_mangle]
+pub  extern fn indy_import_did_key(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   passphrase: *const c_char,
+                                   exported: *const c_char,
+                                   policy: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        did: *const c_char)>) -> ErrorCode {
+    trace!("indy_import_did_key: >>> wallet_handle: {:?}, passphrase: {:?}, exported: {:?}, policy: {:?}", wallet_handle, passphrase, exported, policy);
+
+    check_useful_c_str!(passphrase, ErrorCode::CommonInvalidParam3);
+    check_useful_c_str!(exported, ErrorCode::CommonInvalidParam4);
+    check_useful_opt_c_str!(policy, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_import_did_key: entities >>> wallet_handle: {:?}, passphrase: {:?}, exported: {:?}, policy: {:?}", wallet_handle, secret!(&passphrase), exported, policy);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::ImportDidKey(
+            wallet_handle,
+            passphrase,
+            exported,
+            policy,
+            boxed_callback_string!("indy_import_did_key", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_import_did_key: <<< res: {:?}", res);
+
+    res
+}
+
+/// Checks that keys of a DID owned by the caller of the library are consistent.
+/// Intended for diagnostics of damaged wallets.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// did: DID stored in the wallet.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - report: json
+///   {
+///     "did": string, - checked DID
+///     "verkey_ok": bool, - stored signkey belongs to stored verkey
+///     "did_ok": bool, - DID is derived from the first 16 bytes of verkey
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub  extern fn indy_check_did_integrity(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        did: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             report: *const c_char)>) -> ErrorCode {
+    trace!("indy_check_did_integrity: >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    check_useful_validatable_string!(did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_check_did_integrity: entities >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::CheckDidIntegrity(
+            wallet_handle,
+            did,
+            boxed_callback_string!("indy_check_did_integrity", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_check_did_integrity: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a bundle of signatures made by several DIDs over the same message (m-of-n check).
+///
+/// Every DID is resolved to its verkey from the wallet (my DIDs and their DIDs, see "indy_key_for_local_did").
+/// DIDs that can't be resolved and malformed signatures count as invalid but don't abort verification.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: Wallet handle (created by open_wallet).
+/// signatures_json: map of signer DIDs to base58 encoded signatures
+///     {
+///         "<did>": "<signature>",
+///         ...
+///     }
+/// message_raw: a pointer to first byte of message that was signed
+/// message_len: a message length. Empty message is accepted
+/// threshold: minimal number of valid signatures by distinct keys (at least 1)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - valid: true - if at least `threshold` signatures are valid, false - otherwise
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verify_multi_did_signature(command_handle: CommandHandle,
+                                              wallet_handle: WalletHandle,
+                                              signatures_json: *const c_char,
+                                              message_raw: *const u8,
+                                              message_len: u32,
+                                              threshold: u32,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode,
+                                                                   valid: bool)>) -> ErrorCode {
+    trace!("indy_verify_multi_did_signature: >>> wallet_handle: {:?}, signatures_json: {:?}, message_raw: {:?}, message_len: {:?}, threshold: {:?}",
+           wallet_handle, signatures_json, message_raw, message_len, threshold);
+
+    check_useful_json!(signatures_json, ErrorCode::CommonInvalidParam3, HashMap<String, String>);
+    check_useful_c_possibly_empty_byte_array!(message_raw, message_len, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_verify_multi_did_signature: entities >>> wallet_handle: {:?}, signatures_json: {:?}, message_raw: {:?}, message_len: {:?}, threshold: {:?}",
+           wallet_handle, signatures_json, message_raw, message_len, threshold);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::VerifyMulti(
+            wallet_handle,
+            signatures_json,
+            message_raw,
+            threshold,
+            Box::new(move |result| {
+                let (err, valid) = prepare_result_1!(result, false);
+                trace!("indy_verify_multi_did_signature: valid: {:?}", valid);
+                cb(command_handle, err, valid)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verify_multi_did_signature: <<< res: {:?}", res);
+
+    res
+}
+
+/// Generates a random seed together with its BIP39 mnemonic (24 English words) for human-friendly backup.
+/// The seed can be passed as "seed" to "indy_create_and_store_my_did" and recovered from the
+/// mnemonic with "indy_seed_from_mnemonic".
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - mnemonic: 24 words mnemonic phrase.
+/// - seed: 32 bytes seed as HEX string.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_generate_mnemonic_seed(command_handle: CommandHandle,
+                                          cb: Option<extern fn(command_handle_: CommandHandle,
+                                                               err: ErrorCode,
+                                                               mnemonic: *const c_char,
+                                                               seed: *const c_char)>) -> ErrorCode {
+    trace!("indy_generate_mnemonic_seed: >>>");
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam2);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::GenerateMnemonicSeed(
+            Box::new(move |result| {
+                let (err, mnemonic, seed) = prepare_secret_result_2!(result, String::new(), String::new());
+                trace!("indy_generate_mnemonic_seed: mnemonic: {:?}, seed: {:?}", secret!(&mnemonic), secret!(&seed));
+                let mnemonic = ctypes::string_to_cstring(mnemonic);
+                let seed = ctypes::string_to_cstring(seed);
+                cb(command_handle, err, mnemonic.as_ptr(), seed.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_generate_mnemonic_seed: <<< res: {:?}", res);
+
+    res
+}
+
+/// Reconstructs the seed from a BIP39 mnemonic (24 English words) generated by "indy_generate_mnemonic_seed".
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// mnemonic: 24 words mnemonic phrase.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error Code
+/// cb:
+/// - command_handle_: Command handle to map callback to caller context.
+/// - err: Error code.
+/// - seed: 32 bytes seed as HEX string.
+///
+/// #Errors
+/// Common* (CommonInvalidStructure for unknown words, wrong number of words or bad checksum)
+#[no_mangle]
+pub extern fn indy_seed_from_mnemonic(command_handle: CommandHandle,
+                                      mnemonic: *const c_char,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode,
+                                                           seed: *const c_char)>) -> ErrorCode {
+    trace!("indy_seed_from_mnemonic: >>> mnemonic: {:?}", secret!(mnemonic));
+
+    check_useful_c_str!(mnemonic, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_seed_from_mnemonic: entities >>> mnemonic: {:?}", secret!(&mnemonic));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Did(DidCommand::SeedFromMnemonic(
+            mnemonic,
+            Box::new(move |result| {
+                let (err, seed) = prepare_secret_result_1!(result, String::new());
+                trace!("indy_seed_from_mnemonic: seed: {:?}", secret!(&seed));
+                let seed = ctypes::string_to_cstring(seed);
+                cb(command_handle, err, seed.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_seed_from_mnemonic: <<< res: {:?}", res);
 
     res
 }
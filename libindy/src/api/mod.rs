@@ -30,6 +30,14 @@ use indy_utils::ctypes;
 ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
 ///         Capturing of backtrace can affect library performance.
 ///         NOTE: must be set before invocation of any other API functions.
+///     "strict_messages": Optional<bool> - whether signing or verifying a message that consists of
+///         whitespace only must fail. (false by default)
+///     "ledger_fallback_max_retries": Optional<int> - how many times a DID lookup that falls back to the ledger
+///         is resubmitted after the pool timed out. (2 by default)
+///     "ledger_fallback_base_delay_ms": Optional<int> - delay before the first resubmission in milliseconds,
+///         every next one waits twice as long. (1000 by default)
+///     "verkey_cache_ttl_secs": Optional<int> - how long a verkey resolved from the ledger is used
+///         without asking the pool again, in seconds. (300 by default)
 /// }
 ///
 /// #Errors
@@ -40,9 +48,9 @@ pub extern fn indy_set_runtime_config(config: *const c_char) -> ErrorCode {
 
     check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam1, IndyConfig);
 
-    crate::commands::indy_set_runtime_config(config);
+    let result = crate::commands::indy_set_runtime_config(config);
 
-    let res = ErrorCode::Success;
+    let res = prepare_result!(result);
 
     trace!("indy_set_runtime_config: <<< res: {:?}", res);
 
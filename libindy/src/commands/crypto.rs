@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
-use crate::domain::crypto::key::{Key, KeyInfo, KeyMetadata};
+use crate::domain::batch::BatchResult;
+use crate::domain::crypto::key::{Key, KeyInfo, KeyMetadata, VerificationResult, AnyKeyVerificationResult, VERIFY_REASON_KEY_NOT_RESOLVED, VERIFY_REASON_MALFORMED_SIGNATURE, VERIFY_REASON_SIGNATURE_MISMATCH};
 use crate::domain::crypto::pack::*;
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
@@ -10,8 +11,11 @@ use std::rc::Rc;
 use std::str;
 use indy_utils::crypto::base64;
 use indy_utils::crypto::chacha20poly1305_ietf;
+use indy_utils::crypto::ed25519_sign;
 use crate::domain::crypto::combo_box::ComboBox;
 use indy_api_types::WalletHandle;
+use crate::utils::crypto::base58;
+use crate::utils::crypto::json_canonicalizer::canonicalize_json;
 
 pub const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
 pub const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
@@ -41,12 +45,57 @@ pub enum CryptoCommand {
         Vec<u8>, // msg
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    CryptoSignMultiple(
+        WalletHandle,
+        String,  // my vk
+        Vec<String>, // base64 encoded msgs
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    CryptoMultiSign(
+        WalletHandle,
+        Vec<String>,  // my vks
+        Vec<u8>, // msg
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
     CryptoVerify(
         String,  // their vk
         Vec<u8>, // msg
         Vec<u8>, // signature
         Box<dyn Fn(IndyResult<bool>) + Send>,
     ),
+    CryptoVerifyDetailed(
+        String,  // their vk
+        Vec<u8>, // msg
+        Vec<u8>, // signature
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    CryptoVerifyAnyKey(
+        Vec<String>, // their vks
+        Vec<u8>, // msg
+        Vec<u8>, // signature
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    CryptoSignJson(
+        WalletHandle,
+        String,  // my vk
+        String, // msg json
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    CryptoVerifyJson(
+        String,  // their vk
+        String, // msg json
+        Vec<u8>, // signature
+        Box<dyn Fn(IndyResult<bool>) + Send>,
+    ),
+    GetNonce(
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    SelfTest(
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    GetCryptoTypesSupported(
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
     AuthenticatedEncrypt(
         WalletHandle,
         String,  // my vk
@@ -71,6 +120,12 @@ pub enum CryptoCommand {
         Vec<u8>, // msg
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    DeriveSharedSecret(
+        WalletHandle,
+        String,  // my vk
+        String,  // their vk
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
     PackMessage(
         Vec<u8>, // plaintext message
         Vec<String>,  // list of receiver's keys
@@ -83,6 +138,10 @@ pub enum CryptoCommand {
         WalletHandle,
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    // Internal commands
+    SetStrictMessages(
+        bool, // strict
+    ),
 }
 
 pub struct CryptoCommandExecutor {
@@ -119,10 +178,46 @@ impl CryptoCommandExecutor {
                 debug!("CryptoSign command received");
                 cb(self.crypto_sign(wallet_handle, &my_vk, &msg));
             }
+            CryptoCommand::CryptoSignMultiple(wallet_handle, my_vk, msgs, cb) => {
+                debug!("CryptoSignMultiple command received");
+                cb(self.crypto_sign_multiple(wallet_handle, &my_vk, &msgs));
+            }
+            CryptoCommand::CryptoMultiSign(wallet_handle, my_vks, msg, cb) => {
+                debug!("CryptoMultiSign command received");
+                cb(self.crypto_multi_sign(wallet_handle, &my_vks, &msg));
+            }
             CryptoCommand::CryptoVerify(their_vk, msg, signature, cb) => {
                 debug!("CryptoVerify command received");
                 cb(self.crypto_verify(&their_vk, &msg, &signature));
             }
+            CryptoCommand::CryptoVerifyDetailed(their_vk, msg, signature, cb) => {
+                debug!("CryptoVerifyDetailed command received");
+                cb(self.crypto_verify_detailed(&their_vk, &msg, &signature));
+            }
+            CryptoCommand::CryptoVerifyAnyKey(their_vks, msg, signature, cb) => {
+                debug!("CryptoVerifyAnyKey command received");
+                cb(self.crypto_verify_any_key(&their_vks, &msg, &signature));
+            }
+            CryptoCommand::CryptoSignJson(wallet_handle, my_vk, msg_json, cb) => {
+                debug!("CryptoSignJson command received");
+                cb(self.crypto_sign_json(wallet_handle, &my_vk, &msg_json));
+            }
+            CryptoCommand::CryptoVerifyJson(their_vk, msg_json, signature, cb) => {
+                debug!("CryptoVerifyJson command received");
+                cb(self.crypto_verify_json(&their_vk, &msg_json, &signature));
+            }
+            CryptoCommand::GetNonce(cb) => {
+                debug!("GetNonce command received");
+                cb(self.get_nonce());
+            }
+            CryptoCommand::SelfTest(cb) => {
+                debug!("SelfTest command received");
+                cb(self.self_test());
+            }
+            CryptoCommand::GetCryptoTypesSupported(cb) => {
+                debug!("GetCryptoTypesSupported command received");
+                cb(self.get_crypto_types_supported());
+            }
             CryptoCommand::AuthenticatedEncrypt(wallet_handle, my_vk, their_vk, msg, cb) => {
                 debug!("AuthenticatedEncrypt command received");
                 cb(self.authenticated_encrypt(wallet_handle, &my_vk, &their_vk, &msg));
@@ -139,6 +234,10 @@ impl CryptoCommandExecutor {
                 debug!("AnonymousDecrypt command received");
                 cb(self.anonymous_decrypt(wallet_handle, &my_vk, &encrypted_msg));
             }
+            CryptoCommand::DeriveSharedSecret(wallet_handle, my_vk, their_vk, cb) => {
+                debug!("DeriveSharedSecret command received");
+                cb(self.derive_shared_secret(wallet_handle, &my_vk, &their_vk));
+            }
             CryptoCommand::PackMessage(message, receivers, sender_vk, wallet_handle, cb) => {
                 debug!("PackMessage command received");
                 cb(self.pack_msg(message, receivers, sender_vk, wallet_handle));
@@ -147,6 +246,10 @@ impl CryptoCommandExecutor {
                 debug!("UnpackMessage command received");
                 cb(self.unpack_msg(jwe_json, wallet_handle));
             }
+            CryptoCommand::SetStrictMessages(strict) => {
+                debug!("SetStrictMessages command received");
+                self.crypto_service.set_strict_messages(strict);
+            }
         };
     }
 
@@ -157,6 +260,8 @@ impl CryptoCommandExecutor {
             secret!(key_info)
         );
 
+        self.wallet_service.check(wallet_handle)?;
+
         let key = self.crypto_service.create_key(key_info)?;
         self.wallet_service
             .add_indy_object(wallet_handle, &key.verkey, &key, &HashMap::new())?;
@@ -187,6 +292,69 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn crypto_sign_multiple(&self, wallet_handle: WalletHandle, my_vk: &str, msgs: &[String]) -> IndyResult<String> {
+        trace!(
+            "crypto_sign_multiple >>> wallet_handle: {:?}, sender_vk: {:?}, msgs: {:?}",
+            wallet_handle, my_vk, msgs
+        );
+
+        self.crypto_service.validate_key(my_vk)?;
+
+        let key: Key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            &my_vk,
+            &RecordOptions::id_value(),
+        )?;
+
+        // A message that can't be decoded is reported in its own slot and doesn't stop the rest of the batch.
+        let signatures: Vec<BatchResult<String>> = msgs
+            .iter()
+            .map(|msg| {
+                base64::decode(msg)
+                    .and_then(|msg| self.crypto_service.sign(&key, &msg))
+                    .map(|signature| base58::to_b58(&signature))
+                    .into()
+            })
+            .collect();
+
+        let res = serde_json::to_string(&signatures)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize signatures")?;
+
+        trace!("crypto_sign_multiple <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn crypto_multi_sign(&self, wallet_handle: WalletHandle, my_vks: &[String], msg: &[u8]) -> IndyResult<String> {
+        trace!(
+            "crypto_multi_sign >>> wallet_handle: {:?}, sender_vks: {:?}, msg: {:?}",
+            wallet_handle, my_vks, msg
+        );
+
+        let mut signatures: HashMap<String, String> = HashMap::new();
+
+        for my_vk in my_vks {
+            self.crypto_service.validate_key(my_vk)?;
+
+            let key: Key = self.wallet_service.get_indy_object(
+                wallet_handle,
+                &my_vk,
+                &RecordOptions::id_value(),
+            ).map_err(|err| err.extend(format!("Can't get signer key: {}", my_vk)))?;
+
+            let signature = self.crypto_service.sign(&key, msg)?;
+
+            signatures.insert(my_vk.to_string(), base58::to_b58(&signature));
+        }
+
+        let res = serde_json::to_string(&signatures)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize signatures")?;
+
+        trace!("crypto_multi_sign <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn crypto_verify(&self,
                      their_vk: &str,
                      msg: &[u8],
@@ -205,6 +373,139 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    // Signature is made over the canonical form of the json (sorted keys, no insignificant whitespace),
+    // so logically equal objects verify against the same signature.
+    fn crypto_sign_json(&self, wallet_handle: WalletHandle, my_vk: &str, msg_json: &str) -> IndyResult<Vec<u8>> {
+        trace!(
+            "crypto_sign_json >>> wallet_handle: {:?}, sender_vk: {:?}, msg_json: {:?}",
+            wallet_handle, my_vk, msg_json
+        );
+
+        let msg = canonicalize_json(msg_json)?;
+
+        let res = self.crypto_sign(wallet_handle, my_vk, msg.as_bytes())?;
+
+        trace!("crypto_sign_json <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn crypto_verify_json(&self,
+                          their_vk: &str,
+                          msg_json: &str,
+                          signature: &[u8]) -> IndyResult<bool> {
+        trace!(
+            "crypto_verify_json >>> their_vk: {:?}, msg_json: {:?}, signature: {:?}",
+            their_vk, msg_json, signature
+        );
+
+        let msg = canonicalize_json(msg_json)?;
+
+        let res = self.crypto_verify(their_vk, msg.as_bytes(), signature)?;
+
+        trace!("crypto_verify_json <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn crypto_verify_detailed(&self,
+                              their_vk: &str,
+                              msg: &[u8],
+                              signature: &[u8]) -> IndyResult<String> {
+        trace!(
+            "crypto_verify_detailed >>> their_vk: {:?}, msg: {:?}, signature: {:?}",
+            their_vk, msg, signature
+        );
+
+        // abbreviated verkey can't be used without DID it was abbreviated against
+        let reason = if their_vk.starts_with('~') || self.crypto_service.validate_key(their_vk).is_err() {
+            Some(VERIFY_REASON_KEY_NOT_RESOLVED)
+        } else if signature.len() != ed25519_sign::SIGNATUREBYTES {
+            Some(VERIFY_REASON_MALFORMED_SIGNATURE)
+        } else if !self.crypto_service.verify(their_vk, msg, signature)? {
+            Some(VERIFY_REASON_SIGNATURE_MISMATCH)
+        } else {
+            None
+        };
+
+        let result = VerificationResult { verified: reason.is_none(), reason: reason.map(String::from) };
+
+        let res = serde_json::to_string(&result)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize verification result")?;
+
+        trace!("crypto_verify_detailed <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Keys are tried in the given order, so during a rotation the caller can put the key it
+    // expects most (usually the new one) first.
+    fn crypto_verify_any_key(&self,
+                             their_vks: &[String],
+                             msg: &[u8],
+                             signature: &[u8]) -> IndyResult<String> {
+        trace!(
+            "crypto_verify_any_key >>> their_vks: {:?}, msg: {:?}, signature: {:?}",
+            their_vks, msg, signature
+        );
+
+        for their_vk in their_vks {
+            self.crypto_service.validate_key(their_vk)?;
+        }
+
+        let mut verkey = None;
+
+        for their_vk in their_vks {
+            if self.crypto_service.verify(their_vk, msg, signature)? {
+                verkey = Some(their_vk.to_string());
+                break;
+            }
+        }
+
+        let result = AnyKeyVerificationResult { verified: verkey.is_some(), verkey };
+
+        let res = serde_json::to_string(&result)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize verification result")?;
+
+        trace!("crypto_verify_any_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_nonce(&self) -> IndyResult<String> {
+        trace!("get_nonce >>>");
+
+        let res = base58::to_b58(&self.crypto_service.gen_nonce());
+
+        trace!("get_nonce <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn self_test(&self) -> IndyResult<String> {
+        trace!("self_test >>>");
+
+        let report = self.crypto_service.self_test()?;
+
+        let res = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize self-test report")?;
+
+        trace!("self_test <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn get_crypto_types_supported(&self) -> IndyResult<String> {
+        trace!("get_crypto_types_supported >>>");
+
+        let res = serde_json::to_string(&self.crypto_service.supported_crypto_types())
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize supported crypto types")?;
+
+        trace!("get_crypto_types_supported <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     //TODO begin deprecation process this function. It will be replaced by pack
     fn authenticated_encrypt(
         &self,
@@ -213,7 +514,7 @@ impl CryptoCommandExecutor {
         their_vk: &str,
         msg: &[u8],
     ) -> IndyResult<Vec<u8>> {
-        trace!("authenticated_encrypt >>> wallet_handle: {:?}, my_vk: {:?}, their_vk: {:?}, msg: {:?}", wallet_handle, my_vk, their_vk, msg);
+        trace!("authenticated_encrypt >>> wallet_handle: {:?}, my_vk: {:?}, their_vk: {:?}, msg: {:?}", wallet_handle, my_vk, their_vk, secret!(msg));
 
         self.crypto_service.validate_key(my_vk)?;
         self.crypto_service.validate_key(their_vk)?;
@@ -268,7 +569,7 @@ impl CryptoCommandExecutor {
 
         let res = (parsed_msg.sender, decrypted_msg);
 
-        trace!("authenticated_decrypt <<< res: {:?}", res);
+        trace!("authenticated_decrypt <<< sender_vk: {:?}, msg: {:?}", res.0, secret!(&res.1));
 
         Ok(res)
     }
@@ -278,7 +579,7 @@ impl CryptoCommandExecutor {
                          msg: &[u8]) -> IndyResult<Vec<u8>> {
         trace!(
             "anonymous_encrypt >>> their_vk: {:?}, msg: {:?}",
-            their_vk, msg
+            their_vk, secret!(msg)
         );
 
         self.crypto_service.validate_key(their_vk)?;
@@ -311,7 +612,34 @@ impl CryptoCommandExecutor {
             .crypto_service
             .crypto_box_seal_open(&my_key, &encrypted_msg)?;
 
-        trace!("anonymous_decrypt <<< res: {:?}", res);
+        trace!("anonymous_decrypt <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    fn derive_shared_secret(&self,
+                            wallet_handle: WalletHandle,
+                            my_vk: &str,
+                            their_vk: &str) -> IndyResult<String> {
+        trace!(
+            "derive_shared_secret >>> wallet_handle: {:?}, my_vk: {:?}, their_vk: {:?}",
+            wallet_handle, my_vk, their_vk
+        );
+
+        self.crypto_service.validate_key(my_vk)?;
+        self.crypto_service.validate_key(their_vk)?;
+
+        let my_key: Key = self.wallet_service.get_indy_object(
+            wallet_handle,
+            my_vk,
+            &RecordOptions::id_value(),
+        )?;
+
+        let shared_secret = self.crypto_service.derive_shared_secret(&my_key, their_vk)?;
+
+        let res = base58::to_b58(&shared_secret);
+
+        trace!("derive_shared_secret <<< res: {:?}", secret!(&res));
 
         Ok(res)
     }
@@ -322,7 +650,10 @@ impl CryptoCommandExecutor {
             wallet_handle, verkey, metadata
         );
 
+        self.wallet_service.check(wallet_handle)?;
+
         self.crypto_service.validate_key(verkey)?;
+        self._ensure_key_exists(wallet_handle, verkey)?;
 
         let metadata = KeyMetadata {
             value: metadata.to_string(),
@@ -343,6 +674,7 @@ impl CryptoCommandExecutor {
         );
 
         self.crypto_service.validate_key(verkey)?;
+        self._ensure_key_exists(wallet_handle, verkey)?;
 
         let metadata = self.wallet_service.get_indy_object::<KeyMetadata>(
             wallet_handle,
@@ -357,6 +689,14 @@ impl CryptoCommandExecutor {
         Ok(res)
     }
 
+    fn _ensure_key_exists(&self, wallet_handle: WalletHandle, verkey: &str) -> IndyResult<()> {
+        if !self.wallet_service.record_exists::<Key>(wallet_handle, verkey)? {
+            return Err(err_msg(IndyErrorKind::WalletItemNotFound, format!("Key {} not found in the wallet", verkey)));
+        }
+
+        Ok(())
+    }
+
     //TODO: Refactor pack to be more modular to version changes or crypto_scheme changes
     //this match statement is super messy, but the easiest way to comply with current architecture
     pub fn pack_msg(
@@ -1,24 +1,31 @@
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use serde_json;
 
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use crate::commands::ledger::LedgerCommand;
-use crate::domain::crypto::did::{Did, DidValue, DidMetadata, DidWithMeta, MyDidInfo, TemporaryDid, TheirDid, TheirDidInfo, DidMethod};
-use crate::domain::crypto::key::KeyInfo;
-use crate::domain::ledger::attrib::{AttribData, Endpoint, GetAttrReplyResult};
-use crate::domain::ledger::nym::{GetNymReplyResult, GetNymResultDataV0};
-use crate::domain::ledger::response::Reply;
+use crate::domain::crypto::did::{Did, DidValue, DidMetadata, DidWithMeta, MyDidInfo, TemporaryDid, TheirDid, TheirDidInfo, DidMethod, ExportedDidKey, ConnectionRequest, TheirDidWithEndpoint, TheirDidFilter, DidImportPolicy, DidIntegrityReport, DidVerkey, VerkeySource, DidValidationResult};
+use crate::domain::batch::BatchResult;
+use crate::domain::crypto::combo_box::ComboBox;
+use crate::domain::crypto::key::{Key, KeyInfo, KdfParams};
+use crate::domain::ledger::attrib::Endpoint;
 use crate::domain::pairwise::Pairwise;
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
+use crate::services::did::verkey_cache::{CachedVerkey, VerkeyCache};
 use crate::services::ledger::LedgerService;
+use crate::services::ledger::backoff::BackoffPolicy;
+use crate::utils::crypto::base58;
+use crate::utils::crypto::verkey_builder;
 use indy_wallet::{RecordOptions, SearchOptions, WalletService};
 use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
+use indy_api_types::validation::Validatable;
+use indy_utils::crypto::base64;
 use indy_utils::next_command_handle;
-use rust_base58::{FromBase58, ToBase58};
+use zeroize::Zeroizing;
 
 pub enum DidCommand {
     CreateAndStoreMyDid(
@@ -34,10 +41,20 @@ pub enum DidCommand {
         WalletHandle,
         DidValue, // my did
         Box<dyn Fn(IndyResult<()>) + Send>),
+    RotateAndPublish(
+        PoolHandle,
+        WalletHandle,
+        DidValue, // submitter did
+        DidValue, // target did
+        Box<dyn Fn(IndyResult<String>) + Send>),
     StoreTheirDid(
         WalletHandle,
         TheirDidInfo, // their did info json
         Box<dyn Fn(IndyResult<()>) + Send>),
+    StoreTheirDids(
+        WalletHandle,
+        Vec<serde_json::Value>, // their did info jsons
+        Box<dyn Fn(IndyResult<String>) + Send>),
     GetMyDidWithMeta(
         WalletHandle,
         DidValue, // my did
@@ -45,11 +62,20 @@ pub enum DidCommand {
     ListMyDidsWithMeta(
         WalletHandle,
         Box<dyn Fn(IndyResult<String>) + Send>),
+    ListTheirDids(
+        WalletHandle,
+        Option<TheirDidFilter>, // filter
+        Box<dyn Fn(IndyResult<String>) + Send>),
     KeyForDid(
         PoolHandle, // pool handle
         WalletHandle,
         DidValue, // did (my or their)
         Box<dyn Fn(IndyResult<String/*key*/>) + Send>),
+    KeyForDidDetailed(
+        PoolHandle, // pool handle
+        WalletHandle,
+        DidValue, // did (my or their)
+        Box<dyn Fn(IndyResult<String/*verkey with source json*/>) + Send>),
     KeyForLocalDid(
         WalletHandle,
         DidValue, // did (my or their)
@@ -64,6 +90,28 @@ pub enum DidCommand {
         PoolHandle, // pool handle
         DidValue, // did
         Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>),
+    RekeyEndpoint(
+        WalletHandle,
+        DidValue, // did
+        String, // new transport key
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    PrepareAnoncryptedConnectionRequest(
+        WalletHandle,
+        String, // their verkey
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>),
+    ParseConnectionRequest(
+        WalletHandle,
+        String, // my verkey
+        Vec<u8>, // anoncrypted request
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ReEncrypt(
+        WalletHandle,
+        DidValue, // my did
+        String, // original sender verkey
+        String, // new recipient verkey
+        Vec<u8>, // authcrypted message
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>),
     SetDidMetadata(
         WalletHandle,
         DidValue, // did
@@ -77,19 +125,88 @@ pub enum DidCommand {
         DidValue, // did
         String, // verkey
         Box<dyn Fn(IndyResult<String>) + Send>),
+    ExpandVerkey(
+        DidValue, // did
+        String, // verkey
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    DidFromVerkey(
+        String, // verkey
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ComputeDidFingerprint(
+        String, // verkey
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ValidateDidString(
+        String, // did
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerkeyToCurve25519(
+        String, // verkey
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    SignkeyToCurve25519(
+        WalletHandle,
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ForgetDid(
+        WalletHandle,
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    ExportDidKey(
+        WalletHandle,
+        DidValue, // my did
+        String, // passphrase
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    ImportDidKey(
+        WalletHandle,
+        String, // passphrase
+        String, // exported blob
+        Option<String>, // policy
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckDidIntegrity(
+        WalletHandle,
+        DidValue, // my did
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyMulti(
+        WalletHandle,
+        HashMap<String, String>, // did -> base58 signature
+        Vec<u8>, // msg
+        u32, // threshold
+        Box<dyn Fn(IndyResult<bool>) + Send>),
+    GenerateMnemonicSeed(
+        BoxedCallbackStringStringSend),
+    SeedFromMnemonic(
+        String, // mnemonic
+        Box<dyn Fn(IndyResult<String>) + Send>),
     // Internal commands
     GetNymAck(
         WalletHandle,
         DidValue, // did
         IndyResult<String>, // GetNym Result
-        CommandHandle, // deferred cmd id
+        CommandHandle, // ledger lookup id
     ),
     // Internal commands
     GetAttribAck(
         WalletHandle,
+        DidValue, // did
         IndyResult<String>, // GetAttrib Result
+        CommandHandle, // ledger lookup id
+    ),
+    // Internal commands
+    RotateAndPublishAck(
+        IndyResult<String>, // Nym Result
         CommandHandle, // deferred cmd id
     ),
+    // Internal commands
+    KeyForDidDetailedAck(
+        WalletHandle,
+        DidValue, // their did fetched from the ledger
+        Box<dyn Fn(IndyResult<String/*verkey with source json*/>) + Send>),
+    // Internal commands
+    SetBackoffPolicy(
+        Option<u32>, // max retries
+        Option<Duration>, // base delay
+    ),
+    SetVerkeyCacheTtl(
+        Duration, // ttl
+    ),
     QualifyDid(
         WalletHandle,
         DidValue, // did
@@ -109,22 +226,106 @@ macro_rules! ensure_their_did {
         );
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum LedgerLookupType {
+    Nym,
+    Attrib,
+}
+
+/// GET_NYM or GET_ATTRIB request deferred commands are waiting for.
+/// Commands that need the same DID share one lookup, so the pool isn't asked for it twice at once.
+struct LedgerLookup {
+    type_: LedgerLookupType,
+    wallet_handle: WalletHandle,
+    pool_handle: PoolHandle,
+    did: DidValue,
+    request: String,
+    retry: u32,
+    resubmit_at: Option<Instant>,
+    deferred_cmd_ids: Vec<CommandHandle>,
+}
+
+/// Builds the command a reply to a ledger lookup is acked with.
+type LedgerLookupAck = Box<dyn Fn(IndyResult<String>) -> DidCommand + Send>;
+
+/// Sends a ledger lookup request to the pool, the reply comes back as the command built by the ack.
+type LedgerLookupSubmitter = Box<dyn Fn(PoolHandle, String, LedgerLookupAck) -> IndyResult<()>>;
+
+fn submit_ledger_lookup_to_pool(pool_handle: PoolHandle, request: String, ack: LedgerLookupAck) -> IndyResult<()> {
+    CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::SubmitRequest(
+            pool_handle,
+            request,
+            Box::new(move |result| {
+                if let Err(err) = CommandExecutor::instance().send(Command::Did(ack(result))) {
+                    error!("Can't send ledger lookup reply: {}", err);
+                }
+            }),
+        )))
+}
+
 pub struct DidCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     ledger_service: Rc<LedgerService>,
     deferred_commands: RefCell<HashMap<CommandHandle, DidCommand>>,
+    ledger_lookups: RefCell<HashMap<CommandHandle, LedgerLookup>>,
+    backoff_policy: Cell<BackoffPolicy>,
+    verkey_cache: Rc<VerkeyCache>,
+    submit_ledger_lookup: LedgerLookupSubmitter,
 }
 
 impl DidCommandExecutor {
     pub fn new(wallet_service: Rc<WalletService>,
                crypto_service: Rc<CryptoService>,
-               ledger_service: Rc<LedgerService>) -> DidCommandExecutor {
+               ledger_service: Rc<LedgerService>,
+               verkey_cache: Rc<VerkeyCache>) -> DidCommandExecutor {
+        DidCommandExecutor::with_ledger_submitter(wallet_service, crypto_service, ledger_service, verkey_cache,
+                                                  Box::new(submit_ledger_lookup_to_pool))
+    }
+
+    fn with_ledger_submitter(wallet_service: Rc<WalletService>,
+                             crypto_service: Rc<CryptoService>,
+                             ledger_service: Rc<LedgerService>,
+                             verkey_cache: Rc<VerkeyCache>,
+                             submit_ledger_lookup: LedgerLookupSubmitter) -> DidCommandExecutor {
         DidCommandExecutor {
             wallet_service,
             crypto_service,
             ledger_service,
             deferred_commands: RefCell::new(HashMap::new()),
+            ledger_lookups: RefCell::new(HashMap::new()),
+            backoff_policy: Cell::new(BackoffPolicy::default()),
+            verkey_cache,
+            submit_ledger_lookup,
+        }
+    }
+
+    /// Time left until the next ledger lookup the pool didn't answer is due to be resubmitted.
+    pub fn resubmission_timeout(&self) -> Option<Duration> {
+        let now = Instant::now();
+
+        self.ledger_lookups.borrow()
+            .values()
+            .filter_map(|lookup| lookup.resubmit_at)
+            .min()
+            .map(|resubmit_at| if resubmit_at > now { resubmit_at - now } else { Duration::from_secs(0) })
+    }
+
+    pub fn resubmit_due_lookups(&self) {
+        let now = Instant::now();
+
+        let due_lookup_ids: Vec<CommandHandle> = self.ledger_lookups.borrow_mut()
+            .iter_mut()
+            .filter(|(_, lookup)| lookup.resubmit_at.map_or(false, |resubmit_at| resubmit_at <= now))
+            .map(|(lookup_id, lookup)| {
+                lookup.resubmit_at = None;
+                *lookup_id
+            })
+            .collect();
+
+        for lookup_id in due_lookup_ids {
+            self._submit_ledger_lookup(lookup_id);
         }
     }
 
@@ -142,10 +343,18 @@ impl DidCommandExecutor {
                 debug!("ReplaceKeysApply command received");
                 cb(self.replace_keys_apply(wallet_handle, &did));
             }
+            DidCommand::RotateAndPublish(pool_handle, wallet_handle, submitter_did, target_did, cb) => {
+                debug!("RotateAndPublish command received");
+                self.rotate_and_publish(pool_handle, wallet_handle, submitter_did, target_did, cb);
+            }
             DidCommand::StoreTheirDid(wallet_handle, their_did_info, cb) => {
                 debug!("StoreTheirDid command received");
                 cb(self.store_their_did(wallet_handle, &their_did_info));
             }
+            DidCommand::StoreTheirDids(wallet_handle, their_did_infos, cb) => {
+                debug!("StoreTheirDids command received");
+                cb(self.store_their_dids(wallet_handle, &their_did_infos));
+            }
             DidCommand::GetMyDidWithMeta(wallet_handle, my_did, cb) => {
                 debug!("GetMyDidWithMeta command received");
                 cb(self.get_my_did_with_meta(wallet_handle, &my_did))
@@ -154,10 +363,18 @@ impl DidCommandExecutor {
                 debug!("ListMyDidsWithMeta command received");
                 cb(self.list_my_dids_with_meta(wallet_handle));
             }
+            DidCommand::ListTheirDids(wallet_handle, filter, cb) => {
+                debug!("ListTheirDids command received");
+                cb(self.list_their_dids(wallet_handle, filter.unwrap_or_default()));
+            }
             DidCommand::KeyForDid(pool_handle, wallet_handle, did, cb) => {
                 debug!("KeyForDid command received");
                 self.key_for_did(pool_handle, wallet_handle, did, cb);
             }
+            DidCommand::KeyForDidDetailed(pool_handle, wallet_handle, did, cb) => {
+                debug!("KeyForDidDetailed command received");
+                self.key_for_did_detailed(pool_handle, wallet_handle, did, cb);
+            }
             DidCommand::KeyForLocalDid(wallet_handle, did, cb) => {
                 debug!("KeyForLocalDid command received");
                 cb(self.key_for_local_did(wallet_handle, &did));
@@ -170,6 +387,22 @@ impl DidCommandExecutor {
                 debug!("GetEndpointForDid command received");
                 self.get_endpoint_for_did(wallet_handle, pool_handle, did, cb);
             }
+            DidCommand::RekeyEndpoint(wallet_handle, did, transport_key, cb) => {
+                debug!("RekeyEndpoint command received");
+                cb(self.rekey_endpoint(wallet_handle, &did, transport_key));
+            }
+            DidCommand::PrepareAnoncryptedConnectionRequest(wallet_handle, their_vk, my_did, cb) => {
+                debug!("PrepareAnoncryptedConnectionRequest command received");
+                cb(self.prepare_anoncrypted_connection_request(wallet_handle, &their_vk, &my_did));
+            }
+            DidCommand::ParseConnectionRequest(wallet_handle, my_vk, request, cb) => {
+                debug!("ParseConnectionRequest command received");
+                cb(self.parse_connection_request(wallet_handle, &my_vk, &request));
+            }
+            DidCommand::ReEncrypt(wallet_handle, my_did, sender_vk, recipient_vk, encrypted_msg, cb) => {
+                debug!("ReEncrypt command received");
+                cb(self.re_encrypt(wallet_handle, &my_did, &sender_vk, &recipient_vk, &encrypted_msg));
+            }
             DidCommand::SetDidMetadata(wallet_handle, did, metadata, cb) => {
                 debug!("SetDidMetadata command received");
                 cb(self.set_did_metadata(wallet_handle, &did, metadata));
@@ -182,13 +415,81 @@ impl DidCommandExecutor {
                 debug!("AbbreviateVerkey command received");
                 cb(self.abbreviate_verkey(&did, verkey));
             }
-            DidCommand::GetNymAck(wallet_handle, did, result, deferred_cmd_id) => {
+            DidCommand::ExpandVerkey(did, verkey, cb) => {
+                debug!("ExpandVerkey command received");
+                cb(self.expand_verkey(&did, verkey));
+            }
+            DidCommand::DidFromVerkey(verkey, cb) => {
+                debug!("DidFromVerkey command received");
+                cb(self.did_from_verkey(&verkey));
+            }
+            DidCommand::ComputeDidFingerprint(verkey, cb) => {
+                debug!("ComputeDidFingerprint command received");
+                cb(self.compute_did_fingerprint(&verkey));
+            }
+            DidCommand::ValidateDidString(did, cb) => {
+                debug!("ValidateDidString command received");
+                cb(self.validate_did_string(did));
+            }
+            DidCommand::VerkeyToCurve25519(verkey, cb) => {
+                debug!("VerkeyToCurve25519 command received");
+                cb(self.verkey_to_curve25519(&verkey));
+            }
+            DidCommand::SignkeyToCurve25519(wallet_handle, did, cb) => {
+                debug!("SignkeyToCurve25519 command received");
+                cb(self.signkey_to_curve25519(wallet_handle, &did));
+            }
+            DidCommand::ForgetDid(wallet_handle, did, cb) => {
+                debug!("ForgetDid command received");
+                cb(self.forget_did(wallet_handle, &did));
+            }
+            DidCommand::ExportDidKey(wallet_handle, did, passphrase, cb) => {
+                debug!("ExportDidKey command received");
+                cb(self.export_did_key(wallet_handle, &did, &passphrase));
+            }
+            DidCommand::ImportDidKey(wallet_handle, passphrase, blob, policy, cb) => {
+                debug!("ImportDidKey command received");
+                cb(self.import_did_key(wallet_handle, &passphrase, &blob, policy.as_ref().map(String::as_str)));
+            }
+            DidCommand::CheckDidIntegrity(wallet_handle, did, cb) => {
+                debug!("CheckDidIntegrity command received");
+                cb(self.check_did_integrity(wallet_handle, &did));
+            }
+            DidCommand::VerifyMulti(wallet_handle, signatures, msg, threshold, cb) => {
+                debug!("VerifyMulti command received");
+                cb(self.verify_multi(wallet_handle, &signatures, &msg, threshold));
+            }
+            DidCommand::GenerateMnemonicSeed(cb) => {
+                debug!("GenerateMnemonicSeed command received");
+                cb(self.generate_mnemonic_seed());
+            }
+            DidCommand::SeedFromMnemonic(mnemonic, cb) => {
+                debug!("SeedFromMnemonic command received");
+                cb(self.seed_from_mnemonic(&mnemonic));
+            }
+            DidCommand::GetNymAck(wallet_handle, did, result, lookup_id) => {
                 debug!("GetNymAck command received");
-                self.get_nym_ack(wallet_handle, did, result, deferred_cmd_id);
+                self.get_nym_ack(wallet_handle, did, result, lookup_id);
             }
-            DidCommand::GetAttribAck(wallet_handle, result, deferred_cmd_id) => {
+            DidCommand::GetAttribAck(wallet_handle, did, result, lookup_id) => {
                 debug!("GetAttribAck command received");
-                self.get_attrib_ack(wallet_handle, result, deferred_cmd_id);
+                self.get_attrib_ack(wallet_handle, did, result, lookup_id);
+            }
+            DidCommand::RotateAndPublishAck(result, deferred_cmd_id) => {
+                debug!("RotateAndPublishAck command received");
+                self.rotate_and_publish_ack(result, deferred_cmd_id);
+            }
+            DidCommand::KeyForDidDetailedAck(wallet_handle, did, cb) => {
+                debug!("KeyForDidDetailedAck command received");
+                cb(self.key_for_did_detailed_ack(wallet_handle, &did));
+            }
+            DidCommand::SetBackoffPolicy(max_retries, base_delay) => {
+                debug!("SetBackoffPolicy command received");
+                self.backoff_policy.set(self.backoff_policy.get().update(max_retries, base_delay));
+            }
+            DidCommand::SetVerkeyCacheTtl(ttl) => {
+                debug!("SetVerkeyCacheTtl command received");
+                self.verkey_cache.set_ttl(ttl);
             }
             DidCommand::QualifyDid(wallet_handle, did, method, cb) => {
                 debug!("QualifyDid command received");
@@ -202,6 +503,8 @@ impl DidCommandExecutor {
                                my_did_info: &MyDidInfo) -> IndyResult<(String, String)> {
         debug!("create_and_store_my_did >>> wallet_handle: {:?}, my_did_info_json: {:?}", wallet_handle, secret!(my_did_info));
 
+        self.wallet_service.check(wallet_handle)?;
+
         let (did, key) = self.crypto_service.create_my_did(&my_did_info)?;
 
         if let Ok(current_did) = self._wallet_get_my_did(wallet_handle, &did.did) {
@@ -229,6 +532,8 @@ impl DidCommandExecutor {
                           my_did: &DidValue) -> IndyResult<String> {
         debug!("replace_keys_start >>> wallet_handle: {:?}, key_info_json: {:?}, my_did: {:?}", wallet_handle, secret!(key_info), my_did);
 
+        self.wallet_service.check(wallet_handle)?;
+
         self.crypto_service.validate_did(my_did)?;
 
         let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
@@ -236,8 +541,10 @@ impl DidCommandExecutor {
         let temporary_key = self.crypto_service.create_key(&key_info)?;
         let my_temporary_did = TemporaryDid { did: my_did.did, verkey: temporary_key.verkey.clone() };
 
-        self.wallet_service.add_indy_object(wallet_handle, &temporary_key.verkey, &temporary_key, &HashMap::new())?;
-        self.wallet_service.add_indy_object(wallet_handle, &my_temporary_did.did.0, &my_temporary_did, &HashMap::new())?;
+        self.wallet_service.transaction(wallet_handle, || {
+            self.wallet_service.add_indy_object(wallet_handle, &temporary_key.verkey, &temporary_key, &HashMap::new())?;
+            self.wallet_service.add_indy_object(wallet_handle, &my_temporary_did.did.0, &my_temporary_did, &HashMap::new())
+        })?;
 
         let res = my_temporary_did.verkey;
 
@@ -251,6 +558,8 @@ impl DidCommandExecutor {
                           my_did: &DidValue) -> IndyResult<()> {
         debug!("replace_keys_apply >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
 
+        self.wallet_service.check(wallet_handle)?;
+
         self.crypto_service.validate_did(my_did)?;
 
         let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
@@ -259,28 +568,155 @@ impl DidCommandExecutor {
 
         let my_did = Did::from(my_temporary_did);
 
-        self.wallet_service.update_indy_object(wallet_handle, &my_did.did.0, &my_did)?;
-        self.wallet_service.delete_indy_record::<TemporaryDid>(wallet_handle, &my_did.did.0)?;
+        self.wallet_service.transaction(wallet_handle, || {
+            self.wallet_service.update_indy_object(wallet_handle, &my_did.did.0, &my_did)?;
+            self.wallet_service.delete_indy_record::<TemporaryDid>(wallet_handle, &my_did.did.0)
+        })?;
+        self.verkey_cache.invalidate(wallet_handle, &my_did.did.0);
 
         debug!("replace_keys_apply <<<");
 
         Ok(())
     }
 
+    fn rotate_and_publish(&self,
+                          pool_handle: PoolHandle,
+                          wallet_handle: WalletHandle,
+                          submitter_did: DidValue,
+                          target_did: DidValue,
+                          cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("rotate_and_publish >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}",
+               pool_handle, wallet_handle, submitter_did, target_did);
+
+        try_cb!(self.wallet_service.check(wallet_handle), cb);
+
+        try_cb!(self.crypto_service.validate_did(&submitter_did), cb);
+        try_cb!(self.crypto_service.validate_did(&target_did), cb);
+
+        // A key left pending by a previously rejected rotation is published again instead of generating a new one
+        let pending_did = try_cb!(self.wallet_service.get_indy_opt_object::<TemporaryDid>(wallet_handle, &target_did.0, &RecordOptions::id_value()), cb);
+
+        let temporary_verkey = match pending_did {
+            Some(pending_did) => pending_did.verkey,
+            None => try_cb!(self.replace_keys_start(wallet_handle, &KeyInfo { seed: None, crypto_type: None }, &target_did), cb)
+        };
+
+        let nym_request = try_cb!(self.ledger_service.build_nym_request(&submitter_did, &target_did, Some(&temporary_verkey), None, None), cb);
+
+        // Apply the new key only after the ledger has accepted the NYM transaction.
+        let deferred_cmd_id = self._defer_command(DidCommand::ReplaceKeysApply(
+            wallet_handle,
+            target_did,
+            Box::new(move |res| cb(res.map(|()| temporary_verkey.clone()))),
+        ));
+
+        CommandExecutor::instance()
+            .send(Command::Ledger(LedgerCommand::SignAndSubmitRequest(
+                pool_handle,
+                wallet_handle,
+                submitter_did,
+                nym_request,
+                Box::new(move |result| {
+                    CommandExecutor::instance()
+                        .send(Command::Did(DidCommand::RotateAndPublishAck(
+                            result,
+                            deferred_cmd_id,
+                        ))).unwrap();
+                }),
+            ))).unwrap();
+
+        debug!("rotate_and_publish <<<");
+    }
+
+    fn rotate_and_publish_ack(&self,
+                              nym_reply_result: IndyResult<String>,
+                              deferred_cmd_id: CommandHandle) {
+        trace!("rotate_and_publish_ack >>> nym_reply_result: {:?}", nym_reply_result);
+
+        let res = nym_reply_result
+            .and_then(|nym_reply| self.ledger_service.parse_write_response(&nym_reply));
+
+        self._execute_deferred_command(deferred_cmd_id, res.err());
+    }
+
     fn store_their_did(&self,
                        wallet_handle: WalletHandle,
                        their_did_info: &TheirDidInfo) -> IndyResult<()> {
         debug!("store_their_did >>> wallet_handle: {:?}, their_did_info: {:?}", wallet_handle, their_did_info);
 
+        self.wallet_service.check(wallet_handle)?;
+
         let their_did = self.crypto_service.create_their_did(their_did_info)?;
 
+        if their_did_info.overwrite != Some(true) {
+            let stored_did = self.wallet_service.get_indy_opt_object::<TheirDid>(wallet_handle, &their_did.did.0, &RecordOptions::id_value())?;
+
+            if let Some(stored_did) = stored_did {
+                // A DID stored without verkey keeps the DID itself as a placeholder verkey,
+                // so the first real verkey for it is accepted without "overwrite".
+                let stored_without_verkey = stored_did.verkey == stored_did.did.to_unqualified().0;
+
+                if stored_without_verkey && their_did_info.verkey.is_some() {
+                    self.wallet_service.upsert_indy_object(wallet_handle, &their_did.did.0, &their_did)?;
+                    self.verkey_cache.invalidate(wallet_handle, &their_did.did.0);
+
+                    debug!("store_their_did <<< verkey set");
+                    return Ok(());
+                }
+
+                if their_did_info.verkey.is_some() && stored_did.verkey != their_did.verkey {
+                    return Err(err_msg(IndyErrorKind::InvalidState,
+                                       format!("Their DID {} is already stored with another verkey. Set \"overwrite\" to replace it", their_did.did.0)));
+                }
+
+                debug!("store_their_did <<< already stored");
+                return Ok(());
+            }
+        }
+
         self.wallet_service.upsert_indy_object(wallet_handle, &their_did.did.0, &their_did)?;
+        self.verkey_cache.invalidate(wallet_handle, &their_did.did.0);
 
         debug!("store_their_did <<<");
 
         Ok(())
     }
 
+    fn store_their_dids(&self,
+                        wallet_handle: WalletHandle,
+                        their_did_infos: &[serde_json::Value]) -> IndyResult<String> {
+        debug!("store_their_dids >>> wallet_handle: {:?}, their_did_infos: {:?}", wallet_handle, their_did_infos);
+
+        self.wallet_service.check(wallet_handle)?;
+
+        // A broken entry is reported in its own slot and doesn't stop the rest of the batch.
+        let results: Vec<BatchResult<String>> = their_did_infos
+            .iter()
+            .map(|their_did_info| self._store_their_did_json(wallet_handle, their_did_info).into())
+            .collect();
+
+        let res = serde_json::to_string(&results)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize store their DIDs results")?;
+
+        debug!("store_their_dids <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _store_their_did_json(&self,
+                             wallet_handle: WalletHandle,
+                             their_did_info: &serde_json::Value) -> IndyResult<String> {
+        let their_did_info: TheirDidInfo = serde_json::from_value(their_did_info.clone())
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid their DID info json")?;
+
+        their_did_info.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        self.store_their_did(wallet_handle, &their_did_info)?;
+
+        Ok(their_did_info.did.0)
+    }
+
     fn get_my_did_with_meta(&self, wallet_handle: WalletHandle, my_did: &DidValue) -> IndyResult<String> {
         debug!("get_my_did_with_meta >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
 
@@ -359,6 +795,8 @@ impl DidCommandExecutor {
             dids.push(did_with_meta);
         }
 
+        dids.sort_by(|a, b| a.did.0.cmp(&b.did.0));
+
         let res = serde_json::to_string(&dids)
             .to_indy(IndyErrorKind::InvalidState, "Can't serialize DIDs list")?;
 
@@ -367,6 +805,59 @@ impl DidCommandExecutor {
         Ok(res)
     }
 
+    fn list_their_dids(&self, wallet_handle: WalletHandle, filter: TheirDidFilter) -> IndyResult<String> {
+        debug!("list_their_dids >>> wallet_handle: {:?}, filter: {:?}", wallet_handle, filter);
+
+        let mut did_search =
+            self.wallet_service.search_indy_records::<TheirDid>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        let mut endpoint_search =
+            self.wallet_service.search_indy_records::<Endpoint>(wallet_handle, "{}", &SearchOptions::id_value())?;
+
+        let mut endpoint_map: HashMap<String, Endpoint> = HashMap::new();
+
+        while let Some(record) = endpoint_search.fetch_next_record()? {
+            let did_id = record.get_id();
+            let endpoint: Endpoint = record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value for Endpoint record"))
+                .and_then(|endpoint_json| serde_json::from_str(&endpoint_json)
+                    .to_indy(IndyErrorKind::InvalidState, format!("Cannot deserialize Endpoint: {:?}", did_id)))?;
+            endpoint_map.insert(String::from(did_id), endpoint);
+        }
+
+        let mut dids: Vec<TheirDidWithEndpoint> = Vec::new();
+
+        while let Some(did_record) = did_search.fetch_next_record()? {
+            let did_id = did_record.get_id();
+
+            let their_did: TheirDid = did_record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value for Their DID record"))
+                .and_then(|did_json| serde_json::from_str(&did_json)
+                    .to_indy(IndyErrorKind::InvalidState, format!("Cannot deserialize Their DID: {:?}", did_id)))?;
+
+            let endpoint = endpoint_map.remove(&their_did.did.0);
+
+            let their_did = TheirDidWithEndpoint {
+                did: their_did.did,
+                verkey: their_did.verkey,
+                endpoint,
+            };
+
+            if filter.matches(&their_did) {
+                dids.push(their_did);
+            }
+        }
+
+        dids.sort_by(|a, b| a.did.0.cmp(&b.did.0));
+
+        let res = serde_json::to_string(&dids)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize their DIDs list")?;
+
+        debug!("list_their_dids <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn key_for_did(&self,
                    pool_handle: PoolHandle,
                    wallet_handle: WalletHandle,
@@ -383,6 +874,20 @@ impl DidCommandExecutor {
             Err(err) => return cb(Err(err))
         };
 
+        // Look to verkeys recently resolved from the ledger
+        match self.verkey_cache.get(wallet_handle, &did.0, Instant::now()) {
+            CachedVerkey::Fresh(verkey, _) => return cb(Ok(verkey)),
+            CachedVerkey::Expired => return self._fetch_their_did_from_ledger(wallet_handle,
+                                                                              pool_handle,
+                                                                              &did,
+                                                                              DidCommand::KeyForDid(
+                                                                                  pool_handle,
+                                                                                  wallet_handle,
+                                                                                  did.clone(),
+                                                                                  cb)),
+            CachedVerkey::Missing => {}
+        }
+
         // look to their did
         let their_did = ensure_their_did!(self,
                                           wallet_handle,
@@ -402,6 +907,75 @@ impl DidCommandExecutor {
         cb(Ok(res))
     }
 
+    fn key_for_did_detailed(&self,
+                            pool_handle: PoolHandle,
+                            wallet_handle: WalletHandle,
+                            did: DidValue,
+                            cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("key_for_did_detailed >>> pool_handle: {:?}, wallet_handle: {:?}, did: {:?}", pool_handle, wallet_handle, did);
+
+        try_cb!(self.crypto_service.validate_did(&did), cb);
+
+        let (verkey, source) = match self._wallet_get_my_did(wallet_handle, &did) {
+            Ok(my_did) => (my_did.verkey, VerkeySource::My),
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {
+                match self.verkey_cache.get(wallet_handle, &did.0, Instant::now()) {
+                    CachedVerkey::Fresh(verkey, _) => (verkey, VerkeySource::TheirCached),
+                    CachedVerkey::Expired => return self._fetch_their_did_from_ledger(wallet_handle,
+                                                                                      pool_handle,
+                                                                                      &did,
+                                                                                      DidCommand::KeyForDidDetailedAck(
+                                                                                          wallet_handle,
+                                                                                          did.clone(),
+                                                                                          cb)),
+                    CachedVerkey::Missing => {
+                        let their_did = ensure_their_did!(self,
+                                                          wallet_handle,
+                                                          pool_handle,
+                                                          did,
+                                                          DidCommand::KeyForDidDetailedAck(
+                                                              wallet_handle,
+                                                              did.clone(),
+                                                              cb),
+                                                          cb);
+                        (their_did.verkey, VerkeySource::TheirCached)
+                    }
+                }
+            }
+            Err(err) => return cb(Err(err))
+        };
+
+        let res = try_cb!(DidCommandExecutor::_did_verkey_json(verkey, source), cb);
+
+        debug!("key_for_did_detailed <<< res: {:?}", res);
+
+        cb(Ok(res))
+    }
+
+    // Runs once the ledger lookup `key_for_did_detailed` was deferred on has finished.
+    // The cache entry written by the lookup tells whether the ledger answered or the stored DID was kept.
+    fn key_for_did_detailed_ack(&self,
+                                wallet_handle: WalletHandle,
+                                did: &DidValue) -> IndyResult<String> {
+        debug!("key_for_did_detailed_ack >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+        let (verkey, source) = match self.verkey_cache.get(wallet_handle, &did.0, Instant::now()) {
+            CachedVerkey::Fresh(verkey, source) => (verkey, source),
+            _ => (self._wallet_get_their_did(wallet_handle, did)?.verkey, VerkeySource::TheirCached)
+        };
+
+        let res = DidCommandExecutor::_did_verkey_json(verkey, source)?;
+
+        debug!("key_for_did_detailed_ack <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn _did_verkey_json(verkey: String, source: VerkeySource) -> IndyResult<String> {
+        serde_json::to_string(&DidVerkey { verkey, source })
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize DID verkey")
+    }
+
     fn key_for_local_did(&self,
                          wallet_handle: WalletHandle,
                          did: &DidValue) -> IndyResult<String> {
@@ -434,6 +1008,8 @@ impl DidCommandExecutor {
                             endpoint: &Endpoint) -> IndyResult<()> {
         debug!("set_endpoint_for_did >>> wallet_handle: {:?}, did: {:?}, endpoint: {:?}", wallet_handle, did, endpoint);
 
+        self.wallet_service.check(wallet_handle)?;
+
         self.crypto_service.validate_did(did)?;
 
         if endpoint.verkey.is_some() {
@@ -473,81 +1049,557 @@ impl DidCommandExecutor {
         };
     }
 
-    fn set_did_metadata(&self,
-                        wallet_handle: WalletHandle,
-                        did: &DidValue,
-                        metadata: String) -> IndyResult<()> {
-        debug!("set_did_metadata >>> wallet_handle: {:?}, did: {:?}, metadata: {:?}", wallet_handle, did, metadata);
+    fn rekey_endpoint(&self,
+                      wallet_handle: WalletHandle,
+                      did: &DidValue,
+                      transport_key: String) -> IndyResult<()> {
+        debug!("rekey_endpoint >>> wallet_handle: {:?}, did: {:?}, transport_key: {:?}", wallet_handle, did, transport_key);
+
+        self.wallet_service.check(wallet_handle)?;
 
         self.crypto_service.validate_did(did)?;
+        self.crypto_service.validate_key(&transport_key)?;
 
-        let metadata = DidMetadata { value: metadata };
+        let endpoint = self.wallet_service.get_indy_object::<Endpoint>(wallet_handle, &did.0, &RecordOptions::id_value())
+            .map_err(|err| if err.kind() == IndyErrorKind::WalletItemNotFound {
+                err.extend(format!("No endpoint is stored for DID {}", did.0))
+            } else { err })?;
 
-        self.wallet_service.upsert_indy_object(wallet_handle, &did.0, &metadata)?;
+        let endpoint = Endpoint::new(endpoint.ha, Some(transport_key));
 
-        debug!("set_did_metadata >>>");
+        self.wallet_service.update_indy_object(wallet_handle, &did.0, &endpoint)?;
 
+        debug!("rekey_endpoint <<<");
         Ok(())
     }
 
-    fn get_did_metadata(&self,
-                        wallet_handle: WalletHandle,
-                        did: &DidValue) -> IndyResult<String> {
-        debug!("get_did_metadata >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+    fn prepare_anoncrypted_connection_request(&self,
+                                              wallet_handle: WalletHandle,
+                                              their_vk: &str,
+                                              my_did: &DidValue) -> IndyResult<Vec<u8>> {
+        debug!("prepare_anoncrypted_connection_request >>> wallet_handle: {:?}, their_vk: {:?}, my_did: {:?}", wallet_handle, their_vk, my_did);
 
-        self.crypto_service.validate_did(did)?;
+        self.wallet_service.check(wallet_handle)?;
 
-        let metadata = self.wallet_service.get_indy_object::<DidMetadata>(wallet_handle, &did.0, &RecordOptions::id_value())?;
+        self.crypto_service.validate_did(my_did)?;
+        self.crypto_service.validate_key(their_vk)?;
 
-        let res = metadata.value;
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+        let endpoint = self.wallet_service.get_indy_opt_object::<Endpoint>(wallet_handle, &my_did.did.0, &RecordOptions::id_value())?;
 
-        debug!("get_did_metadata <<< res: {:?}", res);
+        let request = ConnectionRequest {
+            did: my_did.did,
+            verkey: my_did.verkey,
+            endpoint,
+        };
+
+        let request = serde_json::to_vec(&request)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize connection request")?;
+
+        let res = self.crypto_service.crypto_box_seal(their_vk, &request)?;
+
+        debug!("prepare_anoncrypted_connection_request <<< res: {:?}", res);
 
         Ok(res)
     }
 
-    fn abbreviate_verkey(&self,
-                         did: &DidValue,
-                         verkey: String) -> IndyResult<String> {
-        debug!("abbreviate_verkey >>> did: {:?}, verkey: {:?}", did, verkey);
+    fn parse_connection_request(&self,
+                                wallet_handle: WalletHandle,
+                                my_vk: &str,
+                                request: &[u8]) -> IndyResult<String> {
+        debug!("parse_connection_request >>> wallet_handle: {:?}, my_vk: {:?}, request: {:?}", wallet_handle, my_vk, request);
 
-        self.crypto_service.validate_did(&did)?;
-        self.crypto_service.validate_key(&verkey)?;
+        self.wallet_service.check(wallet_handle)?;
 
-        if !did.is_abbreviatable() {
-            return Ok(verkey);
-        }
+        self.crypto_service.validate_key(my_vk)?;
 
-        let did = &did.to_unqualified().0.from_base58()?;
-        let dverkey = &verkey.from_base58()?;
+        let my_key: Key = self.wallet_service.get_indy_object(wallet_handle, my_vk, &RecordOptions::id_value())?;
 
-        let (first_part, second_part) = dverkey.split_at(16);
+        let request = self.crypto_service.crypto_box_seal_open(&my_key, request)?;
 
-        let res = if first_part.eq(did.as_slice()) {
-            format!("~{}", second_part.to_base58())
-        } else {
-            verkey
-        };
+        let request: ConnectionRequest = serde_json::from_slice(&request)
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid connection request")?;
 
-        debug!("abbreviate_verkey <<< res: {:?}", res);
+        request.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
 
-        Ok(res)
-    }
+        self.crypto_service.validate_key(&request.verkey)?;
 
-    fn qualify_did(&self,
-                   wallet_handle: WalletHandle,
-                   did: &DidValue,
-                   method: &DidMethod) -> IndyResult<String> {
-        debug!("qualify_did >>> wallet_handle: {:?}, curr_did: {:?}, method: {:?}", wallet_handle, did, method);
+        // An anoncrypted request doesn't authenticate its sender, so the DID must at least be bound to the verkey
+        let did = request.did.to_unqualified().0;
+        let full_verkey = verkey_builder::build_full_verkey(&did, Some(&request.verkey))?;
+        let (verkey, _) = verkey_builder::split_verkey(&full_verkey);
 
-        self.crypto_service.validate_did(did)?;
+        if did != verkey_builder::did_from_verkey(verkey)? && did != verkey {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Connection request DID isn't derived from its verkey"));
+        }
 
-        let mut curr_did: Did = self.wallet_service.get_indy_object::<Did>(wallet_handle, &did.0, &RecordOptions::id_value())?;
+        if let Some(ref endpoint) = request.endpoint {
+            if let Some(ref transport_key) = endpoint.verkey {
+                self.crypto_service.validate_key(transport_key)?;
+            }
+        }
+
+        let their_did_info = TheirDidInfo::new(request.did.clone(), Some(request.verkey.clone()));
+        let their_did = self.crypto_service.create_their_did(&their_did_info)?;
+
+        // A request for a DID that is already known must neither replace its verkey nor its stored endpoint,
+        // but the same request can be parsed again
+        self.wallet_service.transaction(wallet_handle, || {
+            match self.wallet_service.get_indy_opt_object::<TheirDid>(wallet_handle, &their_did.did.0, &RecordOptions::id_value())? {
+                Some(ref stored_did) if stored_did.verkey != their_did.verkey =>
+                    return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists,
+                                       format!("Their DID {} is already stored with another verkey", their_did.did.0))),
+                Some(_) => {}
+                None => self.wallet_service.add_indy_object(wallet_handle, &their_did.did.0, &their_did, &HashMap::new())?,
+            }
+
+            if let Some(ref endpoint) = request.endpoint {
+                if !self.wallet_service.record_exists::<Endpoint>(wallet_handle, &their_did.did.0)? {
+                    self.wallet_service.add_indy_object(wallet_handle, &their_did.did.0, endpoint, &HashMap::new())?;
+                }
+            }
+
+            Ok(())
+        })?;
+        self.verkey_cache.invalidate(wallet_handle, &their_did.did.0);
+
+        let res = serde_json::to_string(&request)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize connection request")?;
+
+        debug!("parse_connection_request <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn re_encrypt(&self,
+                  wallet_handle: WalletHandle,
+                  my_did: &DidValue,
+                  sender_vk: &str,
+                  recipient_vk: &str,
+                  encrypted_msg: &[u8]) -> IndyResult<Vec<u8>> {
+        debug!("re_encrypt >>> wallet_handle: {:?}, my_did: {:?}, sender_vk: {:?}, recipient_vk: {:?}, encrypted_msg: {:?}",
+               wallet_handle, my_did, sender_vk, recipient_vk, encrypted_msg);
+
+        self.wallet_service.check(wallet_handle)?;
+
+        self.crypto_service.validate_did(my_did)?;
+        self.crypto_service.validate_key(sender_vk)?;
+        self.crypto_service.validate_key(recipient_vk)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+        let my_key: Key = self.wallet_service.get_indy_object(wallet_handle, &my_did.verkey, &RecordOptions::id_value())?;
+
+        let combo_box = self.crypto_service.crypto_box_seal_open(&my_key, encrypted_msg)?;
+
+        let combo_box = ComboBox::from_msg_pack(combo_box.as_slice())
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Can't deserialize ComboBox: {:?}", err)))?;
+
+        if combo_box.sender != sender_vk {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Message wasn't sent by the expected sender"));
+        }
+
+        let doc: Vec<u8> = base64::decode(&combo_box.msg)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Can't decode internal msg field from base64 {}", err)))?;
+
+        let nonce: Vec<u8> = base64::decode(&combo_box.nonce)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Can't decode nonce from base64 {}", err)))?;
+
+        let msg = Zeroizing::new(self.crypto_service.crypto_box_open(&my_key, &combo_box.sender, &doc, &nonce)?);
+
+        let res = self.crypto_service.crypto_box_seal(recipient_vk, &msg)?;
+
+        debug!("re_encrypt <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn set_did_metadata(&self,
+                        wallet_handle: WalletHandle,
+                        did: &DidValue,
+                        metadata: String) -> IndyResult<()> {
+        debug!("set_did_metadata >>> wallet_handle: {:?}, did: {:?}, metadata: {:?}", wallet_handle, did, metadata);
+
+        self.wallet_service.check(wallet_handle)?;
+
+        self.crypto_service.validate_did(did)?;
+
+        let metadata = DidMetadata { value: metadata };
+
+        self.wallet_service.upsert_indy_object(wallet_handle, &did.0, &metadata)?;
+
+        debug!("set_did_metadata >>>");
+
+        Ok(())
+    }
+
+    fn get_did_metadata(&self,
+                        wallet_handle: WalletHandle,
+                        did: &DidValue) -> IndyResult<String> {
+        debug!("get_did_metadata >>> wallet_handle: {:?}, did: {:?}", wallet_handle, did);
+
+        self.crypto_service.validate_did(did)?;
+
+        let metadata = self.wallet_service.get_indy_object::<DidMetadata>(wallet_handle, &did.0, &RecordOptions::id_value())?;
+
+        let res = metadata.value;
+
+        debug!("get_did_metadata <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn abbreviate_verkey(&self,
+                         did: &DidValue,
+                         verkey: String) -> IndyResult<String> {
+        debug!("abbreviate_verkey >>> did: {:?}, verkey: {:?}", did, verkey);
+
+        self.crypto_service.validate_did(&did)?;
+        self.crypto_service.validate_key(&verkey)?;
+
+        if !did.is_abbreviatable() {
+            return Ok(verkey);
+        }
+
+        let res = verkey_builder::abbreviate_verkey(&did.to_unqualified().0, &verkey)?;
+
+        debug!("abbreviate_verkey <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn expand_verkey(&self,
+                     did: &DidValue,
+                     verkey: String) -> IndyResult<String> {
+        debug!("expand_verkey >>> did: {:?}, verkey: {:?}", did, verkey);
+
+        self.crypto_service.validate_did(&did)?;
+
+        let res = verkey_builder::build_full_verkey(&did.to_unqualified().0, Some(&verkey))?;
+
+        self.crypto_service.validate_key(&res)?;
+
+        debug!("expand_verkey <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn did_from_verkey(&self, verkey: &str) -> IndyResult<String> {
+        debug!("did_from_verkey >>> verkey: {:?}", verkey);
+
+        self.crypto_service.validate_key(verkey)?;
+
+        let res = verkey_builder::did_from_verkey(verkey)?;
+
+        debug!("did_from_verkey <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn compute_did_fingerprint(&self, verkey: &str) -> IndyResult<String> {
+        debug!("compute_did_fingerprint >>> verkey: {:?}", verkey);
+
+        // Checked before validate_key so abbreviated verkeys get a clear error instead of a base58 one
+        let res = verkey_builder::fingerprint(verkey)?;
+
+        self.crypto_service.validate_key(verkey)?;
+
+        debug!("compute_did_fingerprint <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn validate_did_string(&self, did: String) -> IndyResult<String> {
+        debug!("validate_did_string >>> did: {:?}", did);
+
+        let kind = DidValue(did).kind();
+
+        let res = DidValidationResult {
+            valid: kind.is_some(),
+            kind,
+        };
+
+        let res = serde_json::to_string(&res)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize DID validation result")?;
+
+        debug!("validate_did_string <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verkey_to_curve25519(&self, verkey: &str) -> IndyResult<String> {
+        debug!("verkey_to_curve25519 >>> verkey: {:?}", verkey);
+
+        self.crypto_service.validate_key(verkey)?;
+
+        let res = self.crypto_service.verkey_to_curve25519(verkey)?;
+
+        debug!("verkey_to_curve25519 <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn signkey_to_curve25519(&self,
+                             wallet_handle: WalletHandle,
+                             my_did: &DidValue) -> IndyResult<String> {
+        debug!("signkey_to_curve25519 >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+        self.crypto_service.validate_did(my_did)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+        let key: Key = self.wallet_service.get_indy_object(wallet_handle, &my_did.verkey, &RecordOptions::id_value())?;
+
+        let res = self.crypto_service.signkey_to_curve25519(&key)?;
+
+        debug!("signkey_to_curve25519 <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    fn verify_multi(&self,
+                    wallet_handle: WalletHandle,
+                    signatures: &HashMap<String, String>,
+                    msg: &[u8],
+                    threshold: u32) -> IndyResult<bool> {
+        debug!("verify_multi >>> wallet_handle: {:?}, signatures: {:?}, msg: {:?}, threshold: {:?}", wallet_handle, signatures, msg, threshold);
+
+        if threshold == 0 {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid `threshold`: at least one valid signature must be required"));
+        }
+
+        // Several DIDs sharing one key are a single signer, so distinct verkeys are counted
+        let mut signers: HashSet<String> = HashSet::new();
+
+        for (did, signature) in signatures {
+            // Unresolvable or invalid DIDs and malformed signatures don't count towards the threshold, but don't abort verification
+            let verkey = match self.key_for_local_did(wallet_handle, &DidValue(did.to_string())) {
+                Ok(verkey) => verkey,
+                Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound || err.kind() == IndyErrorKind::InvalidStructure => {
+                    debug!("verify_multi: can't resolve verkey for DID {:?}", did);
+                    continue;
+                }
+                Err(err) => return Err(err)
+            };
+
+            let valid = base58::from_b58(signature)
+                .and_then(|signature| self.crypto_service.verify(&verkey, msg, &signature))
+                .unwrap_or(false);
+
+            if valid {
+                signers.insert(verkey);
+            }
+        }
+
+        let res = signers.len() >= threshold as usize;
+
+        debug!("verify_multi <<< res: {:?}, signers: {:?}", res, signers.len());
+
+        Ok(res)
+    }
+
+    fn generate_mnemonic_seed(&self) -> IndyResult<(String, String)> {
+        debug!("generate_mnemonic_seed >>>");
+
+        let res = self.crypto_service.generate_mnemonic_seed()?;
+
+        debug!("generate_mnemonic_seed <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    fn seed_from_mnemonic(&self, mnemonic: &str) -> IndyResult<String> {
+        debug!("seed_from_mnemonic >>> mnemonic: {:?}", secret!(mnemonic));
+
+        let res = self.crypto_service.seed_from_mnemonic(mnemonic)?;
+
+        debug!("seed_from_mnemonic <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    fn forget_did(&self,
+                  wallet_handle: WalletHandle,
+                  my_did: &DidValue) -> IndyResult<()> {
+        debug!("forget_did >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+        self.wallet_service.check(wallet_handle)?;
+
+        self.crypto_service.validate_did(my_did)?;
+
+        // The Key record isn't removed: keys are used on their own and may back other DIDs too.
+        self.wallet_service.transaction(wallet_handle, || {
+            self.wallet_service.delete_indy_record::<Did>(wallet_handle, &my_did.0)?;
+
+            // Metadata, endpoint and pending keys are optional, so only remove the ones that were set.
+            if self.wallet_service.record_exists::<DidMetadata>(wallet_handle, &my_did.0)? {
+                self.wallet_service.delete_indy_record::<DidMetadata>(wallet_handle, &my_did.0)?;
+            }
+
+            if self.wallet_service.record_exists::<Endpoint>(wallet_handle, &my_did.0)? {
+                self.wallet_service.delete_indy_record::<Endpoint>(wallet_handle, &my_did.0)?;
+            }
+
+            if self.wallet_service.record_exists::<TemporaryDid>(wallet_handle, &my_did.0)? {
+                self.wallet_service.delete_indy_record::<TemporaryDid>(wallet_handle, &my_did.0)?;
+            }
+
+            Ok(())
+        })?;
+        self.verkey_cache.invalidate(wallet_handle, &my_did.0);
+
+        debug!("forget_did <<<");
+
+        Ok(())
+    }
+
+    fn export_did_key(&self,
+                      wallet_handle: WalletHandle,
+                      my_did: &DidValue,
+                      passphrase: &str) -> IndyResult<String> {
+        debug!("export_did_key >>> wallet_handle: {:?}, my_did: {:?}, passphrase: {:?}", wallet_handle, my_did, secret!(passphrase));
+
+        self.crypto_service.validate_did(my_did)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+        let key: Key = self.wallet_service.get_indy_object(wallet_handle, &my_did.verkey, &RecordOptions::id_value())?;
+
+        let exported = ExportedDidKey { did: my_did.did, key };
+        let exported = Zeroizing::new(serde_json::to_vec(&exported)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize exported DID key")?);
+
+        let res = self.crypto_service.encrypt_with_passphrase(&exported, passphrase, &KdfParams::default())?;
+        let res = base64::encode(&res);
+
+        debug!("export_did_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn import_did_key(&self,
+                      wallet_handle: WalletHandle,
+                      passphrase: &str,
+                      blob: &str,
+                      policy: Option<&str>) -> IndyResult<String> {
+        debug!("import_did_key >>> wallet_handle: {:?}, passphrase: {:?}, blob: {:?}, policy: {:?}", wallet_handle, secret!(passphrase), blob, policy);
+
+        let policy = if let Some(policy) = policy {
+            serde_json::from_value::<DidImportPolicy>(serde_json::Value::String(policy.to_string()))
+                .to_indy(IndyErrorKind::InvalidStructure, "Invalid import policy format")?
+        } else {
+            DidImportPolicy::Fail
+        };
+
+        self.wallet_service.check(wallet_handle)?;
+
+        let blob = base64::decode(blob)?;
+        let exported = Zeroizing::new(self.crypto_service.decrypt_with_passphrase(&blob, passphrase)?);
+
+        let exported: ExportedDidKey = serde_json::from_slice(&exported)
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid exported DID key")?;
+
+        self.crypto_service.validate_did(&exported.did)?;
+        self.crypto_service.validate_key(&exported.key.verkey)?;
+
+        // make sure signkey really belongs to verkey before storing anything
+        if !self.crypto_service.check_key(&exported.key)? {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Exported signkey doesn't match verkey"));
+        }
+
+        let did = Did::new(exported.did.clone(), exported.key.verkey.clone());
+
+        let stored_did = self.wallet_service.get_indy_opt_object::<Did>(wallet_handle, &did.did.0, &RecordOptions::id_value())?;
+
+        if stored_did.is_some() {
+            match policy {
+                DidImportPolicy::Fail =>
+                    return Err(err_msg(IndyErrorKind::DIDAlreadyExists, format!("DID \"{}\" already exists", did.did.0))),
+                DidImportPolicy::Skip => {
+                    debug!("import_did_key <<< skipped, res: {:?}", did.did);
+                    return Ok(did.did.0);
+                }
+                DidImportPolicy::Overwrite => {}
+            }
+        }
+
+        self.wallet_service.transaction(wallet_handle, || {
+            // An overwritten DID doesn't leave its previous signkey behind
+            if let Some(ref stored_did) = stored_did {
+                if stored_did.verkey != did.verkey && self.wallet_service.record_exists::<Key>(wallet_handle, &stored_did.verkey)? {
+                    self.wallet_service.delete_indy_record::<Key>(wallet_handle, &stored_did.verkey)?;
+                }
+            }
+
+            self.wallet_service.upsert_indy_object(wallet_handle, &exported.key.verkey, &exported.key)?;
+            self.wallet_service.upsert_indy_object(wallet_handle, &did.did.0, &did)
+        })?;
+
+        let res = did.did.0;
+
+        debug!("import_did_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn check_did_integrity(&self,
+                           wallet_handle: WalletHandle,
+                           my_did: &DidValue) -> IndyResult<String> {
+        debug!("check_did_integrity >>> wallet_handle: {:?}, my_did: {:?}", wallet_handle, my_did);
+
+        self.crypto_service.validate_did(my_did)?;
+
+        let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
+
+        // stored signkey must belong to stored verkey
+        let verkey_ok = match self.wallet_service.get_indy_opt_object::<Key>(wallet_handle, &my_did.verkey, &RecordOptions::id_value())? {
+            Some(key) => key.verkey == my_did.verkey && self.crypto_service.check_key(&key)?,
+            None => false
+        };
+
+        // DID must be derived from verkey (first 16 bytes)
+        let did_ok = verkey_builder::did_from_verkey(&my_did.verkey)
+            .map(|did| did == my_did.did.to_short().0)
+            .unwrap_or(false);
+
+        let report = DidIntegrityReport { did: my_did.did, verkey_ok, did_ok };
+
+        let res = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize DID integrity report")?;
+
+        debug!("check_did_integrity <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn qualify_did(&self,
+                   wallet_handle: WalletHandle,
+                   did: &DidValue,
+                   method: &DidMethod) -> IndyResult<String> {
+        debug!("qualify_did >>> wallet_handle: {:?}, curr_did: {:?}, method: {:?}", wallet_handle, did, method);
+
+        self.wallet_service.check(wallet_handle)?;
+
+        self.crypto_service.validate_did(did)?;
+
+        let mut curr_did: Did = self.wallet_service.get_indy_object::<Did>(wallet_handle, &did.0, &RecordOptions::id_value())?;
 
         curr_did.did = DidValue::new(&did.to_short().0, Some(&method.0));
 
+        if curr_did.did.eq(did) {
+            debug!("qualify_did <<< already qualified, res: {:?}", curr_did.did);
+            return Ok(curr_did.did.0);
+        }
+
+        self.wallet_service.transaction(wallet_handle, || self._qualify_did(wallet_handle, did, &curr_did))?;
+
+        debug!("qualify_did <<< res: {:?}", curr_did.did);
+
+        Ok(curr_did.did.0)
+    }
+
+    // Moves the DID and every record referring to it, must run in a wallet transaction
+    fn _qualify_did(&self, wallet_handle: WalletHandle, did: &DidValue, curr_did: &Did) -> IndyResult<()> {
         self.wallet_service.delete_indy_record::<Did>(wallet_handle, &did.0)?;
-        self.wallet_service.add_indy_object(wallet_handle, &curr_did.did.0, &curr_did, &HashMap::new())?;
+        self.wallet_service.add_indy_object(wallet_handle, &curr_did.did.0, curr_did, &HashMap::new())?;
 
         // move temporary Did
         if let Ok(mut temp_did) = self.wallet_service.get_indy_object::<TemporaryDid>(wallet_handle, &did.0, &RecordOptions::id_value()) {
@@ -578,9 +1630,7 @@ impl DidCommandExecutor {
             }
         }
 
-        debug!("qualify_did <<< res: {:?}", curr_did.did);
-
-        Ok(curr_did.did.0)
+        Ok(())
     }
 
     fn update_dependent_entity_reference<T>(&self, wallet_handle: WalletHandle, id: &str, new_id: &str) -> IndyResult<()>
@@ -596,9 +1646,15 @@ impl DidCommandExecutor {
                    wallet_handle: WalletHandle,
                    did: DidValue,
                    get_nym_reply_result: IndyResult<String>,
-                   deferred_cmd_id: CommandHandle) {
-        let res = self._get_nym_ack(wallet_handle, did, get_nym_reply_result);
-        self._execute_deferred_command(deferred_cmd_id, res.err());
+                   lookup_id: CommandHandle) {
+        let get_nym_reply_result = match self._resubmit_on_timeout(lookup_id, get_nym_reply_result) {
+            Some(result) => result,
+            None => return
+        };
+
+        let res = self._get_nym_ack(wallet_handle, did.clone(), get_nym_reply_result)
+            .or_else(|err| self._fallback_to_stored_their_did(wallet_handle, &did, err));
+        self._finish_ledger_lookup(lookup_id, res);
     }
 
     fn _get_nym_ack(&self, wallet_handle: WalletHandle, did: DidValue, get_nym_reply_result: IndyResult<String>) -> IndyResult<()> {
@@ -606,58 +1662,60 @@ impl DidCommandExecutor {
 
         let get_nym_reply = get_nym_reply_result?;
 
-        let get_nym_response: Reply<GetNymReplyResult> = serde_json::from_str(&get_nym_reply)
-            .to_indy(IndyErrorKind::InvalidState, "Invalid GetNymReplyResult json")?;
+        let nym_data = self.ledger_service.parse_get_nym_data(&get_nym_reply)
+            .map_err(|err| match err.kind() {
+                IndyErrorKind::LedgerItemNotFound => err_msg(IndyErrorKind::WalletItemNotFound, "Their DID isn't found on the ledger"), //TODO FIXME use separate error
+                _ => err
+            })?;
 
-        let their_did_info = match get_nym_response.result() {
-            GetNymReplyResult::GetNymReplyResultV0(res) => {
-                if let Some(data) = &res.data {
-                    let gen_nym_result_data: GetNymResultDataV0 = serde_json::from_str(data)
-                        .to_indy(IndyErrorKind::InvalidState, "Invalid GetNymResultData json")?;
-
-                    TheirDidInfo::new(gen_nym_result_data.dest.qualify(did.get_method()), gen_nym_result_data.verkey)
-                } else {
-                    return Err(err_msg(IndyErrorKind::WalletItemNotFound, "Their DID isn't found on the ledger")); //TODO FIXME use separate error
-                }
-            }
-            GetNymReplyResult::GetNymReplyResultV1(res) => TheirDidInfo::new(res.txn.data.did.qualify(did.get_method()), res.txn.data.verkey)
-        };
+        let their_did_info = TheirDidInfo::new(nym_data.did.qualify(did.get_method()), nym_data.verkey);
 
         let their_did = self.crypto_service.create_their_did(&their_did_info)?;
 
-        self.wallet_service.add_indy_object(wallet_handle, &their_did.did.0, &their_did, &HashMap::new())?;
+        // An expired cache entry is re-resolved over an already stored DID
+        self.wallet_service.upsert_indy_object(wallet_handle, &their_did.did.0, &their_did)?;
+        self.verkey_cache.insert(wallet_handle, &did.0, &their_did.verkey, VerkeySource::Ledger, Instant::now());
 
         trace!("_get_nym_ack <<<");
 
         Ok(())
     }
 
+    // An expired cache entry that can't be re-resolved keeps serving the stored DID until it expires again
+    fn _fallback_to_stored_their_did(&self, wallet_handle: WalletHandle, did: &DidValue, err: IndyError) -> IndyResult<()> {
+        let their_did = match self._wallet_get_their_did(wallet_handle, did) {
+            Ok(their_did) => their_did,
+            Err(_) => return Err(err)
+        };
+
+        warn!("Can't re-resolve DID {} on the ledger, the stored verkey is used: {}", did.0, err);
+        self.verkey_cache.insert(wallet_handle, &did.0, &their_did.verkey, VerkeySource::TheirCached, Instant::now());
+
+        Ok(())
+    }
+
     fn get_attrib_ack(&self,
                       wallet_handle: WalletHandle,
+                      did: DidValue,
                       get_attrib_reply_result: IndyResult<String>,
-                      deferred_cmd_id: CommandHandle) {
-        let res = self._get_attrib_ack(wallet_handle, get_attrib_reply_result);
-        self._execute_deferred_command(deferred_cmd_id, res.err());
+                      lookup_id: CommandHandle) {
+        let get_attrib_reply_result = match self._resubmit_on_timeout(lookup_id, get_attrib_reply_result) {
+            Some(result) => result,
+            None => return
+        };
+
+        let res = self._get_attrib_ack(wallet_handle, &did, get_attrib_reply_result);
+        self._finish_ledger_lookup(lookup_id, res);
     }
 
-    fn _get_attrib_ack(&self, wallet_handle: WalletHandle, get_attrib_reply_result: IndyResult<String>) -> IndyResult<()> {
-        trace!("_get_attrib_ack >>> wallet_handle: {:?}, get_attrib_reply_result: {:?}", wallet_handle, get_attrib_reply_result);
+    fn _get_attrib_ack(&self, wallet_handle: WalletHandle, did: &DidValue, get_attrib_reply_result: IndyResult<String>) -> IndyResult<()> {
+        trace!("_get_attrib_ack >>> wallet_handle: {:?}, did: {:?}, get_attrib_reply_result: {:?}", wallet_handle, did, get_attrib_reply_result);
 
         let get_attrib_reply = get_attrib_reply_result?;
 
-        let get_attrib_reply: Reply<GetAttrReplyResult> = serde_json::from_str(&get_attrib_reply)
-            .to_indy(IndyErrorKind::InvalidState, "Invalid GetAttrReplyResult json")?;
-
-        let (raw, did) = match get_attrib_reply.result() {
-            GetAttrReplyResult::GetAttrReplyResultV0(res) => (res.data, res.dest),
-            GetAttrReplyResult::GetAttrReplyResultV1(res) => (res.txn.data.raw, res.txn.data.did)
-        };
-
-        let attrib_data: AttribData = serde_json::from_str(&raw)
-            .to_indy(IndyErrorKind::InvalidState, "Invalid GetAttReply json")?;
-
-        let endpoint = Endpoint::new(attrib_data.endpoint.ha, attrib_data.endpoint.verkey);
+        let (did, endpoint) = self.ledger_service.parse_get_attrib_endpoint(&get_attrib_reply, did)?;
 
+        // cache resolved endpoint so next lookups don't go to the ledger
         self.wallet_service.add_indy_object(wallet_handle, &did.0, &endpoint, &HashMap::new())?;
 
         trace!("_get_attrib_ack <<<");
@@ -700,6 +1758,12 @@ impl DidCommandExecutor {
             DidCommand::KeyForDid(_, _, _, cb) => {
                 cb(Err(err));
             }
+            DidCommand::KeyForDidDetailed(_, _, _, cb) => {
+                cb(Err(err));
+            }
+            DidCommand::KeyForDidDetailedAck(_, _, cb) => {
+                cb(Err(err));
+            }
             DidCommand::GetEndpointForDid(_, _, _, cb) => {
                 cb(Err(err));
             }
@@ -713,25 +1777,17 @@ impl DidCommandExecutor {
         // Defer this command until their did is fetched from ledger.
         let deferred_cmd_id = self._defer_command(deferred_cmd);
 
+        if self._join_ledger_lookup(LedgerLookupType::Nym, wallet_handle, pool_handle, did, deferred_cmd_id) {
+            return;
+        }
+
         // TODO we need passing of my_did as identifier
-        // TODO: FIXME: Remove this unwrap by sending GetNymAck with the error.
-        let get_nym_request = self.ledger_service.build_get_nym_request(None, did).unwrap();
-        let did = did.clone();
+        let get_nym_request = match self.ledger_service.build_get_nym_request(None, did) {
+            Ok(request) => request,
+            Err(err) => return self._execute_deferred_command(deferred_cmd_id, Some(err)),
+        };
 
-        CommandExecutor::instance()
-            .send(Command::Ledger(LedgerCommand::SubmitRequest(
-                pool_handle,
-                get_nym_request,
-                Box::new(move |result| {
-                    CommandExecutor::instance()
-                        .send(Command::Did(DidCommand::GetNymAck(
-                            wallet_handle,
-                            did.clone(),
-                            result,
-                            deferred_cmd_id,
-                        ))).unwrap();
-                }),
-            ))).unwrap();
+        self._start_ledger_lookup(LedgerLookupType::Nym, wallet_handle, pool_handle, did, get_nym_request, deferred_cmd_id);
     }
 
     fn _fetch_attrib_from_ledger(&self,
@@ -740,23 +1796,116 @@ impl DidCommandExecutor {
         // Defer this command until their did is fetched from ledger.
         let deferred_cmd_id = self._defer_command(deferred_cmd);
 
+        if self._join_ledger_lookup(LedgerLookupType::Attrib, wallet_handle, pool_handle, did, deferred_cmd_id) {
+            return;
+        }
+
         // TODO we need passing of my_did as identifier
-        // TODO: FIXME: Remove this unwrap by sending GetAttribAck with the error.
-        let get_attrib_request = self.ledger_service.build_get_attrib_request(None, did, Some("endpoint"), None, None).unwrap();
+        let get_attrib_request = match self.ledger_service.build_get_attrib_request(None, did, Some("endpoint"), None, None) {
+            Ok(request) => request,
+            Err(err) => return self._execute_deferred_command(deferred_cmd_id, Some(err)),
+        };
 
-        CommandExecutor::instance()
-            .send(Command::Ledger(LedgerCommand::SubmitRequest(
-                pool_handle,
-                get_attrib_request,
-                Box::new(move |result| {
-                    CommandExecutor::instance()
-                        .send(Command::Did(DidCommand::GetAttribAck(
-                            wallet_handle,
-                            result,
-                            deferred_cmd_id,
-                        ))).unwrap();
-                }),
-            ))).unwrap();
+        self._start_ledger_lookup(LedgerLookupType::Attrib, wallet_handle, pool_handle, did, get_attrib_request, deferred_cmd_id);
+    }
+
+    fn _join_ledger_lookup(&self,
+                           type_: LedgerLookupType,
+                           wallet_handle: WalletHandle, pool_handle: PoolHandle,
+                           did: &DidValue, deferred_cmd_id: CommandHandle) -> bool {
+        let mut ledger_lookups = self.ledger_lookups.borrow_mut();
+
+        let lookup = ledger_lookups
+            .values_mut()
+            .find(|lookup| lookup.type_ == type_ && lookup.wallet_handle == wallet_handle && lookup.pool_handle == pool_handle && lookup.did == *did);
+
+        match lookup {
+            Some(lookup) => {
+                lookup.deferred_cmd_ids.push(deferred_cmd_id);
+                true
+            }
+            None => false
+        }
+    }
+
+    fn _start_ledger_lookup(&self,
+                            type_: LedgerLookupType,
+                            wallet_handle: WalletHandle, pool_handle: PoolHandle,
+                            did: &DidValue, request: String, deferred_cmd_id: CommandHandle) {
+        let lookup_id = next_command_handle();
+
+        self.ledger_lookups.borrow_mut().insert(lookup_id, LedgerLookup {
+            type_,
+            wallet_handle,
+            pool_handle,
+            did: did.clone(),
+            request,
+            retry: 0,
+            resubmit_at: None,
+            deferred_cmd_ids: vec![deferred_cmd_id],
+        });
+
+        self._submit_ledger_lookup(lookup_id);
+    }
+
+    fn _submit_ledger_lookup(&self, lookup_id: CommandHandle) {
+        let (type_, wallet_handle, pool_handle, did, request) = match self.ledger_lookups.borrow().get(&lookup_id) {
+            Some(lookup) => (lookup.type_, lookup.wallet_handle, lookup.pool_handle, lookup.did.clone(), lookup.request.clone()),
+            None => return error!("No ledger lookup for id: {:?}", lookup_id)
+        };
+
+        let res = (self.submit_ledger_lookup)(
+            pool_handle,
+            request,
+            Box::new(move |result| match type_ {
+                LedgerLookupType::Nym => DidCommand::GetNymAck(wallet_handle, did.clone(), result, lookup_id),
+                LedgerLookupType::Attrib => DidCommand::GetAttribAck(wallet_handle, did.clone(), result, lookup_id),
+            }),
+        );
+
+        if let Err(err) = res {
+            self._finish_ledger_lookup(lookup_id, Err(err));
+        }
+    }
+
+    // Waiting for a resubmission must not block the executor, so the lookup is only marked
+    // here and resubmitted by `resubmit_due_lookups` once its delay has passed.
+    fn _resubmit_on_timeout(&self, lookup_id: CommandHandle, result: IndyResult<String>) -> Option<IndyResult<String>> {
+        match result {
+            Err(ref err) if err.kind() == IndyErrorKind::PoolTimeout => {}
+            result => return Some(result)
+        }
+
+        let mut ledger_lookups = self.ledger_lookups.borrow_mut();
+
+        let lookup = match ledger_lookups.get_mut(&lookup_id) {
+            Some(lookup) => lookup,
+            None => return Some(result)
+        };
+
+        match self.backoff_policy.get().next_delay(lookup.retry) {
+            Some(delay) => {
+                warn!("Ledger request timed out, resubmitting in {:?}", delay);
+
+                lookup.retry += 1;
+                lookup.resubmit_at = Some(Instant::now() + delay);
+
+                None
+            }
+            None => Some(Err(err_msg(IndyErrorKind::PoolTimeout,
+                                     format!("Ledger is unavailable: no reply after {} attempts", lookup.retry + 1))))
+        }
+    }
+
+    fn _finish_ledger_lookup(&self, lookup_id: CommandHandle, res: IndyResult<()>) {
+        let lookup = match self.ledger_lookups.borrow_mut().remove(&lookup_id) {
+            Some(lookup) => lookup,
+            None => return error!("No ledger lookup for id: {:?}", lookup_id)
+        };
+
+        for deferred_cmd_id in lookup.deferred_cmd_ids {
+            self._execute_deferred_command(deferred_cmd_id, res.clone().err());
+        }
     }
 
     fn _wallet_get_my_did(&self, wallet_handle: WalletHandle, my_did: &DidValue) -> IndyResult<Did> {
@@ -767,3 +1916,85 @@ impl DidCommandExecutor {
         self.wallet_service.get_indy_object(wallet_handle, &their_did.0, &RecordOptions::id_value())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+
+    const DID: &str = "8wZcEriaNLNKtteJvx7f8i";
+    const RETRIES: u32 = 2;
+
+    fn did_command_executor(submitted_acks: Rc<RefCell<Vec<LedgerLookupAck>>>) -> DidCommandExecutor {
+        let executor = DidCommandExecutor::with_ledger_submitter(
+            Rc::new(WalletService::new()),
+            Rc::new(CryptoService::new()),
+            Rc::new(LedgerService::new()),
+            Rc::new(VerkeyCache::new(Duration::from_secs(300))),
+            Box::new(move |_pool_handle, _request, ack| {
+                submitted_acks.borrow_mut().push(ack);
+                Ok(())
+            }));
+
+        executor.backoff_policy.set(BackoffPolicy::new(RETRIES, Duration::from_secs(0)));
+        executor
+    }
+
+    fn pool_timeout() -> IndyResult<String> {
+        Err(err_msg(IndyErrorKind::PoolTimeout, "Pool timeout"))
+    }
+
+    #[test]
+    fn ledger_lookup_is_resubmitted_on_pool_timeout() {
+        let submitted_acks = Rc::new(RefCell::new(Vec::new()));
+        let executor = did_command_executor(submitted_acks.clone());
+
+        let (sender, receiver) = channel();
+        let did = DidValue(DID.to_string());
+        executor._fetch_their_did_from_ledger(WalletHandle(1), 1, &did,
+                                              DidCommand::KeyForDid(1, WalletHandle(1), did.clone(),
+                                                                    Box::new(move |res| sender.send(res).unwrap())));
+        assert_eq!(1, submitted_acks.borrow().len());
+
+        for _ in 0..RETRIES {
+            let ack = submitted_acks.borrow_mut().pop().unwrap();
+            executor.execute(ack(pool_timeout()));
+            assert!(receiver.try_recv().is_err());
+
+            executor.resubmit_due_lookups();
+            assert_eq!(1, submitted_acks.borrow().len());
+        }
+
+        let ack = submitted_acks.borrow_mut().pop().unwrap();
+        executor.execute(ack(pool_timeout()));
+
+        let err = receiver.try_recv().unwrap().unwrap_err();
+        assert_eq!(IndyErrorKind::PoolTimeout, err.kind());
+        assert!(err.to_string().contains(&format!("Ledger is unavailable: no reply after {} attempts", RETRIES + 1)));
+
+        executor.resubmit_due_lookups();
+        assert!(submitted_acks.borrow().is_empty());
+        assert!(executor.ledger_lookups.borrow().is_empty());
+    }
+
+    #[test]
+    fn ledger_lookup_is_not_resubmitted_on_other_errors() {
+        let submitted_acks = Rc::new(RefCell::new(Vec::new()));
+        let executor = did_command_executor(submitted_acks.clone());
+
+        let (sender, receiver) = channel();
+        let did = DidValue(DID.to_string());
+        executor._fetch_their_did_from_ledger(WalletHandle(1), 1, &did,
+                                              DidCommand::KeyForDid(1, WalletHandle(1), did.clone(),
+                                                                    Box::new(move |res| sender.send(res).unwrap())));
+
+        let ack = submitted_acks.borrow_mut().pop().unwrap();
+        executor.execute(ack(Err(err_msg(IndyErrorKind::PoolTerminated, "Pool terminated"))));
+
+        assert_kind!(IndyErrorKind::PoolTerminated, receiver.try_recv().unwrap());
+
+        executor.resubmit_due_lookups();
+        assert!(submitted_acks.borrow().is_empty());
+    }
+}
@@ -4,7 +4,7 @@ extern crate threadpool;
 use std::env;
 use std::rc::Rc;
 use std::sync::{Mutex, MutexGuard};
-use std::sync::mpsc::{channel, Sender};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
 use std::thread;
 
 use crate::commands::anoncreds::{AnoncredsCommand, AnoncredsCommandExecutor};
@@ -24,6 +24,7 @@ use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
+use crate::services::did::verkey_cache::{VerkeyCache, DEFAULT_VERKEY_CACHE_TTL};
 use crate::services::ledger::LedgerService;
 use crate::services::payments::PaymentsService;
 use crate::services::pool::{PoolService, set_freshness_threshold};
@@ -32,7 +33,7 @@ use crate::services::metrics::command_metrics::CommandMetric;
 use indy_wallet::WalletService;
 
 use self::threadpool::ThreadPool;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub mod anoncreds;
 pub mod blob_storage;
@@ -84,7 +85,7 @@ lazy_static! {
     static ref THREADPOOL: Mutex<ThreadPool> = Mutex::new(ThreadPool::new(4));
 }
 
-pub fn indy_set_runtime_config(config: IndyConfig) {
+pub fn indy_set_runtime_config(config: IndyConfig) -> IndyResult<()> {
     if let Some(crypto_thread_pool_size) = config.crypto_thread_pool_size {
         THREADPOOL.lock().unwrap().set_num_threads(crypto_thread_pool_size);
     }
@@ -96,6 +97,23 @@ pub fn indy_set_runtime_config(config: IndyConfig) {
     if let Some(threshold) = config.freshness_threshold {
         set_freshness_threshold(threshold);
     }
+    if let Some(strict) = config.strict_messages {
+        CommandExecutor::instance()
+            .send(Command::Crypto(CryptoCommand::SetStrictMessages(strict)))?;
+    }
+    if config.ledger_fallback_max_retries.is_some() || config.ledger_fallback_base_delay_ms.is_some() {
+        CommandExecutor::instance()
+            .send(Command::Did(DidCommand::SetBackoffPolicy(
+                config.ledger_fallback_max_retries,
+                config.ledger_fallback_base_delay_ms.map(Duration::from_millis),
+            )))?;
+    }
+    if let Some(ttl_secs) = config.verkey_cache_ttl_secs {
+        CommandExecutor::instance()
+            .send(Command::Did(DidCommand::SetVerkeyCacheTtl(Duration::from_secs(ttl_secs))))?;
+    }
+
+    Ok(())
 }
 
 fn get_cur_time() -> u128 {
@@ -134,13 +152,14 @@ impl CommandExecutor {
                 let pool_service = Rc::new(PoolService::new());
                 let wallet_service = Rc::new(WalletService::new());
                 let metrics_service = Rc::new(MetricsService::new());
+                let verkey_cache = Rc::new(VerkeyCache::new(DEFAULT_VERKEY_CACHE_TTL));
 
                 let anoncreds_command_executor = AnoncredsCommandExecutor::new(anoncreds_service.clone(), blob_storage_service.clone(), pool_service.clone(), wallet_service.clone(), crypto_service.clone());
                 let crypto_command_executor = CryptoCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
                 let ledger_command_executor = LedgerCommandExecutor::new(pool_service.clone(), crypto_service.clone(), wallet_service.clone(), ledger_service.clone());
                 let pool_command_executor = PoolCommandExecutor::new(pool_service.clone());
-                let did_command_executor = DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
-                let wallet_command_executor = WalletCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
+                let did_command_executor = DidCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), ledger_service.clone(), verkey_cache.clone());
+                let wallet_command_executor = WalletCommandExecutor::new(wallet_service.clone(), crypto_service.clone(), verkey_cache.clone());
                 let pairwise_command_executor = PairwiseCommandExecutor::new(wallet_service.clone());
                 let blob_storage_command_executor = BlobStorageCommandExecutor::new(blob_storage_service.clone());
                 let non_secret_command_executor = NonSecretsCommandExecutor::new(wallet_service.clone());
@@ -149,10 +168,15 @@ impl CommandExecutor {
                 let metrics_command_executor = MetricsCommandExecutor::new(wallet_service.clone(), metrics_service.clone());
 
                 loop {
-                    let instrumented_cmd = match receiver.recv() {
+                    did_command_executor.resubmit_due_lookups();
+
+                    let instrumented_cmd = match CommandExecutor::_receive(&receiver, did_command_executor.resubmission_timeout()) {
                         Ok(cmd) => {
                             cmd
                         }
+                        Err(RecvTimeoutError::Timeout) => {
+                            continue
+                        }
                         Err(err) => {
                             error!("Failed to get command!");
                             panic!("Failed to get command! {:?}", err)
@@ -224,6 +248,14 @@ impl CommandExecutor {
         }
     }
 
+    // Waits for the next command, but not longer than until a pending ledger lookup has to be resubmitted
+    fn _receive(receiver: &Receiver<InstrumentedCommand>, timeout: Option<Duration>) -> Result<InstrumentedCommand, RecvTimeoutError> {
+        match timeout {
+            Some(timeout) => receiver.recv_timeout(timeout),
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        }
+    }
+
     pub fn send(&self, cmd: Command) -> IndyResult<()> {
         self.sender
             .send(InstrumentedCommand::new(cmd))
@@ -224,7 +224,7 @@ impl PoolCommandExecutor {
     fn set_protocol_version(&self, version: usize) -> IndyResult<()> {
         debug!("set_protocol_version >>> version: {:?}", version);
 
-        if version != 1 && version != 2 {
+        if !ProtocolVersion::is_supported(version) {
             return Err(err_msg(IndyErrorKind::PoolIncompatibleProtocolVersion, format!("Unsupported Protocol version: {}", version)));
         }
 
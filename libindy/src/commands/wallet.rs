@@ -7,6 +7,7 @@ use crate::commands::{Command, CommandExecutor};
 use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
+use crate::services::did::verkey_cache::VerkeyCache;
 use indy_wallet::{KeyDerivationData, WalletService, Metadata};
 use indy_utils::crypto::{chacha20poly1305_ietf, randombytes};
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
@@ -100,15 +101,17 @@ macro_rules! get_cb {
 pub struct WalletCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
+    verkey_cache: Rc<VerkeyCache>,
     open_callbacks: RefCell<HashMap<WalletHandle, Box<dyn Fn(IndyResult<WalletHandle>) + Send>>>,
     pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>
 }
 
 impl WalletCommandExecutor {
-    pub fn new(wallet_service: Rc<WalletService>, crypto_service: Rc<CryptoService>) -> WalletCommandExecutor {
+    pub fn new(wallet_service: Rc<WalletService>, crypto_service: Rc<CryptoService>, verkey_cache: Rc<VerkeyCache>) -> WalletCommandExecutor {
         WalletCommandExecutor {
             wallet_service,
             crypto_service,
+            verkey_cache,
             open_callbacks: RefCell::new(HashMap::new()),
             pending_callbacks: RefCell::new(HashMap::new())
         }
@@ -337,6 +340,7 @@ impl WalletCommandExecutor {
         trace!("_close >>> handle: {:?}", wallet_handle);
 
         self.wallet_service.close_wallet(wallet_handle)?;
+        self.verkey_cache.invalidate_wallet(wallet_handle);
 
         trace!("_close <<< res: ()");
         Ok(())
@@ -0,0 +1,73 @@
+use indy_api_types::ErrorCode;
+use indy_api_types::errors::prelude::*;
+
+/// Outcome of a single element of a batch command.
+///
+/// Serialized as `{"ok": <value>}` or `{"err": {"code": <error code>, "message": <string>}}`,
+/// so every batch command returns the same shape.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchResult<T> {
+    Ok(T),
+    Err(BatchError),
+}
+
+#[derive(Serialize, Debug)]
+pub struct BatchError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl From<IndyError> for BatchError {
+    fn from(err: IndyError) -> BatchError {
+        BatchError {
+            code: ErrorCode::from(err.kind()) as i32,
+            message: err.to_string(),
+        }
+    }
+}
+
+impl<T> From<IndyResult<T>> for BatchResult<T> {
+    fn from(result: IndyResult<T>) -> BatchResult<T> {
+        match result {
+            Ok(value) => BatchResult::Ok(value),
+            Err(err) => BatchResult::Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_result_serialization_works_for_mixed_batch() {
+        let results: Vec<BatchResult<String>> = vec![
+            Ok("first".to_string()).into(),
+            Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid entry")).into(),
+            Err(err_msg(IndyErrorKind::WalletItemNotFound, "Unknown entry")).into(),
+            Ok("last".to_string()).into(),
+        ];
+
+        assert_eq!(json!([
+            {"ok": "first"},
+            {"err": {"code": ErrorCode::CommonInvalidStructure as i32, "message": "Invalid entry"}},
+            {"err": {"code": ErrorCode::WalletItemNotFound as i32, "message": "Unknown entry"}},
+            {"ok": "last"},
+        ]), serde_json::to_value(&results).unwrap());
+    }
+
+    #[test]
+    fn batch_result_serialization_works_for_structured_value() {
+        let result: BatchResult<Vec<u8>> = Ok(vec![1, 2]).into();
+
+        assert_eq!(r#"{"ok":[1,2]}"#, serde_json::to_string(&result).unwrap());
+    }
+
+    #[test]
+    fn batch_result_serialization_works_for_empty_batch() {
+        let results: Vec<BatchResult<String>> = Vec::new();
+
+        assert_eq!("[]", serde_json::to_string(&results).unwrap());
+    }
+}
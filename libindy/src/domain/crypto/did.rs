@@ -1,7 +1,10 @@
 use regex::Regex;
-use rust_base58::FromBase58;
 
 use indy_api_types::validation::Validatable;
+use crate::domain::crypto::key::Key;
+use crate::domain::crypto::redact;
+use crate::domain::ledger::attrib::Endpoint;
+use crate::utils::crypto::base58;
 use crate::utils::qualifier;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
@@ -19,22 +22,38 @@ impl Validatable for DidMethod {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct MyDidInfo {
     pub did: Option<DidValue>,
+    #[derivative(Debug(format_with = "redact"))]
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
     pub cid: Option<bool>,
     pub method_name: Option<DidMethod>,
+    pub strict_seed: Option<bool>,
 }
 
 impl Validatable for MyDidInfo {
     fn validate(&self) -> Result<(), String> {
         if let Some(ref did) = self.did {
-            did.validate()?;
+            did.validate()
+                .map_err(|err| format!("Invalid `did`: {}", err))?;
+        }
+        if let Some(ref seed) = self.seed {
+            if seed.is_empty() {
+                return Err("Invalid `seed`: empty string".to_string());
+            }
+        }
+        if let Some(ref crypto_type) = self.crypto_type {
+            if crypto_type.is_empty() {
+                return Err("Invalid `crypto_type`: empty string".to_string());
+            }
         }
         if let Some(ref name) = self.method_name {
-            name.validate()?
+            name.validate()
+                .map_err(|err| format!("Invalid `method_name`: {}", err))?;
         }
         Ok(())
     }
@@ -44,6 +63,7 @@ impl Validatable for MyDidInfo {
 pub struct TheirDidInfo {
     pub did: DidValue,
     pub verkey: Option<String>,
+    pub overwrite: Option<bool>,
 }
 
 impl TheirDidInfo {
@@ -51,6 +71,7 @@ impl TheirDidInfo {
         TheirDidInfo {
             did,
             verkey,
+            overwrite: None,
         }
     }
 }
@@ -106,6 +127,21 @@ impl DidValue {
             None => true
         }
     }
+
+    /// Unlike `validate`, also checks the method-specific part of a fully qualified DID.
+    pub fn kind(&self) -> Option<DidKind> {
+        let qualified = self.is_fully_qualified();
+
+        let did = if qualified { self.to_unqualified() } else { self.clone() };
+        let did = base58::from_b58(&did.0).ok()?;
+
+        match (qualified, did.len()) {
+            (true, 16) | (true, 32) => Some(DidKind::Qualified),
+            (false, 16) => Some(DidKind::Short),
+            (false, 32) => Some(DidKind::Full),
+            _ => None
+        }
+    }
 }
 
 impl Validatable for DidValue {
@@ -113,7 +149,7 @@ impl Validatable for DidValue {
         if self.is_fully_qualified() {
             // pass
         } else {
-            let did = self.0.from_base58()
+            let did = base58::from_b58(&self.0)
                 .map_err(|err| err.to_string())?;
 
             if did.len() != 16 && did.len() != 32 {
@@ -140,7 +176,7 @@ impl ShortDidValue {
 
 impl Validatable for ShortDidValue {
     fn validate(&self) -> Result<(), String> {
-        let did = self.0.from_base58()
+        let did = base58::from_b58(&self.0)
             .map_err(|err| err.to_string())?;
 
         if did.len() != 16 && did.len() != 32 {
@@ -165,12 +201,61 @@ pub struct DidWithMeta {
     pub metadata: Option<String>,
 }
 
+/// Where the verkey returned by `KeyForDidDetailed` was resolved from.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerkeySource {
+    My,
+    TheirCached,
+    Ledger,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DidVerkey {
+    pub verkey: String,
+    pub source: VerkeySource,
+}
+
+/// Form of a well-formed DID string reported by `ValidateDidString`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DidKind {
+    Short,
+    Full,
+    Qualified,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DidValidationResult {
+    pub valid: bool,
+    pub kind: Option<DidKind>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TheirDid {
     pub did: DidValue,
     pub verkey: String,
 }
 
+#[derive(Serialize, Clone, Debug)]
+pub struct TheirDidWithEndpoint {
+    pub did: DidValue,
+    pub verkey: String,
+    pub endpoint: Option<Endpoint>,
+}
+
+/// Filter for `ListTheirDids`, an absent field doesn't restrict the listing.
+#[derive(Deserialize, Debug, Default)]
+pub struct TheirDidFilter {
+    pub has_endpoint: Option<bool>,
+}
+
+impl TheirDidFilter {
+    pub fn matches(&self, their_did: &TheirDidWithEndpoint) -> bool {
+        self.has_endpoint.map_or(true, |has_endpoint| has_endpoint == their_did.endpoint.is_some())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct TemporaryDid {
     pub did: DidValue,
@@ -185,3 +270,236 @@ impl From<TemporaryDid> for Did {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExportedDidKey {
+    pub did: DidValue,
+    #[serde(flatten)]
+    pub key: Key,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DidIntegrityReport {
+    pub did: DidValue,
+    pub verkey_ok: bool,
+    pub did_ok: bool,
+}
+
+/// Pairing payload that is anoncrypted to the other party's verkey.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConnectionRequest {
+    pub did: DidValue,
+    pub verkey: String,
+    pub endpoint: Option<Endpoint>,
+}
+
+impl Validatable for ConnectionRequest {
+    fn validate(&self) -> Result<(), String> {
+        self.did.validate()?;
+        if self.verkey.is_empty() {
+            return Err("Empty `verkey` in connection request".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DidImportPolicy {
+    Fail,
+    Overwrite,
+    Skip,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID: &str = "8wZcEriaNLNKtteJvx7f8i";
+    const VERKEY: &str = "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp";
+
+    #[test]
+    fn my_did_info_debug_works_for_redacting_seed() {
+        let did_info = MyDidInfo {
+            did: Some(DidValue(DID.to_string())),
+            seed: Some("00000000000000000000000000000My1".to_string()),
+            crypto_type: None,
+            cid: None,
+            method_name: None,
+            strict_seed: None,
+        };
+
+        let debug = format!("{:?}", did_info);
+
+        assert!(debug.contains(DID));
+        assert!(debug.contains("seed: <redacted>"));
+        assert!(!debug.contains("00000000000000000000000000000My1"));
+    }
+
+    #[test]
+    fn did_serialization_round_trip_works() {
+        let did = Did::new(DidValue(DID.to_string()), VERKEY.to_string());
+
+        let json = serde_json::to_value(&did).unwrap();
+        assert_eq!(json!({"did": DID, "verkey": VERKEY}), json);
+
+        let did: Did = serde_json::from_value(json).unwrap();
+        assert_eq!(DID, did.did.0);
+        assert_eq!(VERKEY, did.verkey);
+    }
+
+    #[test]
+    fn their_did_serialization_round_trip_works() {
+        let their_did = TheirDid { did: DidValue(DID.to_string()), verkey: VERKEY.to_string() };
+
+        let json = serde_json::to_value(&their_did).unwrap();
+        assert_eq!(json!({"did": DID, "verkey": VERKEY}), json);
+
+        let their_did: TheirDid = serde_json::from_value(json).unwrap();
+        assert_eq!(DID, their_did.did.0);
+        assert_eq!(VERKEY, their_did.verkey);
+    }
+
+    #[test]
+    fn did_serialization_is_byte_stable() {
+        let did = Did::new(DidValue(DID.to_string()), VERKEY.to_string());
+
+        let expected = format!(r#"{{"did":"{}","verkey":"{}"}}"#, DID, VERKEY);
+        assert_eq!(expected, serde_json::to_string(&did).unwrap());
+        assert_eq!(serde_json::to_string(&did).unwrap(), serde_json::to_string(&did.clone()).unwrap());
+    }
+
+    #[test]
+    fn their_did_serialization_is_byte_stable() {
+        let their_did = TheirDid { did: DidValue(DID.to_string()), verkey: VERKEY.to_string() };
+
+        let expected = format!(r#"{{"did":"{}","verkey":"{}"}}"#, DID, VERKEY);
+        assert_eq!(expected, serde_json::to_string(&their_did).unwrap());
+    }
+
+    #[test]
+    fn did_verkey_serialization_works() {
+        let did_verkey = DidVerkey { verkey: VERKEY.to_string(), source: VerkeySource::TheirCached };
+        assert_eq!(json!({"verkey": VERKEY, "source": "their_cached"}), serde_json::to_value(&did_verkey).unwrap());
+
+        assert_eq!(json!("my"), serde_json::to_value(&VerkeySource::My).unwrap());
+        assert_eq!(json!("ledger"), serde_json::to_value(&VerkeySource::Ledger).unwrap());
+    }
+
+    #[test]
+    fn their_did_deserialization_fails_for_missing_verkey() {
+        assert!(serde_json::from_value::<TheirDid>(json!({"did": DID})).is_err());
+    }
+
+    fn my_did_info(json: serde_json::Value) -> MyDidInfo {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn my_did_info_validate_works() {
+        assert!(my_did_info(json!({})).validate().is_ok());
+        assert!(my_did_info(json!({"did": DID, "seed": "00000000000000000000000000000My1", "crypto_type": "ed25519"})).validate().is_ok());
+        assert!(my_did_info(json!({"did": VERKEY, "cid": true})).validate().is_ok());
+    }
+
+    #[test]
+    fn my_did_info_validate_works_for_invalid_did() {
+        let err = my_did_info(json!({"did": "invalid_base58_did"})).validate().unwrap_err();
+        assert!(err.starts_with("Invalid `did`:"), err);
+    }
+
+    #[test]
+    fn my_did_info_validate_works_for_did_with_cid() {
+        my_did_info(json!({"did": DID, "cid": true})).validate().unwrap();
+    }
+
+    #[test]
+    fn my_did_info_validate_works_for_empty_seed() {
+        let err = my_did_info(json!({"seed": ""})).validate().unwrap_err();
+        assert_eq!("Invalid `seed`: empty string", err);
+    }
+
+    #[test]
+    fn my_did_info_validate_works_for_empty_crypto_type() {
+        let err = my_did_info(json!({"crypto_type": ""})).validate().unwrap_err();
+        assert_eq!("Invalid `crypto_type`: empty string", err);
+    }
+
+    #[test]
+    fn my_did_info_validate_works_for_invalid_method_name() {
+        let err = my_did_info(json!({"method_name": "Invalid-Method"})).validate().unwrap_err();
+        assert!(err.starts_with("Invalid `method_name`:"), err);
+    }
+
+    #[test]
+    fn did_kind_works() {
+        assert_eq!(Some(DidKind::Short), DidValue(DID.to_string()).kind());
+        assert_eq!(Some(DidKind::Full), DidValue(VERKEY.to_string()).kind());
+        assert_eq!(Some(DidKind::Qualified), DidValue(format!("did:sov:{}", DID)).kind());
+        assert_eq!(Some(DidKind::Qualified), DidValue(format!("did:peer:{}", VERKEY)).kind());
+    }
+
+    #[test]
+    fn did_kind_works_for_malformed_did() {
+        assert_eq!(None, DidValue("".to_string()).kind());
+        assert_eq!(None, DidValue("invalid_base58_did".to_string()).kind());
+        assert_eq!(None, DidValue("8wZcEriaNLNKtteJvx7f".to_string()).kind());
+        assert_eq!(None, DidValue("did:sov:".to_string()).kind());
+        assert_eq!(None, DidValue("did:sov:invalid_base58_did".to_string()).kind());
+        assert_eq!(None, DidValue(format!("did:Sov:{}", DID)).kind());
+        assert_eq!(None, DidValue(format!("sov:{}", DID)).kind());
+        assert_eq!(None, DidValue(format!("xyz:sov:{}", DID)).kind());
+    }
+
+    #[test]
+    fn temporary_did_converts_to_did() {
+        let did: Did = TemporaryDid { did: DidValue(DID.to_string()), verkey: VERKEY.to_string() }.into();
+
+        assert_eq!(DID, did.did.0);
+        assert_eq!(VERKEY, did.verkey);
+    }
+
+    #[test]
+    fn their_did_filter_matches_works() {
+        let with_endpoint = TheirDidWithEndpoint {
+            did: DidValue(DID.to_string()),
+            verkey: VERKEY.to_string(),
+            endpoint: Some(Endpoint::new("127.0.0.1:9700".to_string(), None)),
+        };
+        let without_endpoint = TheirDidWithEndpoint { endpoint: None, ..with_endpoint.clone() };
+
+        let filter = TheirDidFilter::default();
+        assert!(filter.matches(&with_endpoint));
+        assert!(filter.matches(&without_endpoint));
+
+        let filter: TheirDidFilter = serde_json::from_value(json!({"has_endpoint": true})).unwrap();
+        assert!(filter.matches(&with_endpoint));
+        assert!(!filter.matches(&without_endpoint));
+
+        let filter: TheirDidFilter = serde_json::from_value(json!({"has_endpoint": false})).unwrap();
+        assert!(!filter.matches(&with_endpoint));
+        assert!(filter.matches(&without_endpoint));
+    }
+
+    #[test]
+    fn connection_request_round_trip_works() {
+        let request = ConnectionRequest {
+            did: DidValue(DID.to_string()),
+            verkey: VERKEY.to_string(),
+            endpoint: Some(Endpoint::new("127.0.0.1:9700".to_string(), None)),
+        };
+
+        let json = serde_json::to_string(&request).unwrap();
+        let parsed: ConnectionRequest = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(DID, parsed.did.0);
+        assert_eq!(VERKEY, parsed.verkey);
+        assert_eq!("127.0.0.1:9700", parsed.endpoint.unwrap().ha);
+    }
+
+    #[test]
+    fn connection_request_validate_works_for_empty_verkey() {
+        let request: ConnectionRequest = serde_json::from_value(json!({"did": DID, "verkey": ""})).unwrap();
+        assert_eq!("Empty `verkey` in connection request", request.validate().unwrap_err());
+    }
+}
@@ -1,16 +1,17 @@
 extern crate zeroize;
 
+use indy_api_types::validation::Validatable;
+use indy_utils::crypto::pwhash_argon2i13;
+
 use self::zeroize::Zeroize;
+use super::redact;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Key {
     pub verkey: String,
-    #[cfg(not(test))]
-    #[derivative(Debug = "ignore")]
-    pub signkey: String,
-    #[cfg(test)]
+    #[derivative(Debug(format_with = "redact"))]
     pub signkey: String,
 }
 
@@ -35,8 +36,11 @@ impl Drop for Key {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Derivative)]
+#[derivative(Debug)]
+#[derive(Serialize, Deserialize)]
 pub struct KeyInfo {
+    #[derivative(Debug(format_with = "redact"))]
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
 }
@@ -44,4 +48,145 @@ pub struct KeyInfo {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct KeyMetadata {
     pub value: String
-}
\ No newline at end of file
+}
+
+pub const VERIFY_REASON_KEY_NOT_RESOLVED: &str = "key not resolved";
+pub const VERIFY_REASON_MALFORMED_SIGNATURE: &str = "malformed signature";
+pub const VERIFY_REASON_SIGNATURE_MISMATCH: &str = "signature mismatch";
+
+#[derive(Serialize, Debug)]
+pub struct VerificationResult {
+    pub verified: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AnyKeyVerificationResult {
+    pub verified: bool,
+    pub verkey: Option<String>,
+}
+
+// Outcome of the known-answer tests run against the crypto backend, one flag per primitive.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SelfTestReport {
+    pub seed_derivation: bool,
+    pub sign_verify: bool,
+    pub box_open: bool,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.seed_derivation && self.sign_verify && self.box_open
+    }
+}
+
+pub const MIN_KDF_OPSLIMIT: u64 = 4;
+pub const MIN_KDF_MEMLIMIT: u64 = 32 * 1024 * 1024;
+
+// Argon2i limits of passphrase based key derivation. They are stored together with
+// encrypted data, so raising defaults doesn't break decryption of older data.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KdfParams {
+    pub opslimit: u64,
+    pub memlimit: u64,
+}
+
+impl KdfParams {
+    pub const BYTES: usize = 16;
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut res = Vec::with_capacity(KdfParams::BYTES);
+        res.extend_from_slice(&self.opslimit.to_le_bytes());
+        res.extend_from_slice(&self.memlimit.to_le_bytes());
+        res
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<KdfParams> {
+        if bytes.len() != KdfParams::BYTES {
+            return None;
+        }
+
+        let mut opslimit = [0u8; 8];
+        let mut memlimit = [0u8; 8];
+        opslimit.copy_from_slice(&bytes[..8]);
+        memlimit.copy_from_slice(&bytes[8..]);
+
+        Some(KdfParams { opslimit: u64::from_le_bytes(opslimit), memlimit: u64::from_le_bytes(memlimit) })
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        KdfParams {
+            opslimit: pwhash_argon2i13::opslimit_interactive(),
+            memlimit: pwhash_argon2i13::memlimit_interactive() as u64,
+        }
+    }
+}
+
+impl Validatable for KdfParams {
+    fn validate(&self) -> Result<(), String> {
+        if self.opslimit < MIN_KDF_OPSLIMIT {
+            return Err(format!("KDF `opslimit` is below the minimum of {}: {}", MIN_KDF_OPSLIMIT, self.opslimit));
+        }
+        if self.memlimit < MIN_KDF_MEMLIMIT {
+            return Err(format!("KDF `memlimit` is below the minimum of {}: {}", MIN_KDF_MEMLIMIT, self.memlimit));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_zeroize_works() {
+        let mut key = Key::new("verkey".to_string(), "signkey".to_string());
+
+        key.zeroize();
+
+        assert_eq!("verkey", key.verkey);
+        assert!(key.signkey.is_empty());
+    }
+
+    #[test]
+    fn key_debug_works_for_redacting_signkey() {
+        let key = Key::new("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string(), "secret_signkey".to_string());
+
+        let debug = format!("{:?}", key);
+
+        assert!(debug.contains("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW"));
+        assert!(debug.contains("signkey: <redacted>"));
+        assert!(!debug.contains("secret_signkey"));
+    }
+
+    #[test]
+    fn key_info_debug_works_for_redacting_seed() {
+        let key_info = KeyInfo { seed: Some("00000000000000000000000000000My1".to_string()), crypto_type: Some("ed25519".to_string()) };
+
+        let debug = format!("{:?}", key_info);
+
+        assert!(debug.contains("crypto_type: Some(\"ed25519\")"));
+        assert!(debug.contains("seed: <redacted>"));
+        assert!(!debug.contains("00000000000000000000000000000My1"));
+    }
+
+    #[test]
+    fn kdf_params_default_works() {
+        let params = KdfParams::default();
+        assert!(params.validate().is_ok());
+        assert_eq!(params, KdfParams::from_bytes(&params.to_bytes()).unwrap());
+    }
+
+    #[test]
+    fn kdf_params_validate_not_works_below_floor() {
+        assert!(KdfParams { opslimit: MIN_KDF_OPSLIMIT - 1, memlimit: MIN_KDF_MEMLIMIT }.validate().is_err());
+        assert!(KdfParams { opslimit: MIN_KDF_OPSLIMIT, memlimit: MIN_KDF_MEMLIMIT - 1 }.validate().is_err());
+    }
+
+    #[test]
+    fn kdf_params_from_bytes_not_works_for_wrong_length() {
+        assert!(KdfParams::from_bytes(&[0u8; KdfParams::BYTES - 1]).is_none());
+    }
+}
@@ -1,4 +1,12 @@
+use std::fmt;
+
 pub mod key;
 pub mod did;
 pub mod combo_box;
 pub mod pack;
+
+// Debug formatter for secret fields (seeds, signing keys), so that logging a whole struct
+// never discloses them.
+pub fn redact<T>(_secret: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("<redacted>")
+}
@@ -1,5 +1,5 @@
 use super::constants::{ATTRIB, GET_ATTR};
-use super::response::GetReplyResultV1;
+use super::response::{GetReplyResultV1, ReplyType};
 use super::super::crypto::did::ShortDidValue;
 
 #[derive(Serialize, PartialEq, Debug)]
@@ -60,11 +60,17 @@ pub enum GetAttrReplyResult {
     GetAttrReplyResultV1(GetReplyResultV1<GetAttResultDataV1>)
 }
 
+impl ReplyType for GetAttrReplyResult {
+    fn get_type<'a>() -> &'a str {
+        GET_ATTR
+    }
+}
+
 #[derive(Deserialize, Eq, PartialEq, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAttResultV0 {
     pub  identifier: ShortDidValue,
-    pub  data: String,
+    pub  data: Option<String>,
     pub  dest: ShortDidValue,
     pub  raw: String
 }
@@ -79,7 +85,7 @@ pub struct GetAttResultDataV1 {
 
 #[derive(Deserialize, Debug)]
 pub struct AttribData {
-    pub endpoint: Endpoint
+    pub endpoint: Option<Endpoint>
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -96,3 +102,19 @@ impl Endpoint {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERKEY: &str = "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp";
+
+    #[test]
+    fn endpoint_serialization_is_byte_stable() {
+        let endpoint = Endpoint::new("127.0.0.1:9700".to_string(), Some(VERKEY.to_string()));
+
+        let expected = format!(r#"{{"ha":"127.0.0.1:9700","verkey":"{}"}}"#, VERKEY);
+        assert_eq!(expected, serde_json::to_string(&endpoint).unwrap());
+        assert_eq!(expected, serde_json::to_string(&endpoint.clone()).unwrap());
+    }
+}
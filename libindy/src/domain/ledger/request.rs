@@ -26,6 +26,10 @@ impl ProtocolVersion {
     pub fn is_node_1_3() -> bool {
         ProtocolVersion::get() == 1
     }
+
+    pub fn is_supported(version: usize) -> bool {
+        version == 1 || version == 2
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
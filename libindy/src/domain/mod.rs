@@ -1,4 +1,5 @@
 pub mod anoncreds;
+pub mod batch;
 pub mod crypto;
 pub mod ledger;
 pub mod pairwise;
@@ -11,7 +12,11 @@ use indy_api_types::validation::Validatable;
 pub struct IndyConfig {
     pub crypto_thread_pool_size: Option<usize>,
     pub collect_backtrace: Option<bool>,
-    pub freshness_threshold: Option<u64>
+    pub freshness_threshold: Option<u64>,
+    pub strict_messages: Option<bool>,
+    pub ledger_fallback_max_retries: Option<u32>,
+    pub ledger_fallback_base_delay_ms: Option<u64>,
+    pub verkey_cache_ttl_secs: Option<u64>
 }
 
 impl Validatable for IndyConfig {}
\ No newline at end of file
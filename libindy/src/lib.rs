@@ -44,6 +44,7 @@ extern crate rust_base58;
 
 extern crate sha2;
 extern crate sha3;
+extern crate bip39;
 
 extern crate zeroize;
 
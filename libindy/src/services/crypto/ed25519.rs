@@ -49,8 +49,20 @@ impl CryptoType for ED25519CryptoType {
                          &ed25519_sign::sk_to_curve25519(sk)?, doc)
     }
 
-    fn validate_key(&self, _vk: &ed25519_sign::PublicKey) -> Result<(), IndyError> {
-        // TODO: FIXME: Validate key
-        Ok(())
+    fn derive_shared_secret(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey) -> Result<Vec<u8>, IndyError> {
+        ed25519_box::derive_shared_secret(&ed25519_sign::sk_to_curve25519(sk)?,
+                                          &ed25519_sign::vk_to_curve25519(vk)?)
+    }
+
+    fn vk_to_curve25519(&self, vk: &ed25519_sign::PublicKey) -> Result<ed25519_box::PublicKey, IndyError> {
+        ed25519_sign::vk_to_curve25519(vk)
+    }
+
+    fn sk_to_curve25519(&self, sk: &ed25519_sign::SecretKey) -> Result<ed25519_box::SecretKey, IndyError> {
+        ed25519_sign::sk_to_curve25519(sk)
+    }
+
+    fn validate_key(&self, vk: &ed25519_sign::PublicKey) -> Result<(), IndyError> {
+        ed25519_sign::validate_key(vk)
     }
 }
\ No newline at end of file
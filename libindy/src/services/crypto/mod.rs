@@ -1,27 +1,38 @@
 extern crate hex;
 
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::str;
 
 use crate::domain::crypto::combo_box::ComboBox;
 use crate::domain::crypto::did::{Did, DidValue, MyDidInfo, TheirDid, TheirDidInfo};
-use crate::domain::crypto::key::{Key, KeyInfo};
+use crate::domain::crypto::key::{Key, KeyInfo, KdfParams, SelfTestReport};
 use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
 use indy_utils::crypto::base64;
 use indy_utils::crypto::ed25519_box;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::gen_nonce_and_encrypt_detached;
 use indy_utils::crypto::ed25519_sign;
+use indy_utils::crypto::pwhash_argon2i13;
+use indy_utils::crypto::randombytes;
+use crate::utils::crypto::base58;
 use crate::utils::crypto::verkey_builder::{build_full_verkey, split_verkey, verkey_get_cryptoname};
 
 use self::ed25519::ED25519CryptoType;
 use self::hex::FromHex;
-use rust_base58::{FromBase58, ToBase58};
+use bip39::{Language, Mnemonic};
+use zeroize::Zeroizing;
 
 mod ed25519;
+mod self_test;
 
 pub const DEFAULT_CRYPTO_TYPE: &str = "ed25519";
 
+/// Minimal seed entropy, in bits per byte, accepted when `strict_seed` is set.
+/// 32 random bytes are close to 5, while seeds like "000...0My1" are below 1.
+pub const MIN_SEED_ENTROPY: f64 = 3.0;
+
 //TODO fix this crypto trait so it matches the functions below
 //TODO create a second crypto trait for additional functions
 trait CryptoType {
@@ -34,10 +45,14 @@ trait CryptoType {
     fn verify(&self, vk: &ed25519_sign::PublicKey, doc: &[u8], signature: &ed25519_sign::Signature) -> IndyResult<bool>;
     fn crypto_box_seal(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
     fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn derive_shared_secret(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey) -> IndyResult<Vec<u8>>;
+    fn vk_to_curve25519(&self, vk: &ed25519_sign::PublicKey) -> IndyResult<ed25519_box::PublicKey>;
+    fn sk_to_curve25519(&self, sk: &ed25519_sign::SecretKey) -> IndyResult<ed25519_box::SecretKey>;
 }
 
 pub struct CryptoService {
-    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>
+    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>,
+    strict_messages: Cell<bool>,
 }
 
 impl CryptoService {
@@ -46,10 +61,21 @@ impl CryptoService {
         crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519CryptoType::new()));
 
         CryptoService {
-            crypto_types
+            crypto_types,
+            strict_messages: Cell::new(false),
         }
     }
 
+    pub fn set_strict_messages(&self, strict: bool) {
+        self.strict_messages.set(strict);
+    }
+
+    pub fn supported_crypto_types(&self) -> Vec<&str> {
+        let mut crypto_types: Vec<&str> = self.crypto_types.keys().cloned().collect();
+        crypto_types.sort();
+        crypto_types
+    }
+
     pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<Key> {
         trace!("create_key >>> key_info: {:?}", secret!(key_info));
 
@@ -66,8 +92,8 @@ impl CryptoService {
 
         let seed = self.convert_seed(key_info.seed.as_ref().map(String::as_ref))?;
         let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
-        let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
+        let mut vk = base58::to_b58(&vk[..]);
+        let sk = base58::to_b58(&sk[..]);
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
             // Use suffix with crypto type name to store crypto type inside of vk
             vk = format!("{}:{}", vk, crypto_type_name);
@@ -89,23 +115,30 @@ impl CryptoService {
             .unwrap_or(DEFAULT_CRYPTO_TYPE);
 
         if !self.crypto_types.contains_key(crypto_type_name) {
-            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("MyDidInfo contains unknown crypto: {}", crypto_type_name)));
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Unsupported `crypto_type`: {}", crypto_type_name)));
         }
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
         let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
+
+        if my_did_info.strict_seed == Some(true) {
+            if let Some(ref seed) = seed {
+                CryptoService::check_seed_entropy(&seed[..])?;
+            }
+        }
+
         let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
         let did = match my_did_info.did {
             Some(ref did) => did.clone(),
             _ if my_did_info.cid == Some(true) =>
-                DidValue::new(&vk[..].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
+                DidValue::new(&base58::to_b58(&vk[..]), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
             _ =>
-                DidValue::new(&vk[0..16].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str()))
+                DidValue::new(&base58::to_b58(&vk[0..16]), my_did_info.method_name.as_ref().map(|method| method.0.as_str()))
         };
 
-        let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
+        let mut vk = base58::to_b58(&vk[..]);
+        let sk = base58::to_b58(&sk[..]);
 
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
             // Use suffix with crypto type name to store crypto type inside of vk
@@ -148,7 +181,9 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.as_str().from_base58()?.as_slice())?;
+        self._check_message(doc)?;
+
+        let my_sk = self._secret_key(my_key, crypto_type_name)?;
         let signature = crypto_type.sign(&my_sk, doc)?[..].to_vec();
 
         trace!("sign <<< signature: {:?}", signature);
@@ -167,7 +202,9 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let their_vk = ed25519_sign::PublicKey::from_slice(&their_vk.from_base58()?)?;
+        self._check_message(msg)?;
+
+        let their_vk = self._public_key(their_vk, crypto_type_name)?;
         let signature = ed25519_sign::Signature::from_slice(&signature)?;
 
         let valid = crypto_type.verify(&their_vk, msg, &signature)?;
@@ -177,8 +214,25 @@ impl CryptoService {
         Ok(valid)
     }
 
+    // Signkey belongs to verkey if a signature made with it verifies against verkey.
+    // Malformed keys are reported as not matching.
+    pub fn check_key(&self, key: &Key) -> IndyResult<bool> {
+        trace!("check_key >>> key: {:?}", key);
+
+        let challenge = self.gen_nonce();
+
+        let res = match self.sign(key, &challenge) {
+            Ok(signature) => self.verify(&key.verkey, &challenge, &signature).unwrap_or(false),
+            Err(_) => false
+        };
+
+        trace!("check_key <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     pub fn create_combo_box(&self, my_key: &Key, their_vk: &str, doc: &[u8]) -> IndyResult<ComboBox> {
-        trace!("create_combo_box >>> my_key: {:?}, their_vk: {:?}, doc: {:?}", my_key, their_vk, doc);
+        trace!("create_combo_box >>> my_key: {:?}, their_vk: {:?}, doc: {:?}", my_key, their_vk, secret!(doc));
 
         let (msg, nonce) = self.crypto_box(my_key, their_vk, doc)?;
 
@@ -194,7 +248,7 @@ impl CryptoService {
     }
 
     pub fn crypto_box(&self, my_key: &Key, their_vk: &str, doc: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>)> {
-        trace!("crypto_box >>> my_key: {:?}, their_vk: {:?}, doc: {:?}", my_key, their_vk, doc);
+        trace!("crypto_box >>> my_key: {:?}, their_vk: {:?}, doc: {:?}", my_key, their_vk, secret!(doc));
 
         let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
 
@@ -214,8 +268,8 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(&crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+        let my_sk = self._secret_key(my_key, crypto_type_name)?;
+        let their_vk = self._public_key(their_vk, crypto_type_name)?;
         let nonce = crypto_type.gen_nonce();
 
         let encrypted_doc = crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?;
@@ -248,19 +302,19 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+        let my_sk = self._secret_key(my_key, crypto_type_name)?;
+        let their_vk = self._public_key(their_vk, crypto_type_name)?;
         let nonce = ed25519_box::Nonce::from_slice(&nonce)?;
 
         let decrypted_doc = crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?;
 
-        trace!("crypto_box_open <<< decrypted_doc: {:?}", decrypted_doc);
+        trace!("crypto_box_open <<< decrypted_doc: {:?}", secret!(&decrypted_doc));
 
         Ok(decrypted_doc)
     }
 
     pub fn crypto_box_seal(&self, their_vk: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
-        trace!("crypto_box_seal >>> their_vk: {:?}, doc: {:?}", their_vk, doc);
+        trace!("crypto_box_seal >>> their_vk: {:?}, doc: {:?}", their_vk, secret!(doc));
 
         let (their_vk, crypto_type_name) = split_verkey(their_vk);
 
@@ -270,7 +324,7 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+        let their_vk = self._public_key(their_vk, crypto_type_name)?;
 
         let encrypted_doc = crypto_type.crypto_box_seal(&their_vk, doc)?;
 
@@ -291,16 +345,189 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_vk = ed25519_sign::PublicKey::from_slice(my_vk.from_base58()?.as_slice())?;
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
+        let my_vk = self._public_key(my_vk, crypto_type_name)?;
+        let my_sk = self._secret_key(my_key, crypto_type_name)?;
 
         let decrypted_doc = crypto_type.crypto_box_seal_open(&my_vk, &my_sk, doc)?;
 
-        trace!("crypto_box_seal_open <<< decrypted_doc: {:?}", decrypted_doc);
+        trace!("crypto_box_seal_open <<< decrypted_doc: {:?}", secret!(&decrypted_doc));
 
         Ok(decrypted_doc)
     }
 
+    pub fn derive_shared_secret(&self, my_key: &Key, their_vk: &str) -> IndyResult<Vec<u8>> {
+        trace!("derive_shared_secret >>> my_key: {:?}, their_vk: {:?}", my_key, their_vk);
+
+        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
+
+        let (their_vk, their_crypto_type_name) = split_verkey(their_vk);
+
+        if !self.crypto_types.contains_key(&crypto_type_name) {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto,
+                               format!("Trying to derive shared secret with unknown crypto: {}", crypto_type_name)));
+        }
+
+        if !crypto_type_name.eq(their_crypto_type_name) {
+            // TODO: FIXME: Use dedicated error code
+            return Err(err_msg(IndyErrorKind::UnknownCrypto,
+                               format!("My key crypto type is incompatible with their key crypto type: {} {}",
+                                       crypto_type_name,
+                                       their_crypto_type_name)));
+        }
+
+        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+
+        let my_sk = self._secret_key(my_key, crypto_type_name)?;
+        let their_vk = self._public_key(their_vk, crypto_type_name)?;
+
+        let shared_secret = crypto_type.derive_shared_secret(&my_sk, &their_vk)?;
+
+        trace!("derive_shared_secret <<< shared_secret: {:?}", secret!(&shared_secret));
+
+        Ok(shared_secret)
+    }
+
+    pub fn verkey_to_curve25519(&self, vk: &str) -> IndyResult<String> {
+        trace!("verkey_to_curve25519 >>> vk: {:?}", vk);
+
+        let (vk, crypto_type_name) = split_verkey(vk);
+
+        let crypto_type = self.crypto_types.get(crypto_type_name)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto,
+                                   format!("Trying to convert verkey with unknown crypto: {}", crypto_type_name)))?;
+
+        let vk = self._public_key(vk, crypto_type_name)?;
+
+        let res = base58::to_b58(&crypto_type.vk_to_curve25519(&vk)?[..]);
+
+        trace!("verkey_to_curve25519 <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    pub fn signkey_to_curve25519(&self, my_key: &Key) -> IndyResult<String> {
+        trace!("signkey_to_curve25519 >>> my_key: {:?}", my_key);
+
+        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
+
+        let crypto_type = self.crypto_types.get(crypto_type_name)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto,
+                                   format!("Trying to convert signkey with unknown crypto: {}", crypto_type_name)))?;
+
+        let my_sk = self._secret_key(my_key, crypto_type_name)?;
+
+        // only the converted key leaves this function, never the Ed25519 signkey itself
+        let res = base58::to_b58(&crypto_type.sk_to_curve25519(&my_sk)?[..]);
+
+        trace!("signkey_to_curve25519 <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    pub fn self_test(&self) -> IndyResult<SelfTestReport> {
+        trace!("self_test >>>");
+
+        let crypto_type = self.crypto_types.get(DEFAULT_CRYPTO_TYPE).unwrap();
+
+        let report = self_test::run(crypto_type.as_ref());
+
+        if !report.passed() {
+            return Err(err_msg(IndyErrorKind::InvalidState,
+                               format!("Crypto self-test failed: {}", json!(report))));
+        }
+
+        trace!("self_test <<< report: {:?}", report);
+
+        Ok(report)
+    }
+
+    pub fn gen_nonce(&self) -> Vec<u8> {
+        let crypto_type = self.crypto_types.get(DEFAULT_CRYPTO_TYPE).unwrap();
+        crypto_type.gen_nonce()[..].to_vec()
+    }
+
+    // Output is kdf params || salt || nonce || ciphertext. Key is derived from passphrase with
+    // Argon2i limits from kdf_params, interactive ones by default as this runs on the command thread.
+    pub fn encrypt_with_passphrase(&self, doc: &[u8], passphrase: &str, kdf_params: &KdfParams) -> IndyResult<Vec<u8>> {
+        trace!("encrypt_with_passphrase >>> doc: {:?}, kdf_params: {:?}", secret!(doc), kdf_params);
+
+        kdf_params.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        let salt = pwhash_argon2i13::gen_salt();
+        let key = chacha20poly1305_ietf::derive_key_with_limits(passphrase, &salt, kdf_params.opslimit, kdf_params.memlimit as usize)?;
+        let (ciphertext, nonce) = chacha20poly1305_ietf::gen_nonce_and_encrypt(doc, &key);
+
+        let mut res = Vec::with_capacity(KdfParams::BYTES + pwhash_argon2i13::SALTBYTES + chacha20poly1305_ietf::NONCEBYTES + ciphertext.len());
+        res.extend_from_slice(&kdf_params.to_bytes());
+        res.extend_from_slice(&salt[..]);
+        res.extend_from_slice(&nonce[..]);
+        res.extend_from_slice(&ciphertext);
+
+        trace!("encrypt_with_passphrase <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    pub fn decrypt_with_passphrase(&self, doc: &[u8], passphrase: &str) -> IndyResult<Vec<u8>> {
+        trace!("decrypt_with_passphrase >>> doc: {:?}", doc);
+
+        if doc.len() < KdfParams::BYTES + pwhash_argon2i13::SALTBYTES + chacha20poly1305_ietf::NONCEBYTES + chacha20poly1305_ietf::TAGBYTES {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Encrypted data is too short"));
+        }
+
+        let (kdf_params, rest) = doc.split_at(KdfParams::BYTES);
+        let (salt, rest) = rest.split_at(pwhash_argon2i13::SALTBYTES);
+        let (nonce, ciphertext) = rest.split_at(chacha20poly1305_ietf::NONCEBYTES);
+
+        // don't let crafted data weaken the key derivation
+        let kdf_params = KdfParams::from_bytes(kdf_params)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid KDF params"))?;
+        kdf_params.validate()
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, err))?;
+
+        let salt = pwhash_argon2i13::Salt::from_slice(salt)?;
+        let nonce = chacha20poly1305_ietf::Nonce::from_slice(nonce)?;
+        let key = chacha20poly1305_ietf::derive_key_with_limits(passphrase, &salt, kdf_params.opslimit, kdf_params.memlimit as usize)?;
+
+        let res = chacha20poly1305_ietf::decrypt(ciphertext, &key, &nonce)
+            .map_err(|_| err_msg(IndyErrorKind::WalletAccessFailed, "Can't decrypt data. Invalid passphrase or corrupted data"))?;
+
+        trace!("decrypt_with_passphrase <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    /// Shannon entropy of the seed bytes in bits per byte, 0 for a seed of one repeated byte.
+    pub fn seed_entropy(seed: &[u8]) -> f64 {
+        let mut counts = [0usize; 256];
+
+        for byte in seed {
+            counts[*byte as usize] += 1;
+        }
+
+        let len = seed.len() as f64;
+
+        counts.iter()
+            .filter(|count| **count > 0)
+            .map(|count| {
+                let p = *count as f64 / len;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    fn check_seed_entropy(seed: &[u8]) -> IndyResult<()> {
+        let entropy = CryptoService::seed_entropy(seed);
+
+        if entropy < MIN_SEED_ENTROPY {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("`seed` entropy is too low: {:.2} bits per byte, at least {} required", entropy, MIN_SEED_ENTROPY)));
+        }
+
+        Ok(())
+    }
+
     pub fn convert_seed(&self, seed: Option<&str>) -> IndyResult<Option<ed25519_sign::Seed>> {
         trace!("convert_seed >>> seed: {:?}", secret!(seed));
 
@@ -329,10 +556,13 @@ impl CryptoService {
             // is hex string
             Vec::from_hex(seed)
                 .to_indy(IndyErrorKind::InvalidStructure, "Seed is invalid hex")?
+        } else if let Some(decoded) = base58::from_b58(seed).ok().filter(|decoded| decoded.len() == ed25519_sign::SEEDBYTES) {
+            // is base58 string
+            decoded
         } else {
             return Err(err_msg(IndyErrorKind::InvalidStructure,
                                format!("Trying to use invalid `seed`. It can be either \
-                               {} bytes string or base64 string or {} bytes HEX string", ed25519_sign::SEEDBYTES, ed25519_sign::SEEDBYTES * 2)));
+                               {} bytes string or base64 string or base58 string or {} bytes HEX string", ed25519_sign::SEEDBYTES, ed25519_sign::SEEDBYTES * 2)));
         };
 
         let res = ed25519_sign::Seed::from_slice(bytes.as_slice())?;
@@ -342,6 +572,42 @@ impl CryptoService {
         Ok(Some(res))
     }
 
+    // The mnemonic encodes exactly SEEDBYTES of entropy (24 words) and that entropy is used as the seed,
+    // so the phrase is a lossless backup of the seed. Seed is returned as HEX string accepted by convert_seed.
+    pub fn generate_mnemonic_seed(&self) -> IndyResult<(String, String)> {
+        trace!("generate_mnemonic_seed >>>");
+
+        let entropy = Zeroizing::new(randombytes::randombytes(ed25519_sign::SEEDBYTES));
+
+        let mnemonic = Mnemonic::from_entropy(&entropy, Language::English)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Can't build mnemonic from entropy: {}", err)))?;
+
+        let res = (mnemonic.phrase().to_string(), hex::encode(&entropy[..]));
+
+        trace!("generate_mnemonic_seed <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    pub fn seed_from_mnemonic(&self, mnemonic: &str) -> IndyResult<String> {
+        trace!("seed_from_mnemonic >>> mnemonic: {:?}", secret!(mnemonic));
+
+        let mnemonic = Mnemonic::from_phrase(mnemonic.trim(), Language::English)
+            .map_err(|err| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid mnemonic: {}", err)))?;
+
+        if mnemonic.entropy().len() != ed25519_sign::SEEDBYTES {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Invalid mnemonic: expected {} words encoding {} bytes, got {} bytes",
+                                       24, ed25519_sign::SEEDBYTES, mnemonic.entropy().len())));
+        }
+
+        let res = hex::encode(mnemonic.entropy());
+
+        trace!("seed_from_mnemonic <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
     pub fn validate_key(&self, vk: &str) -> IndyResult<()> {
         trace!("validate_key >>> vk: {:?}", vk);
 
@@ -354,9 +620,9 @@ impl CryptoService {
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
         if vk.starts_with('~') {
-            let _ = vk[1..].from_base58()?; // TODO: proper validate abbreviated verkey
+            let _ = base58::from_b58(&vk[1..])?; // TODO: proper validate abbreviated verkey
         } else {
-            let vk = ed25519_sign::PublicKey::from_slice(vk.from_base58()?.as_slice())?;
+            let vk = ed25519_sign::PublicKey::from_slice(base58::from_b58(vk)?.as_slice())?;
             crypto_type.validate_key(&vk)?;
         };
 
@@ -365,6 +631,28 @@ impl CryptoService {
         Ok(())
     }
 
+    // Gate for raw key material before it reaches crypto type. Accepts either verkey or signkey
+    // bytes, signkey is checked through the verkey half it embeds.
+    pub fn validate_key_for_crypto_type(&self, key: &[u8], crypto_type_name: &str) -> IndyResult<()> {
+        trace!("validate_key_for_crypto_type >>> crypto_type_name: {:?}", crypto_type_name);
+
+        let crypto_type = self.crypto_types.get(crypto_type_name)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to use key with unknown crypto: {}", crypto_type_name)))?;
+
+        let vk = match key.len() {
+            ed25519_sign::SIG_PUBLICKEYBYTES => key,
+            ed25519_sign::SIG_SECRETKEYBYTES => &key[ed25519_sign::SEEDBYTES..],
+            len => return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                      format!("Invalid key length for crypto {}: {} bytes", crypto_type_name, len)))
+        };
+
+        crypto_type.validate_key(&ed25519_sign::PublicKey::from_slice(vk)?)?;
+
+        trace!("validate_key_for_crypto_type <<<");
+
+        Ok(())
+    }
+
     pub fn validate_did(&self, did: &DidValue) -> IndyResult<()> {
         trace!("validate_did >>> did: {:?}", did);
         // Useful method, huh?
@@ -440,20 +728,48 @@ impl CryptoService {
             err_msg(IndyErrorKind::InvalidStructure, format!("Failed to convert message to UTF-8 {}", err))
         })
     }
+
+    // An empty message is valid to sign, but usually means the caller lost the data on the way.
+    // Whitespace-only messages are only refused when strict messages are enabled in the runtime config.
+    fn _check_message(&self, msg: &[u8]) -> IndyResult<()> {
+        if msg.is_empty() {
+            warn!("Empty message is signed or verified");
+        } else if self.strict_messages.get() && msg.iter().all(u8::is_ascii_whitespace) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Message consists of whitespace only"));
+        }
+
+        Ok(())
+    }
+
+    fn _secret_key(&self, key: &Key, crypto_type_name: &str) -> IndyResult<ed25519_sign::SecretKey> {
+        // decoded signkey bytes are wiped on drop instead of being left in freed memory
+        let signkey = Zeroizing::new(base58::from_b58(&key.signkey)?);
+        self.validate_key_for_crypto_type(signkey.as_slice(), crypto_type_name)?;
+        ed25519_sign::SecretKey::from_slice(signkey.as_slice())
+    }
+
+    fn _public_key(&self, vk: &str, crypto_type_name: &str) -> IndyResult<ed25519_sign::PublicKey> {
+        let vk = base58::from_b58(vk)?;
+        self.validate_key_for_crypto_type(&vk, crypto_type_name)?;
+        ed25519_sign::PublicKey::from_slice(&vk)
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use crate::domain::crypto::did::MyDidInfo;
+    use crate::domain::crypto::key::{MIN_KDF_MEMLIMIT, MIN_KDF_OPSLIMIT};
     use indy_utils::crypto::chacha20poly1305_ietf::gen_key;
+    use std::cell::RefCell;
+    use std::rc::Rc;
 
     use super::*;
 
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
         let my_did = service.create_my_did(&did_info);
         assert!(my_did.is_ok());
     }
@@ -463,12 +779,29 @@ mod tests {
         let service = CryptoService::new();
 
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
-        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
 
         let (my_did, _) = service.create_my_did(&did_info).unwrap();
         assert_eq!(did, my_did.did);
     }
 
+    #[test]
+    fn create_my_did_works_for_default_crypto_type() {
+        let service = CryptoService::new();
+        let seed = Some("00000000000000000000000000000My1".to_string());
+
+        let did_info_default = MyDidInfo { did: None, cid: None, seed: seed.clone(), crypto_type: None, method_name: None, strict_seed: None };
+        let did_info_explicit = MyDidInfo { did: None, cid: None, seed, crypto_type: Some(DEFAULT_CRYPTO_TYPE.to_string()), method_name: None, strict_seed: None };
+
+        let (did_default, key_default) = service.create_my_did(&did_info_default).unwrap();
+        let (did_explicit, key_explicit) = service.create_my_did(&did_info_explicit).unwrap();
+
+        assert!(!did_default.verkey.contains(':'));
+        assert!(!key_default.verkey.contains(':'));
+        assert_eq!(did_default.verkey, did_explicit.verkey);
+        assert_eq!(key_default.verkey, key_explicit.verkey);
+    }
+
     #[test]
     fn create_my_did_not_works_for_invalid_crypto_type() {
         let service = CryptoService::new();
@@ -476,176 +809,721 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let crypto_type = Some("type".to_string());
 
-        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None };
+        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None, strict_seed: None };
 
-        assert!(service.create_my_did(&did_info).is_err());
+        assert_eq!(IndyErrorKind::UnknownCrypto, service.create_my_did(&did_info).unwrap_err().kind());
     }
 
     #[test]
-    fn create_my_did_works_for_seed() {
+    fn create_my_did_works_for_reference_vectors() {
         let service = CryptoService::new();
 
-        let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
-        let seed = Some("00000000000000000000000000000My1".to_string());
+        let vectors = [
+            ("000000000000000000000000Trustee1", "V4SGRU86Z58d6TV7PBUe6f", "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL"),
+            ("00000000000000000000000000000My1", "VsKV7grR1BUE29mG2Fm2kX", "GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa"),
+            ("00000000000000000000000000000My2", "2PRyVHmkXQnQzJQKxHxnXC", "kqa2HyagzfMAq42H5f9u3UMwnSBPQx2QfrSyXbUPxMn"),
+        ];
 
-        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None };
-        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        for &(seed, expected_did, expected_verkey) in vectors.iter() {
+            let did_info = MyDidInfo { did: None, cid: None, seed: Some(seed.to_string()), crypto_type: None, method_name: None, strict_seed: None };
 
-        let (did_with_seed, _) = service.create_my_did(&did_info_with_seed).unwrap();
-        let (did_without_seed, _) = service.create_my_did(&did_info_without_seed).unwrap();
+            let (did, key) = service.create_my_did(&did_info).unwrap();
 
-        assert_ne!(did_with_seed.verkey, did_without_seed.verkey)
+            assert_eq!(expected_did, did.did.0);
+            assert_eq!(expected_verkey, did.verkey);
+            assert_eq!(expected_verkey, key.verkey);
+        }
     }
 
     #[test]
-    fn create_their_did_works_without_verkey() {
+    fn create_my_did_works_for_cid() {
         let service = CryptoService::new();
-        let did = DidValue("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string());
+        let seed = Some("00000000000000000000000000000My1".to_string());
 
-        let their_did_info = TheirDidInfo::new(did.clone(), None);
-        let their_did = service.create_their_did(&their_did_info).unwrap();
+        let did_info_cid = MyDidInfo { did: None, cid: Some(true), seed: seed.clone(), crypto_type: None, method_name: None, strict_seed: None };
+        let did_info_not_cid = MyDidInfo { did: None, cid: Some(false), seed, crypto_type: None, method_name: None, strict_seed: None };
 
-        assert_eq!(did, their_did.did);
-        assert_eq!(did.0, their_did.verkey);
+        let (did_cid, _) = service.create_my_did(&did_info_cid).unwrap();
+        let (did_not_cid, _) = service.create_my_did(&did_info_not_cid).unwrap();
+
+        assert_eq!(32, base58::from_b58(&did_cid.did.0).unwrap().len());
+        assert_eq!(16, base58::from_b58(&did_not_cid.did.0).unwrap().len());
+        assert_eq!(did_cid.verkey, did_cid.did.0);
+        assert_eq!(did_cid.verkey, did_not_cid.verkey);
     }
 
     #[test]
-    fn create_their_did_works_for_full_verkey() {
+    fn create_my_did_works_for_cid_and_passed_did() {
         let service = CryptoService::new();
-        let did = DidValue("8wZcEriaNLNKtteJvx7f8i".to_string());
-        let verkey = "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp";
+        let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
 
-        let their_did_info = TheirDidInfo::new(did.clone(), Some(verkey.to_string()));
-        let their_did = service.create_their_did(&their_did_info).unwrap();
+        let did_info = MyDidInfo { did: Some(did.clone()), cid: Some(true), seed: None, crypto_type: None, method_name: None, strict_seed: None };
 
-        assert_eq!(did, their_did.did);
-        assert_eq!(verkey, their_did.verkey);
+        let (my_did, _) = service.create_my_did(&did_info).unwrap();
+        assert_eq!(did, my_did.did);
     }
 
     #[test]
-    fn create_their_did_works_for_abbreviated_verkey() {
+    fn gen_nonce_works() {
         let service = CryptoService::new();
-        let did = DidValue("8wZcEriaNLNKtteJvx7f8i".to_string());
-        let their_did_info = TheirDidInfo::new(did.clone(), Some("~NcYxiDXkpYi6ov5FcYDi1e".to_string()));
-        let their_did = service.create_their_did(&their_did_info).unwrap();
 
-        assert_eq!(did, their_did.did);
-        assert_eq!("5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp", their_did.verkey);
+        let nonce_1 = service.gen_nonce();
+        let nonce_2 = service.gen_nonce();
+
+        assert_eq!(ed25519_box::NONCEBYTES, nonce_1.len());
+        assert_ne!(nonce_1, nonce_2);
     }
 
     #[test]
-    fn sign_works() {
+    fn check_key_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
 
-        let message = r#"message"#;
-        let (_, my_key) = service.create_my_did(&did_info).unwrap();
-        let sig = service.sign(&my_key, message.as_bytes());
-        assert!(sig.is_ok());
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+        assert!(service.check_key(&key).unwrap());
     }
 
     #[test]
-    fn sign_works_for_invalid_signkey() {
+    fn check_key_works_for_other_verkey() {
         let service = CryptoService::new();
-        let message = r#"message"#;
-        let my_key = Key::new("8wZcEriaNLNKtteJvx7f8i".to_string(), "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp".to_string());
-        assert!(service.sign(&my_key, message.as_bytes()).is_err());
+
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+        let other_key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+
+        let key = Key::new(other_key.verkey.clone(), key.signkey.clone());
+        assert!(!service.check_key(&key).unwrap());
     }
 
     #[test]
-    fn sign_verify_works() {
+    fn check_key_works_for_malformed_signkey() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
-        let message = r#"message"#;
-        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
-        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
-        let valid = service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap();
-        assert!(valid);
+
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+
+        let key = Key::new(key.verkey.clone(), "invalid_base58_signkey".to_string());
+        assert!(!service.check_key(&key).unwrap());
     }
 
     #[test]
-    fn sign_verify_works_for_verkey_contained_crypto_type() {
+    fn encrypt_with_passphrase_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
-        let message = r#"message"#;
-        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
-        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
-        let verkey = my_did.verkey + ":ed25519";
-        let valid = service.verify(&verkey, message.as_bytes(), &signature).unwrap();
-        assert!(valid);
-    }
+        let msg = "some message".as_bytes();
+
+        let encrypted = service.encrypt_with_passphrase(msg, "passphrase", &KdfParams::default()).unwrap();
+        assert!(!encrypted.windows(msg.len()).any(|window| window == msg));
 
+        let decrypted = service.decrypt_with_passphrase(&encrypted, "passphrase").unwrap();
+        assert_eq!(msg, decrypted.as_slice());
+    }
 
     #[test]
-    fn sign_verify_works_for_verkey_contained_invalid_crypto_type() {
+    fn decrypt_with_passphrase_works_for_wrong_passphrase() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
-        let message = r#"message"#;
-        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
-        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
-        let verkey = format!("crypto_type:{}", my_did.verkey);
-        assert!(service.verify(&verkey, message.as_bytes(), &signature).is_err());
+
+        let encrypted = service.encrypt_with_passphrase("some message".as_bytes(), "passphrase", &KdfParams::default()).unwrap();
+
+        let res = service.decrypt_with_passphrase(&encrypted, "other passphrase");
+        assert_kind!(IndyErrorKind::WalletAccessFailed, res);
     }
 
     #[test]
-    fn verify_not_works_for_invalid_verkey() {
+    fn decrypt_with_passphrase_works_for_too_short_data() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
-        let message = r#"message"#;
-        let (_, my_key) = service.create_my_did(&did_info).unwrap();
-        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
-        let verkey = "AnnxV4t3LUHKZaxVQDWoVaG44NrGmeDYMA4Gz6C2tCZd";
-        let valid = service.verify(verkey, message.as_bytes(), &signature).unwrap();
-        assert_eq!(false, valid);
+
+        let res = service.decrypt_with_passphrase(&[1, 2, 3], "passphrase");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
     }
 
     #[test]
-    fn crypto_box_works() {
+    fn encrypt_with_passphrase_works_for_custom_kdf_params() {
         let service = CryptoService::new();
-        let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
-        let (_, my_key) = service.create_my_did(&did_info).unwrap();
-        let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
-        let their_did = Did::new(their_did.did, their_did.verkey);
-        let encrypted_message = service.crypto_box(&my_key, &their_did.verkey, msg.as_bytes());
-        assert!(encrypted_message.is_ok());
+        let kdf_params = KdfParams { opslimit: MIN_KDF_OPSLIMIT + 1, memlimit: MIN_KDF_MEMLIMIT };
+
+        let encrypted = service.encrypt_with_passphrase("some message".as_bytes(), "passphrase", &kdf_params).unwrap();
+        assert_eq!(kdf_params.to_bytes(), encrypted[..KdfParams::BYTES].to_vec());
+
+        let decrypted = service.decrypt_with_passphrase(&encrypted, "passphrase").unwrap();
+        assert_eq!("some message".as_bytes(), decrypted.as_slice());
     }
 
     #[test]
-    fn crypto_box_and_crypto_box_open_works() {
+    fn encrypt_with_passphrase_not_works_for_kdf_params_below_floor() {
         let service = CryptoService::new();
+        let kdf_params = KdfParams { opslimit: MIN_KDF_OPSLIMIT - 1, memlimit: MIN_KDF_MEMLIMIT };
 
-        let msg = "some message";
+        let res = service.encrypt_with_passphrase("some message".as_bytes(), "passphrase", &kdf_params);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+    #[test]
+    fn decrypt_with_passphrase_not_works_for_kdf_params_below_floor() {
+        let service = CryptoService::new();
 
-        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let mut encrypted = service.encrypt_with_passphrase("some message".as_bytes(), "passphrase", &KdfParams::default()).unwrap();
+        let weak = KdfParams { opslimit: 1, memlimit: MIN_KDF_MEMLIMIT };
+        encrypted[..KdfParams::BYTES].copy_from_slice(&weak.to_bytes());
 
-        let my_key_for_encrypt = my_key.clone();
+        let res = service.decrypt_with_passphrase(&encrypted, "passphrase");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
 
-        let their_did_for_decrypt = Did::new(my_did.did, my_did.verkey);
+    #[test]
+    fn convert_seed_works_for_ascii_seed() {
+        let service = CryptoService::new();
+        let seed = "00000000000000000000000000000My1";
 
-        let (their_did, their_key) = service.create_my_did(&did_info.clone()).unwrap();
+        let res = service.convert_seed(Some(seed)).unwrap().unwrap();
+        assert_eq!(seed.as_bytes(), &res[..]);
+    }
 
-        let my_key_for_decrypt = their_key.clone();
+    #[test]
+    fn generate_mnemonic_seed_works() {
+        let service = CryptoService::new();
 
-        let their_did_for_encrypt = Did::new(their_did.did, their_did.verkey);
+        let (mnemonic, seed) = service.generate_mnemonic_seed().unwrap();
+        assert_eq!(24, mnemonic.split_whitespace().count());
+        assert_eq!(ed25519_sign::SEEDBYTES * 2, seed.len());
 
-        let (encrypted_message, noce) = service.crypto_box(&my_key_for_encrypt, &their_did_for_encrypt.verkey, msg.as_bytes()).unwrap();
+        assert_eq!(seed, service.seed_from_mnemonic(&mnemonic).unwrap());
+        service.convert_seed(Some(&seed)).unwrap().unwrap();
+    }
 
-        let decrypted_message = service.crypto_box_open(&my_key_for_decrypt, &their_did_for_decrypt.verkey, &encrypted_message, &noce).unwrap();
+    #[test]
+    fn generate_mnemonic_seed_works_for_different_calls() {
+        let service = CryptoService::new();
+
+        let (mnemonic_1, seed_1) = service.generate_mnemonic_seed().unwrap();
+        let (mnemonic_2, seed_2) = service.generate_mnemonic_seed().unwrap();
+        assert_ne!(mnemonic_1, mnemonic_2);
+        assert_ne!(seed_1, seed_2);
+    }
+
+    #[test]
+    fn seed_from_mnemonic_works_for_known_phrase() {
+        let service = CryptoService::new();
+
+        let mnemonic = format!("{} art", vec!["abandon"; 23].join(" "));
+        assert_eq!("00".repeat(ed25519_sign::SEEDBYTES), service.seed_from_mnemonic(&mnemonic).unwrap());
+    }
+
+    #[test]
+    fn seed_from_mnemonic_not_works_for_invalid_checksum() {
+        let service = CryptoService::new();
+
+        let res = service.seed_from_mnemonic(&vec!["abandon"; 24].join(" "));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn seed_from_mnemonic_not_works_for_unknown_word() {
+        let service = CryptoService::new();
+
+        let mnemonic = format!("{} notaword", vec!["abandon"; 23].join(" "));
+        let res = service.seed_from_mnemonic(&mnemonic);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn seed_from_mnemonic_not_works_for_short_phrase() {
+        let service = CryptoService::new();
+
+        let mnemonic = format!("{} about", vec!["abandon"; 11].join(" "));
+        let res = service.seed_from_mnemonic(&mnemonic);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn convert_seed_works_for_base58_seed() {
+        let service = CryptoService::new();
+        let bytes = [7u8; ed25519_sign::SEEDBYTES];
+
+        let res = service.convert_seed(Some(&base58::to_b58(&bytes))).unwrap().unwrap();
+        assert_eq!(&bytes[..], &res[..]);
+    }
+
+    #[test]
+    fn convert_seed_works_for_base64_seed() {
+        let service = CryptoService::new();
+        let bytes = [7u8; ed25519_sign::SEEDBYTES];
+
+        let res = service.convert_seed(Some(&base64::encode(&bytes))).unwrap().unwrap();
+        assert_eq!(&bytes[..], &res[..]);
+    }
+
+    #[test]
+    fn convert_seed_not_works_for_short_seed() {
+        let service = CryptoService::new();
+
+        let res = service.convert_seed(Some("00000000000My1"));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn convert_seed_not_works_for_short_seed_with_field_in_message() {
+        let service = CryptoService::new();
+
+        let err = service.convert_seed(Some("00000000000My1")).unwrap_err();
+        assert!(err.to_string().contains("invalid `seed`"));
+    }
+
+    #[test]
+    fn create_my_did_not_works_for_unknown_crypto_type() {
+        let service = CryptoService::new();
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: Some("type".to_string()), method_name: None, strict_seed: None };
+
+        let err = service.create_my_did(&did_info).unwrap_err();
+        assert_eq!(IndyErrorKind::UnknownCrypto, err.kind());
+        assert!(err.to_string().contains("Unsupported `crypto_type`: type"));
+    }
+
+    #[test]
+    fn convert_seed_not_works_for_short_base58_seed() {
+        let service = CryptoService::new();
+
+        let res = service.convert_seed(Some(&base58::to_b58(&[7u8; ed25519_sign::SEEDBYTES - 1])));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn create_my_did_works_for_seed() {
+        let service = CryptoService::new();
+
+        let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
+        let seed = Some("00000000000000000000000000000My1".to_string());
+
+        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None, strict_seed: None };
+        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let (did_with_seed, _) = service.create_my_did(&did_info_with_seed).unwrap();
+        let (did_without_seed, _) = service.create_my_did(&did_info_without_seed).unwrap();
+
+        assert_ne!(did_with_seed.verkey, did_without_seed.verkey)
+    }
+
+    #[test]
+    fn supported_crypto_types_works() {
+        let service = CryptoService::new();
+
+        assert_eq!(vec![DEFAULT_CRYPTO_TYPE], service.supported_crypto_types());
+    }
+
+    #[test]
+    fn seed_entropy_works() {
+        assert_eq!(0.0, CryptoService::seed_entropy(b"00000000000000000000000000000000"));
+        assert!(CryptoService::seed_entropy(b"00000000000000000000000000000My1") < 1.0);
+        assert_eq!(5.0, CryptoService::seed_entropy(b"Ab3dEf6hIj9kLm2nOp5qRs8tUv1wXy4z"));
+    }
+
+    #[test]
+    fn create_my_did_works_for_low_entropy_seed_and_strict_seed() {
+        let service = CryptoService::new();
+        let seed = Some("00000000000000000000000000000My1".to_string());
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: seed.clone(), crypto_type: None, method_name: None, strict_seed: Some(true) };
+        let err = service.create_my_did(&did_info).unwrap_err();
+        assert_eq!(IndyErrorKind::InvalidStructure, err.kind());
+        assert!(err.to_string().contains("`seed` entropy is too low"));
+
+        let did_info = MyDidInfo { did: None, cid: None, seed, crypto_type: None, method_name: None, strict_seed: None };
+        service.create_my_did(&did_info).unwrap();
+    }
+
+    #[test]
+    fn create_my_did_works_for_strict_seed() {
+        let service = CryptoService::new();
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: Some("Ab3dEf6hIj9kLm2nOp5qRs8tUv1wXy4z".to_string()), crypto_type: None, method_name: None, strict_seed: Some(true) };
+        service.create_my_did(&did_info).unwrap();
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: Some(true) };
+        service.create_my_did(&did_info).unwrap();
+    }
+
+    #[test]
+    fn create_their_did_works_without_verkey() {
+        let service = CryptoService::new();
+        let did = DidValue("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string());
+
+        let their_did_info = TheirDidInfo::new(did.clone(), None);
+        let their_did = service.create_their_did(&their_did_info).unwrap();
+
+        assert_eq!(did, their_did.did);
+        assert_eq!(did.0, their_did.verkey);
+    }
+
+    #[test]
+    fn create_their_did_works_for_full_verkey() {
+        let service = CryptoService::new();
+        let did = DidValue("8wZcEriaNLNKtteJvx7f8i".to_string());
+        let verkey = "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp";
+
+        let their_did_info = TheirDidInfo::new(did.clone(), Some(verkey.to_string()));
+        let their_did = service.create_their_did(&their_did_info).unwrap();
+
+        assert_eq!(did, their_did.did);
+        assert_eq!(verkey, their_did.verkey);
+    }
+
+    #[test]
+    fn create_their_did_works_for_abbreviated_verkey() {
+        let service = CryptoService::new();
+        let did = DidValue("8wZcEriaNLNKtteJvx7f8i".to_string());
+        let their_did_info = TheirDidInfo::new(did.clone(), Some("~NcYxiDXkpYi6ov5FcYDi1e".to_string()));
+        let their_did = service.create_their_did(&their_did_info).unwrap();
+
+        assert_eq!(did, their_did.did);
+        assert_eq!("5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp", their_did.verkey);
+    }
+
+    #[test]
+    fn create_their_did_not_works_for_short_full_verkey() {
+        let service = CryptoService::new();
+        let did = DidValue("8wZcEriaNLNKtteJvx7f8i".to_string());
+        let their_did_info = TheirDidInfo::new(did, Some("NcYxiDXkpYi6ov5FcYDi1e".to_string()));
+
+        let res = service.create_their_did(&their_did_info);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn sign_works() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let message = r#"message"#;
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let sig = service.sign(&my_key, message.as_bytes());
+        assert!(sig.is_ok());
+    }
+
+    #[test]
+    fn sign_works_for_invalid_signkey() {
+        let service = CryptoService::new();
+        let message = r#"message"#;
+        let my_key = Key::new("8wZcEriaNLNKtteJvx7f8i".to_string(), "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp".to_string());
+        assert!(service.sign(&my_key, message.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn validate_key_for_crypto_type_works() {
+        let service = CryptoService::new();
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+
+        service.validate_key_for_crypto_type(&base58::from_b58(&key.verkey).unwrap(), DEFAULT_CRYPTO_TYPE).unwrap();
+        service.validate_key_for_crypto_type(&base58::from_b58(&key.signkey).unwrap(), DEFAULT_CRYPTO_TYPE).unwrap();
+    }
+
+    #[test]
+    fn validate_key_for_crypto_type_not_works_for_truncated_key() {
+        let service = CryptoService::new();
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+
+        let signkey = base58::from_b58(&key.signkey).unwrap();
+        let res = service.validate_key_for_crypto_type(&signkey[..signkey.len() - 1], DEFAULT_CRYPTO_TYPE);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn validate_key_for_crypto_type_not_works_for_invalid_point() {
+        let service = CryptoService::new();
+
+        let res = service.validate_key_for_crypto_type(&[2u8; ed25519_sign::SIG_PUBLICKEYBYTES], DEFAULT_CRYPTO_TYPE);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn sign_not_works_for_truncated_signkey() {
+        let service = CryptoService::new();
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+
+        let signkey = base58::from_b58(&key.signkey).unwrap();
+        let key = Key::new(key.verkey.clone(), base58::to_b58(&signkey[..signkey.len() - 1]));
+
+        let res = service.sign(&key, b"message");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn sign_verify_works() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let message = r#"message"#;
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+        let valid = service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap();
+        assert!(valid);
+    }
+
+    #[test]
+    fn sign_verify_works_for_empty_message() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+
+        let signature = service.sign(&my_key, &[]).unwrap();
+        assert_eq!(ed25519_sign::SIGNATUREBYTES, signature.len());
+
+        assert!(service.verify(&my_did.verkey, &[], &signature).unwrap());
+        assert!(!service.verify(&my_did.verkey, b"message", &signature).unwrap());
+    }
+
+    #[test]
+    fn sign_verify_works_for_whitespace_message_and_strict_flag() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+
+        let signature = service.sign(&my_key, b" \t\n").unwrap();
+        assert!(service.verify(&my_did.verkey, b" \t\n", &signature).unwrap());
+
+        service.set_strict_messages(true);
+
+        let sign_res = service.sign(&my_key, b" \t\n");
+        let verify_res = service.verify(&my_did.verkey, b" \t\n", &signature);
+        let empty_res = service.sign(&my_key, &[]);
+
+        assert_kind!(IndyErrorKind::InvalidStructure, sign_res);
+        assert_kind!(IndyErrorKind::InvalidStructure, verify_res);
+        assert!(empty_res.is_ok());
+    }
+
+    #[test]
+    fn zero_length_inputs_not_panic() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+
+        assert!(service.verify(&my_did.verkey, &[], &[]).is_err());
+        assert!(service.verify("", b"message", &[0u8; ed25519_sign::SIGNATUREBYTES]).is_err());
+        assert!(service.crypto_box_open(&my_key, &my_did.verkey, &[], &[]).is_err());
+        assert!(service.crypto_box_open(&my_key, &my_did.verkey, &[], &service.gen_nonce()).is_err());
+        assert!(service.crypto_box_seal_open(&my_key, &[]).is_err());
+        assert!(service.decrypt_with_passphrase(&[], "passphrase").is_err());
+        assert!(service.validate_key("").is_err());
+
+        let (encrypted, nonce) = service.crypto_box(&my_key, &my_did.verkey, &[]).unwrap();
+        assert_eq!(Vec::<u8>::new(), service.crypto_box_open(&my_key, &my_did.verkey, &encrypted, &nonce).unwrap());
+
+        let encrypted = service.crypto_box_seal(&my_did.verkey, &[]).unwrap();
+        assert_eq!(Vec::<u8>::new(), service.crypto_box_seal_open(&my_key, &encrypted).unwrap());
+    }
+
+    #[test]
+    fn sign_works_deterministically_for_seed() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: Some("00000000000000000000000000000My1".to_string()), crypto_type: None, method_name: None, strict_seed: None };
+        let message = r#"message"#;
+
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let (_, my_key_2) = service.create_my_did(&did_info).unwrap();
+
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+        let signature_2 = service.sign(&my_key_2, message.as_bytes()).unwrap();
+        assert_eq!(signature, signature_2);
+
+        let valid = service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap();
+        assert!(valid);
+    }
+
+    #[test]
+    fn sign_verify_works_for_verkey_contained_crypto_type() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let message = r#"message"#;
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+        let verkey = my_did.verkey + ":ed25519";
+        let valid = service.verify(&verkey, message.as_bytes(), &signature).unwrap();
+        assert!(valid);
+    }
+
+
+    #[test]
+    fn sign_verify_works_for_verkey_contained_invalid_crypto_type() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let message = r#"message"#;
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+        let verkey = format!("crypto_type:{}", my_did.verkey);
+        assert!(service.verify(&verkey, message.as_bytes(), &signature).is_err());
+    }
+
+    #[test]
+    fn verify_not_works_for_invalid_verkey() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let message = r#"message"#;
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+        let verkey = "AnnxV4t3LUHKZaxVQDWoVaG44NrGmeDYMA4Gz6C2tCZd";
+        let valid = service.verify(verkey, message.as_bytes(), &signature).unwrap();
+        assert_eq!(false, valid);
+    }
+
+    #[test]
+    fn verify_works_for_unknown_crypto_type() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let message = r#"message"#;
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+
+        let res = service.verify(&format!("{}:secp256k1", my_did.verkey), message.as_bytes(), &signature);
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
+    #[test]
+    fn crypto_box_works() {
+        let service = CryptoService::new();
+        let msg = "some message";
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
+        let their_did = Did::new(their_did.did, their_did.verkey);
+        let encrypted_message = service.crypto_box(&my_key, &their_did.verkey, msg.as_bytes());
+        assert!(encrypted_message.is_ok());
+    }
+
+    #[test]
+    fn crypto_box_and_crypto_box_open_works() {
+        let service = CryptoService::new();
+
+        let msg = "some message";
+
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+
+        let my_key_for_encrypt = my_key.clone();
+
+        let their_did_for_decrypt = Did::new(my_did.did, my_did.verkey);
+
+        let (their_did, their_key) = service.create_my_did(&did_info.clone()).unwrap();
+
+        let my_key_for_decrypt = their_key.clone();
+
+        let their_did_for_encrypt = Did::new(their_did.did, their_did.verkey);
+
+        let (encrypted_message, noce) = service.crypto_box(&my_key_for_encrypt, &their_did_for_encrypt.verkey, msg.as_bytes()).unwrap();
+
+        let decrypted_message = service.crypto_box_open(&my_key_for_decrypt, &their_did_for_decrypt.verkey, &encrypted_message, &noce).unwrap();
 
         assert_eq!(msg.as_bytes().to_vec(), decrypted_message);
     }
 
 
+    #[test]
+    fn crypto_box_open_not_works_for_wrong_recipient_key() {
+        let service = CryptoService::new();
+        let msg = "some message";
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
+        let (_, other_key) = service.create_my_did(&did_info.clone()).unwrap();
+
+        let (encrypted_message, nonce) = service.crypto_box(&my_key, &their_did.verkey, msg.as_bytes()).unwrap();
+
+        let res = service.crypto_box_open(&other_key, &my_did.verkey, &encrypted_message, &nonce);
+        assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn derive_shared_secret_works_for_both_directions() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let (their_did, their_key) = service.create_my_did(&did_info.clone()).unwrap();
+        let (other_did, _) = service.create_my_did(&did_info.clone()).unwrap();
+
+        let my_secret = service.derive_shared_secret(&my_key, &their_did.verkey).unwrap();
+        let their_secret = service.derive_shared_secret(&their_key, &my_did.verkey).unwrap();
+        let other_secret = service.derive_shared_secret(&my_key, &other_did.verkey).unwrap();
+
+        assert_eq!(my_secret, their_secret);
+        assert_ne!(my_secret, other_secret);
+    }
+
+    #[test]
+    fn derive_shared_secret_not_works_for_unknown_crypto_type() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+        let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
+
+        let res = service.derive_shared_secret(&my_key, &format!("{}:crypto_type", their_did.verkey));
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
+    #[test]
+    fn verkey_to_curve25519_works_for_reference_vector() {
+        let service = CryptoService::new();
+
+        assert_eq!("EoxFfhuHD7NkiYnn9CyvB1dJoLVN1257X9fAtxGJdTwL",
+                   service.verkey_to_curve25519("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa").unwrap());
+        assert_eq!("EoxFfhuHD7NkiYnn9CyvB1dJoLVN1257X9fAtxGJdTwL",
+                   service.verkey_to_curve25519("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa:ed25519").unwrap());
+    }
+
+    #[test]
+    fn verkey_to_curve25519_works_as_crypto_box() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let (my_did, _) = service.create_my_did(&did_info).unwrap();
+
+        let vk = ed25519_sign::PublicKey::from_slice(&base58::from_b58(&my_did.verkey).unwrap()).unwrap();
+        let expected = base58::to_b58(&ed25519_sign::vk_to_curve25519(&vk).unwrap()[..]);
+
+        assert_eq!(expected, service.verkey_to_curve25519(&my_did.verkey).unwrap());
+    }
+
+    #[test]
+    fn verkey_to_curve25519_not_works_for_unknown_crypto_type() {
+        let service = CryptoService::new();
+
+        let res = service.verkey_to_curve25519("GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa:crypto_type");
+        assert_kind!(IndyErrorKind::UnknownCrypto, res);
+    }
+
+    #[test]
+    fn signkey_to_curve25519_works_for_reference_vector() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: Some("00000000000000000000000000000My1".to_string()), crypto_type: None, method_name: None, strict_seed: None };
+
+        let (_, my_key) = service.create_my_did(&did_info).unwrap();
+
+        let res = service.signkey_to_curve25519(&my_key).unwrap();
+        assert_eq!("ADFHwoDNJwnh7eiv1itsmh884e36XLwVPSVPTCFdfcHc", res);
+        assert_ne!(my_key.signkey, res);
+    }
+
+    #[test]
+    fn signkey_to_curve25519_works_with_converted_verkey() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let (their_did, their_key) = service.create_my_did(&did_info.clone()).unwrap();
+
+        let my_sk = ed25519_box::SecretKey::from_slice(&base58::from_b58(&service.signkey_to_curve25519(&my_key).unwrap()).unwrap()).unwrap();
+        let their_pk = ed25519_box::PublicKey::from_slice(&base58::from_b58(&service.verkey_to_curve25519(&their_did.verkey).unwrap()).unwrap()).unwrap();
+
+        let shared_secret = ed25519_box::derive_shared_secret(&my_sk, &their_pk).unwrap();
+
+        assert_eq!(service.derive_shared_secret(&their_key, &my_did.verkey).unwrap(), shared_secret);
+    }
+
     #[test]
     fn crypto_box_and_crypto_box_open_works_for_verkey_contained_crypto_type() {
         let service = CryptoService::new();
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -671,7 +1549,7 @@ mod tests {
     fn crypto_box_seal_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
         let (did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let did = Did::new(did.did, did.verkey);
         let encrypted_message = service.crypto_box_seal(&did.verkey, msg.as_bytes());
@@ -682,7 +1560,7 @@ mod tests {
     fn crypto_box_seal_and_crypto_box_seal_open_works() {
         let service = CryptoService::new();
         let msg = "some message".as_bytes();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
         let (did, key) = service.create_my_did(&did_info.clone()).unwrap();
         let encrypt_did = Did::new(did.did.clone(), did.verkey.clone());
         let encrypted_message = service.crypto_box_seal(&encrypt_did.verkey, msg).unwrap();
@@ -690,6 +1568,17 @@ mod tests {
         assert_eq!(msg, decrypted_message.as_slice());
     }
 
+    #[test]
+    fn crypto_box_seal_open_not_works_for_other_key() {
+        let service = CryptoService::new();
+        let msg = "some message".as_bytes();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let (did, _) = service.create_my_did(&did_info.clone()).unwrap();
+        let (_, other_key) = service.create_my_did(&did_info.clone()).unwrap();
+        let encrypted_message = service.crypto_box_seal(&did.verkey, msg).unwrap();
+        assert!(service.crypto_box_seal_open(&other_key, &encrypted_message).is_err());
+    }
+
     #[test]
     pub fn test_encrypt_plaintext_and_decrypt_ciphertext_works() {
         let service: CryptoService = CryptoService::new();
@@ -824,4 +1713,163 @@ mod tests {
             .decrypt_ciphertext(&expected_ciphertext, &iv_encoded, &tag, bad_aad, &cek);
         assert!(expected_error.is_err());
     }
+
+    struct MockCryptoType {
+        calls: Rc<RefCell<Vec<&'static str>>>
+    }
+
+    impl CryptoType for MockCryptoType {
+        fn crypto_box(&self, _sk: &ed25519_sign::SecretKey, _vk: &ed25519_sign::PublicKey, doc: &[u8], _nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>> {
+            self.calls.borrow_mut().push("crypto_box");
+            Ok(doc.to_vec())
+        }
+
+        fn crypto_box_open(&self, _sk: &ed25519_sign::SecretKey, _vk: &ed25519_sign::PublicKey, doc: &[u8], _nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>> {
+            self.calls.borrow_mut().push("crypto_box_open");
+            Ok(doc.to_vec())
+        }
+
+        fn gen_nonce(&self) -> ed25519_box::Nonce {
+            self.calls.borrow_mut().push("gen_nonce");
+            ed25519_box::Nonce::from_slice(&[0u8; ed25519_box::NONCEBYTES]).unwrap()
+        }
+
+        fn create_key(&self, _seed: Option<&ed25519_sign::Seed>) -> IndyResult<(ed25519_sign::PublicKey, ed25519_sign::SecretKey)> {
+            self.calls.borrow_mut().push("create_key");
+            Ok((ed25519_sign::PublicKey::from_slice(&[1u8; ed25519_sign::SIG_PUBLICKEYBYTES])?,
+                ed25519_sign::SecretKey::from_slice(&[2u8; ed25519_sign::SIG_SECRETKEYBYTES])?))
+        }
+
+        fn validate_key(&self, _vk: &ed25519_sign::PublicKey) -> IndyResult<()> {
+            self.calls.borrow_mut().push("validate_key");
+            Ok(())
+        }
+
+        fn sign(&self, _sk: &ed25519_sign::SecretKey, _doc: &[u8]) -> IndyResult<ed25519_sign::Signature> {
+            self.calls.borrow_mut().push("sign");
+            ed25519_sign::Signature::from_slice(&[3u8; ed25519_sign::SIGNATUREBYTES])
+        }
+
+        fn verify(&self, _vk: &ed25519_sign::PublicKey, _doc: &[u8], signature: &ed25519_sign::Signature) -> IndyResult<bool> {
+            self.calls.borrow_mut().push("verify");
+            Ok(signature[..] == [3u8; ed25519_sign::SIGNATUREBYTES][..])
+        }
+
+        fn crypto_box_seal(&self, _vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>> {
+            self.calls.borrow_mut().push("crypto_box_seal");
+            Ok(doc.to_vec())
+        }
+
+        fn crypto_box_seal_open(&self, _vk: &ed25519_sign::PublicKey, _sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<Vec<u8>> {
+            self.calls.borrow_mut().push("crypto_box_seal_open");
+            Ok(doc.to_vec())
+        }
+
+        fn derive_shared_secret(&self, _sk: &ed25519_sign::SecretKey, _vk: &ed25519_sign::PublicKey) -> IndyResult<Vec<u8>> {
+            self.calls.borrow_mut().push("derive_shared_secret");
+            Ok(vec![4u8; ed25519_box::PUBLICKEYBYTES])
+        }
+
+        fn vk_to_curve25519(&self, _vk: &ed25519_sign::PublicKey) -> IndyResult<ed25519_box::PublicKey> {
+            self.calls.borrow_mut().push("vk_to_curve25519");
+            ed25519_box::PublicKey::from_slice(&[5u8; ed25519_box::PUBLICKEYBYTES])
+        }
+
+        fn sk_to_curve25519(&self, _sk: &ed25519_sign::SecretKey) -> IndyResult<ed25519_box::SecretKey> {
+            self.calls.borrow_mut().push("sk_to_curve25519");
+            ed25519_box::SecretKey::from_slice(&[6u8; ed25519_box::SECRETKEYBYTES])
+        }
+    }
+
+    fn _crypto_service_with_mock() -> (CryptoService, Rc<RefCell<Vec<&'static str>>>) {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+
+        let mut service = CryptoService::new();
+        service.crypto_types.insert("mock", Box::new(MockCryptoType { calls: calls.clone() }));
+
+        (service, calls)
+    }
+
+    #[test]
+    fn crypto_type_works_for_mock() {
+        let (service, calls) = _crypto_service_with_mock();
+
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: Some("mock".to_string()) }).unwrap();
+        assert_eq!(format!("{}:mock", base58::to_b58(&[1u8; ed25519_sign::SIG_PUBLICKEYBYTES])), key.verkey);
+
+        let signature = service.sign(&key, b"message").unwrap();
+        assert_eq!(vec![3u8; ed25519_sign::SIGNATUREBYTES], signature);
+
+        assert!(service.verify(&key.verkey, b"message", &signature).unwrap());
+
+        assert_eq!(vec!["create_key", "validate_key", "sign", "validate_key", "verify"], *calls.borrow());
+    }
+
+    #[test]
+    fn crypto_type_works_for_mock_and_ed25519_signature() {
+        let (service, calls) = _crypto_service_with_mock();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, strict_seed: None };
+        let message = r#"message"#;
+        let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+        let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+
+        let valid = service.verify(&format!("{}:mock", my_did.verkey), message.as_bytes(), &signature).unwrap();
+        assert!(!valid);
+
+        assert_eq!(vec!["validate_key", "verify"], *calls.borrow());
+    }
+
+    #[test]
+    fn crypto_type_works_for_mock_not_used_for_default_keys() {
+        let (service, calls) = _crypto_service_with_mock();
+
+        let key = service.create_key(&KeyInfo { seed: None, crypto_type: None }).unwrap();
+        let signature = service.sign(&key, b"message").unwrap();
+        assert!(service.verify(&key.verkey, b"message", &signature).unwrap());
+
+        assert!(calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn self_test_works() {
+        let service = CryptoService::new();
+
+        let report = service.self_test().unwrap();
+        assert_eq!(SelfTestReport { seed_derivation: true, sign_verify: true, box_open: true }, report);
+    }
+
+    #[test]
+    fn self_test_not_works_for_broken_crypto_type() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+
+        let mut service = CryptoService::new();
+        service.crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(MockCryptoType { calls: calls.clone() }));
+
+        let res = service.self_test();
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
+    #[test]
+    fn self_test_reports_each_broken_primitive() {
+        let mock = MockCryptoType { calls: Rc::new(RefCell::new(Vec::new())) };
+
+        let report = self_test::run(&mock);
+        assert_eq!(SelfTestReport { seed_derivation: false, sign_verify: false, box_open: false }, report);
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn crypto_type_works_for_mock_box() {
+        let (service, calls) = _crypto_service_with_mock();
+
+        let my_key = service.create_key(&KeyInfo { seed: None, crypto_type: Some("mock".to_string()) }).unwrap();
+
+        let (encrypted, nonce) = service.crypto_box(&my_key, &my_key.verkey, b"message").unwrap();
+        let decrypted = service.crypto_box_open(&my_key, &my_key.verkey, &encrypted, &nonce).unwrap();
+        assert_eq!(b"message".to_vec(), decrypted);
+
+        assert_eq!(vec!["create_key",
+                        "validate_key", "validate_key", "gen_nonce", "crypto_box",
+                        "validate_key", "validate_key", "crypto_box_open"], *calls.borrow());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,73 @@
+use crate::domain::crypto::key::SelfTestReport;
+use indy_utils::crypto::ed25519_box;
+use indy_utils::crypto::ed25519_sign;
+
+use super::CryptoType;
+
+const SEED_1: &[u8; 32] = b"00000000000000000000000000000My1";
+const SEED_2: &[u8; 32] = b"00000000000000000000000000000My2";
+
+// Verkeys produced by SEED_1 and SEED_2.
+const VERKEY_1: [u8; 32] = [233, 198, 118, 109, 146, 166, 42, 37, 34, 23, 211, 11, 40, 37, 124, 32, 134, 8, 18, 195, 139, 30, 194, 70, 230, 160, 73, 245, 42, 208, 96, 25];
+const VERKEY_2: [u8; 32] = [11, 58, 228, 111, 174, 57, 223, 185, 20, 16, 81, 66, 200, 138, 252, 35, 205, 80, 108, 128, 226, 235, 107, 36, 152, 222, 69, 159, 159, 140, 177, 81];
+
+const MESSAGE: &[u8] = br#"{"reqId":1496822211362017764}"#;
+
+// Signature of MESSAGE made with SEED_1 key.
+const SIGNATURE: [u8; 64] = [169, 215, 8, 225, 7, 107, 110, 9, 193, 162, 202, 214, 162, 66, 238, 211, 63, 209, 12, 196, 8, 211, 55, 27, 120, 94, 204, 147, 53, 104, 103, 61, 60, 249, 237, 127, 103, 46, 220, 223, 10, 95, 75, 53, 245, 210, 241, 151, 191, 41, 48, 30, 9, 16, 78, 252, 157, 206, 210, 145, 125, 133, 109, 11];
+
+const NONCE: [u8; 24] = [242, 246, 53, 153, 106, 37, 185, 65, 212, 14, 109, 131, 200, 169, 94, 110, 51, 47, 101, 89, 0, 171, 105, 183];
+
+// MESSAGE boxed from SEED_1 key to SEED_2 key with NONCE.
+const BOX: [u8; 45] = [215, 6, 16, 9, 181, 98, 102, 110, 167, 248, 172, 91, 56, 92, 137, 221, 212, 159, 154, 76, 159, 195, 162, 186, 206, 13, 238, 190, 34, 210, 73, 178, 207, 201, 61, 62, 181, 31, 203, 45, 198, 4, 115, 10, 153];
+
+pub(super) fn run(crypto_type: &dyn CryptoType) -> SelfTestReport {
+    let report = SelfTestReport {
+        seed_derivation: seed_derivation(crypto_type).unwrap_or(false),
+        sign_verify: sign_verify(crypto_type).unwrap_or(false),
+        box_open: box_open(crypto_type).unwrap_or(false),
+    };
+
+    if !report.passed() {
+        warn!("Crypto self-test failed: {:?}", report);
+    }
+
+    report
+}
+
+fn key_pair(crypto_type: &dyn CryptoType, seed: &[u8]) -> Option<(ed25519_sign::PublicKey, ed25519_sign::SecretKey)> {
+    let seed = ed25519_sign::Seed::from_slice(seed).ok()?;
+    crypto_type.create_key(Some(&seed)).ok()
+}
+
+fn seed_derivation(crypto_type: &dyn CryptoType) -> Option<bool> {
+    let (vk_1, _) = key_pair(crypto_type, SEED_1)?;
+    let (vk_2, _) = key_pair(crypto_type, SEED_2)?;
+
+    Some(vk_1[..] == VERKEY_1[..] && vk_2[..] == VERKEY_2[..])
+}
+
+fn sign_verify(crypto_type: &dyn CryptoType) -> Option<bool> {
+    let (vk, sk) = key_pair(crypto_type, SEED_1)?;
+
+    let signature = crypto_type.sign(&sk, MESSAGE).ok()?;
+    let expected_signature = ed25519_sign::Signature::from_slice(&SIGNATURE).ok()?;
+
+    let mut tampered_message = MESSAGE.to_vec();
+    tampered_message[0] ^= 1;
+
+    Some(signature[..] == SIGNATURE[..]
+        && crypto_type.verify(&vk, MESSAGE, &expected_signature).ok()?
+        && !crypto_type.verify(&vk, &tampered_message, &expected_signature).ok()?)
+}
+
+fn box_open(crypto_type: &dyn CryptoType) -> Option<bool> {
+    let (vk_1, sk_1) = key_pair(crypto_type, SEED_1)?;
+    let (vk_2, sk_2) = key_pair(crypto_type, SEED_2)?;
+    let nonce = ed25519_box::Nonce::from_slice(&NONCE).ok()?;
+
+    let boxed = crypto_type.crypto_box(&sk_1, &vk_2, MESSAGE, &nonce).ok()?;
+    let opened = crypto_type.crypto_box_open(&sk_2, &vk_1, &BOX, &nonce).ok()?;
+
+    Some(boxed[..] == BOX[..] && opened[..] == MESSAGE[..])
+}
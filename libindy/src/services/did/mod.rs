@@ -0,0 +1 @@
+pub mod verkey_cache;
@@ -0,0 +1,216 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use indy_api_types::WalletHandle;
+
+use crate::domain::crypto::did::VerkeySource;
+
+/// How long a verkey resolved from the ledger is served without asking the pool again.
+pub const DEFAULT_VERKEY_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// How many verkeys are cached at most, the oldest ones are evicted first.
+pub const MAX_VERKEY_CACHE_ENTRIES: usize = 10_000;
+
+#[derive(Debug, PartialEq)]
+pub enum CachedVerkey {
+    Fresh(String, VerkeySource),
+    Expired,
+    Missing,
+}
+
+/// In-memory cache of verkeys resolved from the ledger, keyed by wallet and DID.
+/// An entry also keeps where the verkey came from: the ledger, or the stored DID when the ledger couldn't be reached.
+/// An evicted DID is looked up in the wallet again, as one that was never cached.
+pub struct VerkeyCache {
+    ttl: Cell<Duration>,
+    max_entries: usize,
+    entries: RefCell<HashMap<(WalletHandle, String), (String, VerkeySource, Instant)>>,
+}
+
+impl VerkeyCache {
+    pub fn new(ttl: Duration) -> VerkeyCache {
+        VerkeyCache::with_max_entries(ttl, MAX_VERKEY_CACHE_ENTRIES)
+    }
+
+    fn with_max_entries(ttl: Duration, max_entries: usize) -> VerkeyCache {
+        VerkeyCache {
+            ttl: Cell::new(ttl),
+            max_entries,
+            entries: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn set_ttl(&self, ttl: Duration) {
+        self.ttl.set(ttl);
+    }
+
+    pub fn get(&self, wallet_handle: WalletHandle, did: &str, now: Instant) -> CachedVerkey {
+        match self.entries.borrow().get(&(wallet_handle, did.to_string())) {
+            Some((verkey, source, resolved_at)) if now.duration_since(*resolved_at) < self.ttl.get() => CachedVerkey::Fresh(verkey.clone(), *source),
+            Some(_) => CachedVerkey::Expired,
+            None => CachedVerkey::Missing,
+        }
+    }
+
+    pub fn insert(&self, wallet_handle: WalletHandle, did: &str, verkey: &str, source: VerkeySource, now: Instant) {
+        let mut entries = self.entries.borrow_mut();
+        let key = (wallet_handle, did.to_string());
+
+        if !entries.contains_key(&key) && entries.len() >= self.max_entries {
+            // Expired entries go first, a full cache of fresh ones loses the oldest
+            let ttl = self.ttl.get();
+            entries.retain(|_, (_, _, resolved_at)| now.duration_since(*resolved_at) < ttl);
+
+            if entries.len() >= self.max_entries {
+                let oldest = entries.iter()
+                    .min_by_key(|(_, (_, _, resolved_at))| *resolved_at)
+                    .map(|(key, _)| key.clone());
+
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+
+        entries.insert(key, (verkey.to_string(), source, now));
+    }
+
+    pub fn invalidate(&self, wallet_handle: WalletHandle, did: &str) {
+        self.entries.borrow_mut().remove(&(wallet_handle, did.to_string()));
+    }
+
+    pub fn invalidate_wallet(&self, wallet_handle: WalletHandle) {
+        self.entries.borrow_mut().retain(|(handle, _), _| *handle != wallet_handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID: &str = "8wZcEriaNLNKtteJvx7f8i";
+    const VERKEY: &str = "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp";
+
+    fn wallet_handle() -> WalletHandle {
+        WalletHandle(1)
+    }
+
+    #[test]
+    fn get_works_for_hit_within_ttl() {
+        let cache = VerkeyCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+
+        assert_eq!(CachedVerkey::Fresh(VERKEY.to_string(), VerkeySource::Ledger), cache.get(wallet_handle(), DID, now));
+        assert_eq!(CachedVerkey::Fresh(VERKEY.to_string(), VerkeySource::Ledger), cache.get(wallet_handle(), DID, now + Duration::from_secs(9)));
+    }
+
+    #[test]
+    fn get_works_for_expired_entry() {
+        let cache = VerkeyCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+
+        assert_eq!(CachedVerkey::Expired, cache.get(wallet_handle(), DID, now + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn get_works_for_missing_entry() {
+        let cache = VerkeyCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+
+        assert_eq!(CachedVerkey::Missing, cache.get(WalletHandle(2), DID, now));
+        assert_eq!(CachedVerkey::Missing, cache.get(wallet_handle(), "VsKV7grR1BUE29mG2Fm2kX", now));
+    }
+
+    #[test]
+    fn insert_works_for_re_resolved_entry() {
+        let cache = VerkeyCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.insert(wallet_handle(), DID, "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW", VerkeySource::Ledger, now + Duration::from_secs(10));
+
+        assert_eq!(CachedVerkey::Fresh("CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW".to_string(), VerkeySource::Ledger),
+                   cache.get(wallet_handle(), DID, now + Duration::from_secs(15)));
+    }
+
+    #[test]
+    fn invalidate_works() {
+        let cache = VerkeyCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.invalidate(wallet_handle(), DID);
+
+        assert_eq!(CachedVerkey::Missing, cache.get(wallet_handle(), DID, now));
+    }
+
+    #[test]
+    fn invalidate_wallet_works() {
+        let cache = VerkeyCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.insert(WalletHandle(2), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.invalidate_wallet(wallet_handle());
+
+        assert_eq!(CachedVerkey::Missing, cache.get(wallet_handle(), DID, now));
+        assert_eq!(CachedVerkey::Fresh(VERKEY.to_string(), VerkeySource::Ledger), cache.get(WalletHandle(2), DID, now));
+    }
+
+    #[test]
+    fn set_ttl_works() {
+        let cache = VerkeyCache::new(Duration::from_secs(10));
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.set_ttl(Duration::from_secs(5));
+
+        assert_eq!(CachedVerkey::Expired, cache.get(wallet_handle(), DID, now + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn insert_works_for_full_cache_with_expired_entries() {
+        let cache = VerkeyCache::with_max_entries(Duration::from_secs(10), 2);
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.insert(WalletHandle(2), DID, VERKEY, VerkeySource::Ledger, now + Duration::from_secs(5));
+        cache.insert(WalletHandle(3), DID, VERKEY, VerkeySource::Ledger, now + Duration::from_secs(10));
+
+        assert_eq!(2, cache.entries.borrow().len());
+        assert_eq!(CachedVerkey::Missing, cache.get(wallet_handle(), DID, now + Duration::from_secs(10)));
+        assert_eq!(CachedVerkey::Fresh(VERKEY.to_string(), VerkeySource::Ledger), cache.get(WalletHandle(2), DID, now + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn insert_works_for_full_cache_with_fresh_entries() {
+        let cache = VerkeyCache::with_max_entries(Duration::from_secs(10), 2);
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.insert(WalletHandle(2), DID, VERKEY, VerkeySource::Ledger, now + Duration::from_secs(1));
+        cache.insert(WalletHandle(3), DID, VERKEY, VerkeySource::Ledger, now + Duration::from_secs(2));
+
+        assert_eq!(2, cache.entries.borrow().len());
+        assert_eq!(CachedVerkey::Missing, cache.get(wallet_handle(), DID, now + Duration::from_secs(2)));
+        assert_eq!(CachedVerkey::Fresh(VERKEY.to_string(), VerkeySource::Ledger), cache.get(WalletHandle(3), DID, now + Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn insert_works_for_full_cache_and_cached_did() {
+        let cache = VerkeyCache::with_max_entries(Duration::from_secs(10), 1);
+        let now = Instant::now();
+
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::Ledger, now);
+        cache.insert(wallet_handle(), DID, VERKEY, VerkeySource::TheirCached, now + Duration::from_secs(1));
+
+        assert_eq!(CachedVerkey::Fresh(VERKEY.to_string(), VerkeySource::TheirCached), cache.get(wallet_handle(), DID, now + Duration::from_secs(1)));
+    }
+}
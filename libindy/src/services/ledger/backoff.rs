@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+/// How many times a ledger lookup is resubmitted after the pool timed out.
+pub const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// Delay before the first resubmission, every next one waits twice as long.
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_secs(1);
+
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Retry policy for the ledger lookups DID commands fall back to when a DID isn't in the wallet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BackoffPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+}
+
+impl BackoffPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration) -> BackoffPolicy {
+        BackoffPolicy {
+            max_retries,
+            base_delay,
+        }
+    }
+
+    /// Returns the policy with the given settings replaced, the rest is kept.
+    pub fn update(&self, max_retries: Option<u32>, base_delay: Option<Duration>) -> BackoffPolicy {
+        BackoffPolicy {
+            max_retries: max_retries.unwrap_or(self.max_retries),
+            base_delay: base_delay.unwrap_or(self.base_delay),
+        }
+    }
+
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base_delay
+            .checked_mul(2u32.saturating_pow(retry))
+            .map_or(MAX_DELAY, |delay| ::std::cmp::min(delay, MAX_DELAY))
+    }
+
+    /// Delay before resubmitting a lookup that has already been resubmitted `retry` times,
+    /// `None` once the retries are exhausted.
+    pub fn next_delay(&self, retry: u32) -> Option<Duration> {
+        if retry < self.max_retries {
+            Some(self.delay(retry))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> BackoffPolicy {
+        BackoffPolicy::new(DEFAULT_MAX_RETRIES, DEFAULT_BASE_DELAY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_works() {
+        let policy = BackoffPolicy::new(5, Duration::from_millis(100));
+
+        assert_eq!(Duration::from_millis(100), policy.delay(0));
+        assert_eq!(Duration::from_millis(200), policy.delay(1));
+        assert_eq!(Duration::from_millis(400), policy.delay(2));
+        assert_eq!(MAX_DELAY, policy.delay(40));
+    }
+
+    #[test]
+    fn next_delay_works() {
+        let policy = BackoffPolicy::new(3, Duration::from_millis(100));
+
+        assert_eq!(Some(Duration::from_millis(100)), policy.next_delay(0));
+        assert_eq!(Some(Duration::from_millis(200)), policy.next_delay(1));
+        assert_eq!(Some(Duration::from_millis(400)), policy.next_delay(2));
+        assert_eq!(None, policy.next_delay(3));
+    }
+
+    #[test]
+    fn next_delay_works_for_no_retries() {
+        let policy = BackoffPolicy::new(0, Duration::from_millis(100));
+
+        assert_eq!(None, policy.next_delay(0));
+    }
+
+    #[test]
+    fn update_works() {
+        let policy = BackoffPolicy::default();
+
+        assert_eq!(BackoffPolicy::new(5, DEFAULT_BASE_DELAY), policy.update(Some(5), None));
+        assert_eq!(BackoffPolicy::new(DEFAULT_MAX_RETRIES, Duration::from_millis(10)), policy.update(None, Some(Duration::from_millis(10))));
+        assert_eq!(policy, policy.update(None, None));
+    }
+}
@@ -11,7 +11,7 @@ use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistr
 use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
 use crate::domain::anoncreds::schema::{Schema, SchemaV1, SchemaId};
 use crate::domain::crypto::did::DidValue;
-use crate::domain::ledger::attrib::{AttribOperation, GetAttribOperation};
+use crate::domain::ledger::attrib::{AttribData, AttribOperation, Endpoint, GetAttribOperation, GetAttrReplyResult};
 use crate::domain::ledger::constants::{GET_VALIDATOR_INFO, POOL_RESTART, ROLE_REMOVE, STEWARD, ENDORSER, TRUSTEE, NETWORK_MONITOR, ROLES, txn_name_to_code};
 use crate::domain::ledger::cred_def::{CredDefOperation, GetCredDefOperation, GetCredDefReplyResult};
 use crate::domain::ledger::ddo::GetDdoOperation;
@@ -31,6 +31,7 @@ use crate::domain::ledger::ledgers_freeze::{LedgersFreezeOperation, GetFrozenLed
 use indy_api_types::errors::prelude::*;
 use indy_utils::crypto::hash::hash as openssl_hash;
 
+pub mod backoff;
 pub mod merkletree;
 
 macro_rules! build_result {
@@ -90,6 +91,15 @@ impl LedgerService {
 
     #[logfn(Info)]
     pub fn parse_get_nym_response(&self, get_nym_response: &str) -> IndyResult<String> {
+        let nym_data = self.parse_get_nym_data(get_nym_response)?;
+
+        let res = serde_json::to_string(&nym_data)
+            .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidState, format!("Cannot serialize NYM data: {}", err)))?;
+
+        Ok(res)
+    }
+
+    pub fn parse_get_nym_data(&self, get_nym_response: &str) -> IndyResult<NymData> {
         let reply: Reply<GetNymReplyResult> = LedgerService::parse_response(get_nym_response)?;
 
         let nym_data = match reply.result() {
@@ -115,10 +125,33 @@ impl LedgerService {
             }
         };
 
-        let res = serde_json::to_string(&nym_data)
-            .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidState, format!("Cannot serialize NYM data: {}", err)))?;
+        Ok(nym_data)
+    }
 
-        Ok(res)
+    /// Returns the endpoint of `did` along with the DID qualified the same way as the requested one.
+    pub fn parse_get_attrib_endpoint(&self, get_attrib_response: &str, did: &DidValue) -> IndyResult<(DidValue, Endpoint)> {
+        let reply: Reply<GetAttrReplyResult> = LedgerService::parse_response(get_attrib_response)?;
+
+        let (raw, dest) = match reply.result() {
+            GetAttrReplyResult::GetAttrReplyResultV0(res) => (res.data, res.dest),
+            GetAttrReplyResult::GetAttrReplyResultV1(res) => (Some(res.txn.data.raw), res.txn.data.did)
+        };
+
+        if dest != did.to_short() {
+            return Err(IndyError::from_msg(IndyErrorKind::InvalidState,
+                                           format!("GET_ATTRIB response is for DID {} instead of {}", dest.0, did.0)));
+        }
+
+        let attrib_data: AttribData = raw
+            .ok_or(IndyError::from_msg(IndyErrorKind::LedgerItemNotFound, "Attribute not found"))
+            .and_then(|raw| serde_json::from_str(&raw)
+                .map_err(|err| IndyError::from_msg(IndyErrorKind::InvalidState, format!("Cannot parse GET_ATTRIB response: {}", err)))
+            )?;
+
+        let endpoint = attrib_data.endpoint
+            .ok_or(IndyError::from_msg(IndyErrorKind::LedgerItemNotFound, "Endpoint not found"))?;
+
+        Ok((dest.qualify(did.get_method()), endpoint))
     }
 
     #[logfn(Info)]
@@ -486,6 +519,19 @@ impl LedgerService {
         }
     }
 
+    #[logfn(Info)]
+    pub fn parse_write_response(&self, response: &str) -> IndyResult<()> {
+        let message: Message<serde_json::Value> = serde_json::from_str(response)
+            .to_indy(IndyErrorKind::InvalidTransaction, "Response is invalid json")?;
+
+        match message {
+            Message::Reject(response) | Message::ReqNACK(response) =>
+                Err(err_msg(IndyErrorKind::InvalidTransaction, format!("Transaction has been failed: {:?}", response.reason))),
+            Message::Reply(_) =>
+                Ok(())
+        }
+    }
+
     #[logfn(Info)]
     pub fn validate_action(&self, request: &str) -> IndyResult<()> {
         let request: Request<serde_json::Value> = serde_json::from_str(request)
@@ -569,9 +615,10 @@ impl LedgerService {
 #[cfg(test)]
 mod tests {
     use crate::domain::anoncreds::schema::AttributeNames;
+    use crate::domain::crypto::did::ShortDidValue;
     use crate::domain::ledger::constants::*;
     use crate::domain::ledger::node::Services;
-    use crate::domain::ledger::request::ProtocolVersion;
+    use crate::domain::ledger::request::{ProtocolVersion, DEFAULT_LIBIDY_DID};
 
     use super::*;
 
@@ -643,6 +690,191 @@ mod tests {
         check_request(&request, expected_result);
     }
 
+    #[test]
+    fn build_get_nym_request_works_for_fully_qualified_dids() {
+        let ledger_service = LedgerService::new();
+
+        let expected_result = json!({
+            "type": GET_NYM,
+            "dest": DEST
+        });
+
+        let identifier = DidValue(format!("did:sov:{}", IDENTIFIER));
+        let dest = DidValue(format!("did:sov:{}", DEST));
+
+        let request = ledger_service.build_get_nym_request(Some(&identifier), &dest).unwrap();
+        check_request(&request, expected_result);
+
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+        assert_eq!(IDENTIFIER, request["identifier"].as_str().unwrap());
+    }
+
+    #[test]
+    fn build_get_nym_request_works_without_submitter() {
+        let ledger_service = LedgerService::new();
+
+        let request = ledger_service.build_get_nym_request(None, &dest()).unwrap();
+
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+        assert_eq!(DEFAULT_LIBIDY_DID, request["identifier"].as_str().unwrap());
+        assert_eq!(DEST, request["operation"]["dest"].as_str().unwrap());
+    }
+
+    #[test]
+    fn build_get_nym_request_contains_request_metadata() {
+        let ledger_service = LedgerService::new();
+
+        let request = ledger_service.build_get_nym_request(Some(&identifier()), &dest()).unwrap();
+
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+        assert!(request["reqId"].as_u64().unwrap() > 0);
+        assert!(request["protocolVersion"].is_u64());
+        assert_eq!(IDENTIFIER, request["identifier"].as_str().unwrap());
+    }
+
+    fn get_nym_reply(data: Option<serde_json::Value>) -> String {
+        json!({
+            "op": "REPLY",
+            "result": {
+                "type": GET_NYM,
+                "identifier": IDENTIFIER,
+                "reqId": 1,
+                "dest": DEST,
+                "seqNo": 10,
+                "txnTime": 1,
+                "data": data.map(|data| data.to_string())
+            }
+        }).to_string()
+    }
+
+    #[test]
+    fn parse_get_nym_data_works() {
+        let ledger_service = LedgerService::new();
+
+        let reply = get_nym_reply(Some(json!({"identifier": IDENTIFIER, "dest": DEST, "role": "0", "verkey": VERKEY})));
+
+        let nym_data = ledger_service.parse_get_nym_data(&reply).unwrap();
+        assert_eq!(NymData {
+            did: ShortDidValue(DEST.to_string()),
+            verkey: Some(VERKEY.to_string()),
+            role: Some("0".to_string()),
+        }, nym_data);
+    }
+
+    #[test]
+    fn parse_get_nym_data_works_for_abbreviated_verkey() {
+        let ledger_service = LedgerService::new();
+
+        let reply = get_nym_reply(Some(json!({"identifier": IDENTIFIER, "dest": DEST, "role": null, "verkey": "~NcYxiDXkpYi6ov5FcYDi1e"})));
+
+        let nym_data = ledger_service.parse_get_nym_data(&reply).unwrap();
+        assert_eq!(Some("~NcYxiDXkpYi6ov5FcYDi1e".to_string()), nym_data.verkey);
+        assert_eq!(None, nym_data.role);
+    }
+
+    #[test]
+    fn parse_get_nym_data_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.parse_get_nym_data(&get_nym_reply(None));
+        assert_kind!(IndyErrorKind::LedgerItemNotFound, res);
+    }
+
+    fn get_attrib_reply(data: Option<serde_json::Value>) -> String {
+        json!({
+            "op": "REPLY",
+            "result": {
+                "type": GET_ATTR,
+                "identifier": IDENTIFIER,
+                "reqId": 1,
+                "dest": DEST,
+                "raw": "endpoint",
+                "seqNo": 10,
+                "txnTime": 1,
+                "data": data.map(|data| data.to_string())
+            }
+        }).to_string()
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works() {
+        let ledger_service = LedgerService::new();
+
+        let reply = get_attrib_reply(Some(json!({"endpoint": {"ha": "127.0.0.1:5555", "verkey": VERKEY}})));
+
+        let (did, endpoint) = ledger_service.parse_get_attrib_endpoint(&reply, &dest()).unwrap();
+        assert_eq!(dest(), did);
+        assert_eq!("127.0.0.1:5555", endpoint.ha);
+        assert_eq!(Some(VERKEY.to_string()), endpoint.verkey);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works_for_no_verkey() {
+        let ledger_service = LedgerService::new();
+
+        let reply = get_attrib_reply(Some(json!({"endpoint": {"ha": "127.0.0.1:5555"}})));
+
+        let (_, endpoint) = ledger_service.parse_get_attrib_endpoint(&reply, &dest()).unwrap();
+        assert_eq!("127.0.0.1:5555", endpoint.ha);
+        assert_eq!(None, endpoint.verkey);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.parse_get_attrib_endpoint(&get_attrib_reply(None), &dest());
+        assert_kind!(IndyErrorKind::LedgerItemNotFound, res);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works_for_other_attribute() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.parse_get_attrib_endpoint(&get_attrib_reply(Some(json!({"url": "http://example.com"}))), &dest());
+        assert_kind!(IndyErrorKind::LedgerItemNotFound, res);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works_for_malformed_endpoint() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.parse_get_attrib_endpoint(&get_attrib_reply(Some(json!({"endpoint": {"verkey": VERKEY}}))), &dest());
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works_for_qualified_did() {
+        let ledger_service = LedgerService::new();
+
+        let reply = get_attrib_reply(Some(json!({"endpoint": {"ha": "127.0.0.1:5555", "verkey": VERKEY}})));
+        let qualified_dest = DidValue(format!("did:sov:{}", DEST));
+
+        let (did, endpoint) = ledger_service.parse_get_attrib_endpoint(&reply, &qualified_dest).unwrap();
+        assert_eq!(qualified_dest, did);
+        assert_eq!("127.0.0.1:5555", endpoint.ha);
+    }
+
+    #[test]
+    fn parse_get_attrib_endpoint_works_for_other_dest() {
+        let ledger_service = LedgerService::new();
+
+        let reply = get_attrib_reply(Some(json!({"endpoint": {"ha": "127.0.0.1:5555", "verkey": VERKEY}})));
+
+        let res = ledger_service.parse_get_attrib_endpoint(&reply, &identifier());
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+
+    #[test]
+    fn parse_get_nym_response_works() {
+        let ledger_service = LedgerService::new();
+
+        let reply = get_nym_reply(Some(json!({"identifier": IDENTIFIER, "dest": DEST, "role": "0", "verkey": VERKEY})));
+
+        let nym_data: serde_json::Value = serde_json::from_str(&ledger_service.parse_get_nym_response(&reply).unwrap()).unwrap();
+        assert_eq!(json!({"did": DEST, "verkey": VERKEY, "role": "0"}), nym_data);
+    }
+
     #[test]
     fn build_get_ddo_request_works() {
         let ledger_service = LedgerService::new();
@@ -1294,6 +1526,40 @@ mod tests {
         assert_eq!(1562284800, LedgerService::datetime_to_date_timestamp(1562284800));
     }
 
+    #[test]
+    fn parse_write_response_works_for_reply() {
+        let ledger_service = LedgerService::new();
+
+        let response = json!({"op": "REPLY", "result": {"txnMetadata": {"seqNo": 10}}}).to_string();
+        ledger_service.parse_write_response(&response).unwrap();
+    }
+
+    #[test]
+    fn parse_write_response_works_for_reject() {
+        let ledger_service = LedgerService::new();
+
+        let response = json!({"op": "REJECT", "reqId": 1, "reason": "client request invalid"}).to_string();
+        let res = ledger_service.parse_write_response(&response);
+        assert_kind!(IndyErrorKind::InvalidTransaction, res);
+    }
+
+    #[test]
+    fn parse_write_response_works_for_reqnack() {
+        let ledger_service = LedgerService::new();
+
+        let response = json!({"op": "REQNACK", "reqId": 1, "reason": "unknown identifier"}).to_string();
+        let res = ledger_service.parse_write_response(&response);
+        assert_kind!(IndyErrorKind::InvalidTransaction, res);
+    }
+
+    #[test]
+    fn parse_write_response_works_for_invalid_json() {
+        let ledger_service = LedgerService::new();
+
+        let res = ledger_service.parse_write_response("not a json");
+        assert_kind!(IndyErrorKind::InvalidTransaction, res);
+    }
+
     fn check_request(request: &str, expected_result: serde_json::Value) {
         let request: serde_json::Value = serde_json::from_str(request).unwrap();
         assert_eq!(request["operation"], expected_result);
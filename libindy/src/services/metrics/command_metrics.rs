@@ -132,13 +132,24 @@ impl From<&Command> for CommandMetric {
                     CryptoCommand::SetKeyMetadata(_, _, _, _) => { CommandMetric::CryptoCommandSetKeyMetadata }
                     CryptoCommand::GetKeyMetadata(_, _, _) => { CommandMetric::CryptoCommandGetKeyMetadata }
                     CryptoCommand::CryptoSign(_, _, _, _) => { CommandMetric::CryptoCommandCryptoSign }
+                    CryptoCommand::CryptoSignMultiple(_, _, _, _) => { CommandMetric::CryptoCommandCryptoSignMultiple }
+                    CryptoCommand::CryptoMultiSign(_, _, _, _) => { CommandMetric::CryptoCommandCryptoMultiSign }
                     CryptoCommand::CryptoVerify(_, _, _, _) => { CommandMetric::CryptoCommandCryptoVerify }
+                    CryptoCommand::CryptoVerifyDetailed(_, _, _, _) => { CommandMetric::CryptoCommandCryptoVerifyDetailed }
+                    CryptoCommand::CryptoVerifyAnyKey(_, _, _, _) => { CommandMetric::CryptoCommandCryptoVerifyAnyKey }
+                    CryptoCommand::CryptoSignJson(_, _, _, _) => { CommandMetric::CryptoCommandCryptoSignJson }
+                    CryptoCommand::CryptoVerifyJson(_, _, _, _) => { CommandMetric::CryptoCommandCryptoVerifyJson }
+                    CryptoCommand::GetNonce(_) => { CommandMetric::CryptoCommandGetNonce }
+                    CryptoCommand::SelfTest(_) => { CommandMetric::CryptoCommandSelfTest }
+                    CryptoCommand::GetCryptoTypesSupported(_) => { CommandMetric::CryptoCommandGetCryptoTypesSupported }
                     CryptoCommand::AuthenticatedEncrypt(_, _, _, _, _) => { CommandMetric::CryptoCommandAuthenticatedEncrypt }
                     CryptoCommand::AuthenticatedDecrypt(_, _, _, _) => { CommandMetric::CryptoCommandAuthenticatedDecrypt }
                     CryptoCommand::AnonymousEncrypt(_, _, _) => { CommandMetric::CryptoCommandAnonymousEncrypt }
                     CryptoCommand::AnonymousDecrypt(_, _, _, _) => { CommandMetric::CryptoCommandAnonymousDecrypt }
+                    CryptoCommand::DeriveSharedSecret(_, _, _, _) => { CommandMetric::CryptoCommandDeriveSharedSecret }
                     CryptoCommand::PackMessage(_, _, _, _, _) => { CommandMetric::CryptoCommandPackMessage }
                     CryptoCommand::UnpackMessage(_, _, _) => { CommandMetric::CryptoCommandUnpackMessage }
+                    CryptoCommand::SetStrictMessages(_) => { CommandMetric::CryptoCommandSetStrictMessages }
                 }
             }
             Command::Ledger(cmd) => {
@@ -214,18 +225,43 @@ impl From<&Command> for CommandMetric {
                     DidCommand::CreateAndStoreMyDid(_, _, _) => { CommandMetric::DidCommandCreateAndStoreMyDid }
                     DidCommand::ReplaceKeysStart(_, _, _, _) => { CommandMetric::DidCommandReplaceKeysStart }
                     DidCommand::ReplaceKeysApply(_, _, _) => { CommandMetric::DidCommandReplaceKeysApply }
+                    DidCommand::RotateAndPublish(_, _, _, _, _) => { CommandMetric::DidCommandRotateAndPublish }
                     DidCommand::StoreTheirDid(_, _, _) => { CommandMetric::DidCommandStoreTheirDid }
+                    DidCommand::StoreTheirDids(_, _, _) => { CommandMetric::DidCommandStoreTheirDids }
                     DidCommand::GetMyDidWithMeta(_, _, _) => { CommandMetric::DidCommandGetMyDidWithMeta }
                     DidCommand::ListMyDidsWithMeta(_, _) => { CommandMetric::DidCommandListMyDidsWithMeta }
+                    DidCommand::ListTheirDids(_, _, _) => { CommandMetric::DidCommandListTheirDids }
                     DidCommand::KeyForDid(_, _, _, _) => { CommandMetric::DidCommandKeyForDid }
+                    DidCommand::KeyForDidDetailed(_, _, _, _) => { CommandMetric::DidCommandKeyForDidDetailed }
                     DidCommand::KeyForLocalDid(_, _, _) => { CommandMetric::DidCommandKeyForLocalDid }
                     DidCommand::SetEndpointForDid(_, _, _, _) => { CommandMetric::DidCommandSetEndpointForDid }
                     DidCommand::GetEndpointForDid(_, _, _, _) => { CommandMetric::DidCommandGetEndpointForDid }
+                    DidCommand::RekeyEndpoint(_, _, _, _) => { CommandMetric::DidCommandRekeyEndpoint }
+                    DidCommand::PrepareAnoncryptedConnectionRequest(_, _, _, _) => { CommandMetric::DidCommandPrepareAnoncryptedConnectionRequest }
+                    DidCommand::ParseConnectionRequest(_, _, _, _) => { CommandMetric::DidCommandParseConnectionRequest }
+                    DidCommand::ReEncrypt(_, _, _, _, _, _) => { CommandMetric::DidCommandReEncrypt }
                     DidCommand::SetDidMetadata(_, _, _, _) => { CommandMetric::DidCommandSetDidMetadata }
                     DidCommand::GetDidMetadata(_, _, _) => { CommandMetric::DidCommandGetDidMetadata }
                     DidCommand::AbbreviateVerkey(_, _, _) => { CommandMetric::DidCommandAbbreviateVerkey }
+                    DidCommand::ExpandVerkey(_, _, _) => { CommandMetric::DidCommandExpandVerkey }
+                    DidCommand::DidFromVerkey(_, _) => { CommandMetric::DidCommandDidFromVerkey }
+                    DidCommand::ComputeDidFingerprint(_, _) => { CommandMetric::DidCommandComputeDidFingerprint }
+                    DidCommand::ValidateDidString(_, _) => { CommandMetric::DidCommandValidateDidString }
+                    DidCommand::VerkeyToCurve25519(_, _) => { CommandMetric::DidCommandVerkeyToCurve25519 }
+                    DidCommand::SignkeyToCurve25519(_, _, _) => { CommandMetric::DidCommandSignkeyToCurve25519 }
+                    DidCommand::ForgetDid(_, _, _) => { CommandMetric::DidCommandForgetDid }
+                    DidCommand::ExportDidKey(_, _, _, _) => { CommandMetric::DidCommandExportDidKey }
+                    DidCommand::ImportDidKey(_, _, _, _, _) => { CommandMetric::DidCommandImportDidKey }
+                    DidCommand::CheckDidIntegrity(_, _, _) => { CommandMetric::DidCommandCheckDidIntegrity }
+                    DidCommand::VerifyMulti(_, _, _, _, _) => { CommandMetric::DidCommandVerifyMulti }
+                    DidCommand::GenerateMnemonicSeed(_) => { CommandMetric::DidCommandGenerateMnemonicSeed }
+                    DidCommand::SeedFromMnemonic(_, _) => { CommandMetric::DidCommandSeedFromMnemonic }
                     DidCommand::GetNymAck(_, _, _, _) => { CommandMetric::DidCommandGetNymAck }
-                    DidCommand::GetAttribAck(_, _, _) => { CommandMetric::DidCommandGetAttribAck }
+                    DidCommand::GetAttribAck(_, _, _, _) => { CommandMetric::DidCommandGetAttribAck }
+                    DidCommand::RotateAndPublishAck(_, _) => { CommandMetric::DidCommandRotateAndPublishAck }
+                    DidCommand::KeyForDidDetailedAck(_, _, _) => { CommandMetric::DidCommandKeyForDidDetailedAck }
+                    DidCommand::SetBackoffPolicy(_, _) => { CommandMetric::DidCommandSetBackoffPolicy }
+                    DidCommand::SetVerkeyCacheTtl(_) => { CommandMetric::DidCommandSetVerkeyCacheTtl }
                     DidCommand::QualifyDid(_, _, _, _) => { CommandMetric::DidCommandQualifyDid }
                 }
             }
@@ -373,13 +409,24 @@ pub enum CommandMetric {
     CryptoCommandSetKeyMetadata,
     CryptoCommandGetKeyMetadata,
     CryptoCommandCryptoSign,
+    CryptoCommandCryptoSignMultiple,
+    CryptoCommandCryptoMultiSign,
     CryptoCommandCryptoVerify,
+    CryptoCommandCryptoVerifyDetailed,
+    CryptoCommandCryptoVerifyAnyKey,
+    CryptoCommandCryptoSignJson,
+    CryptoCommandCryptoVerifyJson,
+    CryptoCommandGetNonce,
+    CryptoCommandSelfTest,
+    CryptoCommandGetCryptoTypesSupported,
     CryptoCommandAuthenticatedEncrypt,
     CryptoCommandAuthenticatedDecrypt,
     CryptoCommandAnonymousEncrypt,
     CryptoCommandAnonymousDecrypt,
+    CryptoCommandDeriveSharedSecret,
     CryptoCommandPackMessage,
     CryptoCommandUnpackMessage,
+    CryptoCommandSetStrictMessages,
     LedgerCommandSignAndSubmitRequest,
     // LedgerCommand
     LedgerCommandSubmitRequest,
@@ -446,18 +493,43 @@ pub enum CommandMetric {
     DidCommandCreateAndStoreMyDid,
     DidCommandReplaceKeysStart,
     DidCommandReplaceKeysApply,
+    DidCommandRotateAndPublish,
     DidCommandStoreTheirDid,
+    DidCommandStoreTheirDids,
     DidCommandGetMyDidWithMeta,
     DidCommandListMyDidsWithMeta,
+    DidCommandListTheirDids,
     DidCommandKeyForDid,
+    DidCommandKeyForDidDetailed,
     DidCommandKeyForLocalDid,
     DidCommandSetEndpointForDid,
     DidCommandGetEndpointForDid,
+    DidCommandRekeyEndpoint,
+    DidCommandPrepareAnoncryptedConnectionRequest,
+    DidCommandParseConnectionRequest,
+    DidCommandReEncrypt,
     DidCommandSetDidMetadata,
     DidCommandGetDidMetadata,
     DidCommandAbbreviateVerkey,
+    DidCommandExpandVerkey,
+    DidCommandDidFromVerkey,
+    DidCommandComputeDidFingerprint,
+    DidCommandValidateDidString,
+    DidCommandVerkeyToCurve25519,
+    DidCommandSignkeyToCurve25519,
+    DidCommandForgetDid,
+    DidCommandExportDidKey,
+    DidCommandImportDidKey,
+    DidCommandCheckDidIntegrity,
+    DidCommandVerifyMulti,
+    DidCommandGenerateMnemonicSeed,
+    DidCommandSeedFromMnemonic,
     DidCommandGetNymAck,
     DidCommandGetAttribAck,
+    DidCommandRotateAndPublishAck,
+    DidCommandKeyForDidDetailedAck,
+    DidCommandSetBackoffPolicy,
+    DidCommandSetVerkeyCacheTtl,
     DidCommandQualifyDid,
     // WalletCommand
     WalletCommandRegisterWalletType,
@@ -0,0 +1,76 @@
+use indy_api_types::errors::prelude::*;
+use rust_base58::{FromBase58, ToBase58};
+
+const ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+pub fn to_b58(doc: &[u8]) -> String {
+    doc.to_base58()
+}
+
+pub fn from_b58(doc: &str) -> IndyResult<Vec<u8>> {
+    if doc.is_empty() {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Empty base58 sequence"));
+    }
+
+    if let Some((position, character)) = doc.char_indices().find(|&(_, character)| !ALPHABET.contains(character)) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Invalid base58 sequence: character {:?} at position {} is not part of the base58 alphabet", character, position)));
+    }
+
+    Ok(doc.from_base58()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_b58_works() {
+        assert_eq!("Ldp", to_b58(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn from_b58_works() {
+        assert_eq!(vec![1, 2, 3], from_b58("Ldp").unwrap());
+    }
+
+    #[test]
+    fn round_trip_works() {
+        let doc = [7u8, 255, 0, 128, 64, 33, 1, 200];
+        assert_eq!(doc.to_vec(), from_b58(&to_b58(&doc)).unwrap());
+    }
+
+    #[test]
+    fn round_trip_works_for_leading_zeros() {
+        let doc = [0u8, 0, 0, 1, 2, 3];
+
+        let encoded = to_b58(&doc);
+        assert!(encoded.starts_with("111"));
+        assert_eq!(doc.to_vec(), from_b58(&encoded).unwrap());
+    }
+
+    #[test]
+    fn from_b58_works_for_only_zeros() {
+        assert_eq!(vec![0u8, 0], from_b58("11").unwrap());
+    }
+
+    #[test]
+    fn from_b58_not_works_for_excluded_characters() {
+        for doc in &["Ld0p", "LdOp", "LdIp", "Ldlp"] {
+            let res = from_b58(doc);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
+    #[test]
+    fn from_b58_not_works_for_non_ascii() {
+        let res = from_b58("Ldpé");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn from_b58_not_works_for_empty_string() {
+        let res = from_b58("");
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+}
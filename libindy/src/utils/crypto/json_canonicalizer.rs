@@ -0,0 +1,81 @@
+use indy_api_types::errors::prelude::*;
+use serde_json::Value;
+
+pub fn canonicalize_json(json: &str) -> Result<String, IndyError> {
+    let value: Value = serde_json::from_str(json)
+        .to_indy(IndyErrorKind::InvalidStructure, "Can't canonicalize JSON: invalid json")?;
+
+    Ok(canonicalize(&value))
+}
+
+// Object keys are sorted explicitly so the output doesn't depend on the map implementation
+// serde_json was built with.
+pub fn canonicalize(value: &Value) -> String {
+    match value {
+        Value::Array(array) => {
+            let elements: Vec<String> = array.iter().map(canonicalize).collect();
+            format!("[{}]", elements.join(","))
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+
+            let members: Vec<String> = keys
+                .into_iter()
+                .map(|key| format!("{}:{}", Value::String(key.clone()), canonicalize(&map[key])))
+                .collect();
+
+            format!("{{{}}}", members.join(","))
+        }
+        value => value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonicalize_works_for_sorting_keys() {
+        assert_eq!(r#"{"a":1,"b":2}"#, canonicalize_json(r#"{"b": 2, "a": 1}"#).unwrap());
+    }
+
+    #[test]
+    fn canonicalize_works_for_nested_objects_and_arrays() {
+        let json = r#"{
+            "z": [3, {"y": true, "x": null}, "s"],
+            "m": {"d": {"b": 1.5, "a": -1}, "c": []}
+        }"#;
+
+        assert_eq!(r#"{"m":{"c":[],"d":{"a":-1,"b":1.5}},"z":[3,{"x":null,"y":true},"s"]}"#,
+                   canonicalize_json(json).unwrap());
+    }
+
+    #[test]
+    fn canonicalize_works_for_escaped_strings() {
+        assert_eq!(r#"{"k\"ey":"line\nbreak"}"#, canonicalize_json(r#"{ "k\"ey" : "line\nbreak" }"#).unwrap());
+    }
+
+    #[test]
+    fn canonicalize_works_for_equal_objects_with_different_formatting() {
+        let json_1 = r#"{"name": "Alice", "attrs": {"age": 28, "height": 175}, "tags": ["a", "b"]}"#;
+        let json_2 = r#"{
+                          "tags" : [ "a","b" ],
+                          "attrs":{"height":175,"age":28},
+                          "name":"Alice"
+                        }"#;
+
+        assert_eq!(canonicalize_json(json_1).unwrap(), canonicalize_json(json_2).unwrap());
+    }
+
+    #[test]
+    fn canonicalize_keeps_array_order() {
+        assert_ne!(canonicalize_json("[1,2]").unwrap(), canonicalize_json("[2,1]").unwrap());
+    }
+
+    #[test]
+    fn canonicalize_json_not_works_for_invalid_json() {
+        let res = canonicalize_json(r#"{"a":"#);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+}
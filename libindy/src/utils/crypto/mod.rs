@@ -1,2 +1,4 @@
+pub mod base58;
+pub mod json_canonicalizer;
 pub mod verkey_builder;
 pub mod signature_serializer;
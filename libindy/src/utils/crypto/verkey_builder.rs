@@ -1,6 +1,10 @@
 use indy_api_types::errors::prelude::*;
-use rust_base58::{FromBase58, ToBase58};
+use crate::utils::crypto::base58;
 use crate::services::crypto::DEFAULT_CRYPTO_TYPE;
+use sha2::{Digest, Sha256};
+
+// Number of leading SHA-256 bytes kept in a verkey fingerprint.
+const FINGERPRINT_BYTES: usize = 8;
 
 
 pub fn build_full_verkey(dest: &str, verkey: Option<&str>) -> Result<String, IndyError> {
@@ -13,10 +17,19 @@ pub fn build_full_verkey(dest: &str, verkey: Option<&str>) -> Result<String, Ind
         };
 
         let verkey = if verkey.starts_with('~') {
-            let mut result = dest.from_base58()?;
-            let mut end = verkey[1..].from_base58()?;
+            let mut result = base58::from_b58(dest)?;
+            let mut end = base58::from_b58(&verkey[1..])?;
+
+            // An abbreviated verkey is only meaningful for a DID made of the first 16 bytes
+            // of the full verkey, so both halves must be exactly 16 bytes long.
+            if result.len() != 16 || end.len() != 16 {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Abbreviated verkey doesn't match DID {}: expanded verkey would be {} bytes long instead of 32",
+                                           dest, result.len() + end.len())));
+            }
+
             result.append(&mut end );
-            result.to_base58()
+            base58::to_b58(&result)
         } else {
             verkey.to_owned()
         };
@@ -34,6 +47,51 @@ pub fn build_full_verkey(dest: &str, verkey: Option<&str>) -> Result<String, Ind
     }
 }
 
+pub fn abbreviate_verkey(dest: &str, verkey: &str) -> Result<String, IndyError> {
+    let did = base58::from_b58(dest)?;
+    let dverkey = base58::from_b58(verkey)?;
+
+    if dverkey.len() == 32 && dverkey[..16] == did[..] {
+        Ok(format!("~{}", base58::to_b58(&dverkey[16..])))
+    } else {
+        Ok(verkey.to_owned())
+    }
+}
+
+pub fn did_from_verkey(verkey: &str) -> Result<String, IndyError> {
+    let (verkey, _) = split_verkey(verkey);
+
+    if verkey.starts_with('~') {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Can't derive DID from abbreviated verkey"));
+    }
+
+    let verkey = base58::from_b58(verkey)?;
+
+    if verkey.len() != 32 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Trying to use verkey with unexpected length: {}", verkey.len())));
+    }
+
+    Ok(base58::to_b58(&verkey[..16]))
+}
+
+pub fn fingerprint(verkey: &str) -> Result<String, IndyError> {
+    let (verkey, _) = split_verkey(verkey);
+
+    if verkey.starts_with('~') {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Can't compute fingerprint of abbreviated verkey"));
+    }
+
+    let verkey = base58::from_b58(verkey)?;
+
+    if verkey.len() != 32 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Trying to use verkey with unexpected length: {}", verkey.len())));
+    }
+
+    let hash = Sha256::digest(&verkey);
+
+    Ok(hex::encode(&hash[..FINGERPRINT_BYTES]))
+}
+
 pub fn split_verkey(verkey: &str) -> (&str, &str) {
     let position = verkey.find(':');
     match position {
@@ -72,6 +130,117 @@ pub fn verkey_get_cryptoname(verkey: &str) -> &str {
 mod tests {
     use super::*;
 
+    const DID: &str = "8wZcEriaNLNKtteJvx7f8i";
+    const FULL_VERKEY: &str = "5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp";
+    const ABBR_VERKEY: &str = "~NcYxiDXkpYi6ov5FcYDi1e";
+
+    # [test]
+    fn abbreviate_verkey_works() {
+        assert_eq!(abbreviate_verkey(DID, FULL_VERKEY).unwrap(), ABBR_VERKEY)
+    }
+
+    # [test]
+    fn abbreviate_verkey_works_for_not_matching_did() {
+        let verkey = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+        assert_eq!(abbreviate_verkey(DID, verkey).unwrap(), verkey)
+    }
+
+    # [test]
+    fn abbreviate_verkey_works_for_invalid_verkey() {
+        assert!(abbreviate_verkey(DID, "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFV0").is_err())
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_abbreviated_verkey() {
+        assert_eq!(build_full_verkey(DID, Some(ABBR_VERKEY)).unwrap(), FULL_VERKEY)
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_full_verkey() {
+        assert_eq!(build_full_verkey(DID, Some(FULL_VERKEY)).unwrap(), FULL_VERKEY)
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_full_verkey_with_crypto_type() {
+        let verkey = format!("{}:{}", FULL_VERKEY, DEFAULT_CRYPTO_TYPE);
+        assert_eq!(build_full_verkey(DID, Some(&verkey)).unwrap(), verkey)
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_invalid_abbreviated_verkey() {
+        assert!(build_full_verkey(DID, Some("~NcYxiDXkpYi6ov5FcYDi10")).is_err())
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_abbreviated_verkey_with_crypto_type() {
+        let verkey = format!("{}:{}", ABBR_VERKEY, DEFAULT_CRYPTO_TYPE);
+        assert_eq!(build_full_verkey(DID, Some(&verkey)).unwrap(), format!("{}:{}", FULL_VERKEY, DEFAULT_CRYPTO_TYPE))
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_abbreviated_verkey_and_long_did() {
+        let res = build_full_verkey(FULL_VERKEY, Some(ABBR_VERKEY));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_abbreviated_verkey_and_short_did() {
+        // 8 bytes of DID plus 24 bytes of suffix add up to 32, but don't form a valid DID/verkey pair
+        let did = base58::to_b58(&[1; 8]);
+        let abbr_verkey = format!("~{}", base58::to_b58(&[2; 24]));
+
+        let res = build_full_verkey(&did, Some(&abbr_verkey));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    # [test]
+    fn build_full_verkey_works_for_too_long_abbreviated_verkey() {
+        let abbr_verkey = format!("~{}", base58::to_b58(&[2; 17]));
+
+        let res = build_full_verkey(DID, Some(&abbr_verkey));
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    # [test]
+    fn did_from_verkey_works() {
+        assert_eq!(did_from_verkey(FULL_VERKEY).unwrap(), DID)
+    }
+
+    # [test]
+    fn did_from_verkey_works_for_crypto_type_suffix() {
+        assert_eq!(did_from_verkey(&format!("{}:{}", FULL_VERKEY, DEFAULT_CRYPTO_TYPE)).unwrap(), DID)
+    }
+
+    # [test]
+    fn did_from_verkey_works_for_abbreviated_verkey() {
+        assert!(did_from_verkey(ABBR_VERKEY).is_err())
+    }
+
+    # [test]
+    fn did_from_verkey_works_for_short_verkey() {
+        assert!(did_from_verkey(DID).is_err())
+    }
+
+    # [test]
+    fn fingerprint_works() {
+        assert_eq!(fingerprint(FULL_VERKEY).unwrap(), "ebcbf6fd8aca2916")
+    }
+
+    # [test]
+    fn fingerprint_works_for_crypto_type_suffix() {
+        assert_eq!(fingerprint(&format!("{}:{}", FULL_VERKEY, DEFAULT_CRYPTO_TYPE)).unwrap(), "ebcbf6fd8aca2916")
+    }
+
+    # [test]
+    fn fingerprint_works_for_abbreviated_verkey() {
+        assert_kind!(IndyErrorKind::InvalidStructure, fingerprint(ABBR_VERKEY))
+    }
+
+    # [test]
+    fn fingerprint_works_for_short_verkey() {
+        assert!(fingerprint(DID).is_err())
+    }
+
     # [test]
     fn split_verkey_empty() {
         assert_eq!(split_verkey(""), ("", DEFAULT_CRYPTO_TYPE))
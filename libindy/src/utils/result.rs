@@ -44,6 +44,31 @@ macro_rules! prepare_result_3 {
     }}
 }
 
+// For results carrying plaintexts, seeds or private keys that must not reach the log
+macro_rules! prepare_secret_result_1 {
+    ($result:ident, $default_value:expr) => {{
+        trace!("prepare_secret_result_1: >>> {:?}", secret!(&$result));
+        match $result {
+            Ok(res) => (ErrorCode::Success, res),
+            Err(err) => {
+                (err.into(), $default_value)
+            }
+        }
+    }}
+}
+
+macro_rules! prepare_secret_result_2 {
+    ($result:ident, $default_value1:expr, $default_value2:expr) => {{
+        trace!("prepare_secret_result_2: >>> {:?}", secret!(&$result));
+        match $result {
+            Ok((res1, res2)) => (ErrorCode::Success, res1, res2),
+            Err(err) => {
+                (err.into(), $default_value1, $default_value2)
+            }
+        }
+    }}
+}
+
 macro_rules! prepare_result_4 {
     ($result:ident, $default_value1:expr, $default_value2:expr, $default_value3:expr, $default_value4:expr) => {{
         trace!("prepare_result_4: >>> {:?}", $result);
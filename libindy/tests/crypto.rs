@@ -20,6 +20,7 @@ mod high_cases {
 
     mod create_key {
         use super::*;
+        use crate::utils::{did, wallet};
         use rust_base58::FromBase58;
 
         #[test]
@@ -29,6 +30,23 @@ mod high_cases {
             assert_eq!(verkey.from_base58().unwrap().len(), 32);
         }
 
+        #[test]
+        fn indy_create_key_works_for_same_seed_in_other_wallet() {
+            let setup = Setup::wallet();
+            let verkey = crypto::create_key(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            assert_eq!(VERKEY_MY1, verkey);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet("indy_create_key_works_for_same_seed_in_other_wallet").unwrap();
+            let other_verkey = crypto::create_key(wallet_handle, Some(MY1_SEED)).unwrap();
+            assert_eq!(verkey, other_verkey);
+
+            // keys are stored by verkey only, no DID is created for them
+            let dids = did::list_my_dids_with_meta(wallet_handle).unwrap();
+            assert_eq!("[]", dids);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
+
         #[test]
         fn indy_create_key_works_without_seed() {
             let setup = Setup::wallet();
@@ -81,6 +99,14 @@ mod high_cases {
             let res = crypto::get_key_metadata(setup.wallet_handle, &setup.verkey);
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
+
+        #[test]
+        fn indy_get_key_metadata_works_for_standalone_key() {
+            let setup = Setup::key();
+
+            crypto::set_key_metadata(setup.wallet_handle, &setup.verkey, METADATA).unwrap();
+            assert_eq!(METADATA, crypto::get_key_metadata(setup.wallet_handle, &setup.verkey).unwrap());
+        }
     }
 
     mod crypto_sign {
@@ -96,6 +122,28 @@ mod high_cases {
             assert_eq!(SIGNATURE.to_vec(), signature);
         }
 
+        #[test]
+        fn indy_crypto_sign_works_for_empty_message() {
+            let setup = Setup::key();
+
+            let signature = crypto::sign(setup.wallet_handle, &setup.verkey, &[]).unwrap();
+
+            assert!(crypto::verify(&setup.verkey, &[], &signature).unwrap());
+            assert!(!crypto::verify(&setup.verkey, MESSAGE.as_bytes(), &signature).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_sign_works_for_did_key() {
+            let setup = Setup::wallet();
+
+            let (my_did, _) = crate::utils::did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            let my_vk = crate::utils::did::key_for_local_did(setup.wallet_handle, &my_did).unwrap();
+
+            let signature = crypto::sign(setup.wallet_handle, &my_vk, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
+            assert!(crypto::verify(&my_vk, MESSAGE.as_bytes(), &signature).unwrap());
+        }
+
         #[test]
         fn indy_crypto_sign_works_for_unknown_signer() {
             let setup = Setup::wallet();
@@ -104,6 +152,63 @@ mod high_cases {
         }
     }
 
+    mod crypto_sign_multiple {
+        use super::*;
+        use rust_base58::FromBase58;
+
+        #[test]
+        fn indy_crypto_sign_multiple_works() {
+            let setup = Setup::key();
+
+            // base64 of "message1", "message2", "message3"
+            let msgs_json = r#"["bWVzc2FnZTE=", "bWVzc2FnZTI=", "bWVzc2FnZTM="]"#;
+
+            let signatures_json = crypto::sign_multiple(setup.wallet_handle, &setup.verkey, msgs_json).unwrap();
+            let signatures: Vec<serde_json::Value> = serde_json::from_str(&signatures_json).unwrap();
+            assert_eq!(3, signatures.len());
+
+            for (msg, signature) in ["message1", "message2", "message3"].iter().zip(signatures.iter()) {
+                let signature = signature["ok"].as_str().unwrap().from_base58().unwrap();
+                assert_eq!(crypto::sign(setup.wallet_handle, &setup.verkey, msg.as_bytes()).unwrap(), signature);
+                assert!(crypto::verify(&setup.verkey, msg.as_bytes(), &signature).unwrap());
+            }
+        }
+
+        #[test]
+        fn indy_crypto_sign_multiple_works_for_empty_list() {
+            let setup = Setup::key();
+
+            let signatures_json = crypto::sign_multiple(setup.wallet_handle, &setup.verkey, "[]").unwrap();
+            assert_eq!("[]", signatures_json);
+        }
+    }
+
+    mod crypto_multi_sign {
+        use super::*;
+        use rust_base58::FromBase58;
+        use std::collections::HashMap;
+
+        #[test]
+        fn indy_crypto_multi_sign_works() {
+            let setup = Setup::wallet();
+
+            let my_vk_1 = crypto::create_key(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            let my_vk_2 = crypto::create_key(setup.wallet_handle, Some(MY2_SEED)).unwrap();
+
+            let signatures_json = crypto::multi_sign(setup.wallet_handle, &json!([my_vk_1, my_vk_2]).to_string(), MESSAGE.as_bytes()).unwrap();
+            let signatures: HashMap<String, String> = serde_json::from_str(&signatures_json).unwrap();
+            assert_eq!(2, signatures.len());
+
+            let signature_1 = signatures[&my_vk_1].from_base58().unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature_1);
+            assert!(crypto::verify(&my_vk_1, MESSAGE.as_bytes(), &signature_1).unwrap());
+
+            let signature_2 = signatures[&my_vk_2].from_base58().unwrap();
+            assert!(crypto::verify(&my_vk_2, MESSAGE.as_bytes(), &signature_2).unwrap());
+            assert!(!crypto::verify(&my_vk_1, MESSAGE.as_bytes(), &signature_2).unwrap());
+        }
+    }
+
     mod crypto_verify {
         use super::*;
 
@@ -113,6 +218,17 @@ mod high_cases {
             assert!(valid);
         }
 
+        #[test]
+        fn indy_crypto_verify_works_for_key_resolved_from_their_did() {
+            let setup = Setup::wallet();
+
+            crate::utils::did::store_their_did_from_parts(setup.wallet_handle, DID_MY1, VERKEY_MY1).unwrap();
+            let their_vk = crate::utils::did::key_for_did(-1, setup.wallet_handle, DID_MY1).unwrap();
+
+            assert!(crypto::verify(&their_vk, MESSAGE.as_bytes(), SIGNATURE).unwrap());
+            assert!(!crypto::verify(&their_vk, b"tampered message", SIGNATURE).unwrap());
+        }
+
         #[test]
         fn indy_crypto_verify_works_for_other_signer() {
             let valid = crypto::verify(&VERKEY_MY2, MESSAGE.as_bytes(), SIGNATURE).unwrap();
@@ -127,6 +243,155 @@ mod high_cases {
         }
     }
 
+    mod crypto_verify_detailed {
+        use super::*;
+
+        fn verify_detailed(their_vk: &str, msg: &[u8], signature: &[u8]) -> serde_json::Value {
+            let res = crypto::verify_detailed(their_vk, msg, signature).unwrap();
+            serde_json::from_str(&res).unwrap()
+        }
+
+        #[test]
+        fn indy_crypto_verify_detailed_works() {
+            let res = verify_detailed(&VERKEY_MY1, MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(json!({"verified": true, "reason": null}), res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_detailed_works_for_signature_mismatch() {
+            let res = verify_detailed(&VERKEY_MY2, MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(json!({"verified": false, "reason": "signature mismatch"}), res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_detailed_works_for_malformed_signature() {
+            let res = verify_detailed(&VERKEY_MY1, MESSAGE.as_bytes(), &SIGNATURE[..20]);
+            assert_eq!(json!({"verified": false, "reason": "malformed signature"}), res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_detailed_works_for_key_not_resolved() {
+            let res = verify_detailed(INVALID_BASE58_VERKEY, MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(json!({"verified": false, "reason": "key not resolved"}), res);
+
+            let res = verify_detailed("~NcYxiDXkpYi6ov5FcYDi1e", MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(json!({"verified": false, "reason": "key not resolved"}), res);
+        }
+    }
+
+    mod crypto_verify_any_key {
+        use super::*;
+
+        fn verify_any_key(their_vks: serde_json::Value, msg: &[u8], signature: &[u8]) -> serde_json::Value {
+            let res = crypto::verify_any_key(&their_vks.to_string(), msg, signature).unwrap();
+            serde_json::from_str(&res).unwrap()
+        }
+
+        #[test]
+        fn indy_crypto_verify_any_key_works_for_match_on_first() {
+            let res = verify_any_key(json!([VERKEY_MY1, VERKEY_MY2]), MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(json!({"verified": true, "verkey": VERKEY_MY1}), res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_any_key_works_for_match_on_second() {
+            let res = verify_any_key(json!([VERKEY_MY2, VERKEY_MY1]), MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(json!({"verified": true, "verkey": VERKEY_MY1}), res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_any_key_works_for_no_match() {
+            let res = verify_any_key(json!([VERKEY_MY2, VERKEY_TRUSTEE]), MESSAGE.as_bytes(), SIGNATURE);
+            assert_eq!(json!({"verified": false, "verkey": null}), res);
+        }
+    }
+
+    mod crypto_sign_json {
+        use super::*;
+
+        const MESSAGE_JSON: &str = r#"{"name": "Alice", "attrs": {"age": 28, "height": 175}, "tags": ["a", "b"]}"#;
+
+        #[test]
+        fn indy_crypto_sign_json_works_for_differently_formatted_equal_json() {
+            let setup = Setup::key();
+
+            let signature = crypto::sign_json(setup.wallet_handle, &setup.verkey, MESSAGE_JSON).unwrap();
+
+            let reformatted_json = r#"{
+                "tags" : [ "a","b" ],
+                "attrs":{"height":175,"age":28},
+                "name":"Alice"
+            }"#;
+            assert!(crypto::verify_json(&setup.verkey, reformatted_json, &signature).unwrap());
+            assert_eq!(signature, crypto::sign_json(setup.wallet_handle, &setup.verkey, reformatted_json).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_verify_json_works_for_changed_json() {
+            let setup = Setup::key();
+
+            let signature = crypto::sign_json(setup.wallet_handle, &setup.verkey, MESSAGE_JSON).unwrap();
+
+            let changed_json = r#"{"name": "Alice", "attrs": {"age": 29, "height": 175}, "tags": ["a", "b"]}"#;
+            assert!(!crypto::verify_json(&setup.verkey, changed_json, &signature).unwrap());
+
+            let reordered_array_json = r#"{"name": "Alice", "attrs": {"age": 28, "height": 175}, "tags": ["b", "a"]}"#;
+            assert!(!crypto::verify_json(&setup.verkey, reordered_array_json, &signature).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_sign_json_works_for_invalid_json() {
+            let setup = Setup::key();
+
+            let res = crypto::sign_json(setup.wallet_handle, &setup.verkey, r#"{"name":"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod get_nonce {
+        use super::*;
+        use rust_base58::FromBase58;
+
+        #[test]
+        fn indy_crypto_get_nonce_works() {
+            let nonce_1 = crypto::get_nonce().unwrap();
+            let nonce_2 = crypto::get_nonce().unwrap();
+
+            assert_ne!(nonce_1, nonce_2);
+            assert_eq!(24, nonce_1.from_base58().unwrap().len());
+            assert_eq!(24, nonce_2.from_base58().unwrap().len());
+        }
+    }
+
+    mod self_test {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_self_test_works() {
+            Setup::empty();
+
+            let report = crypto::self_test().unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(json!({"seed_derivation": true, "sign_verify": true, "box_open": true}), report);
+        }
+    }
+
+    mod get_supported_types {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_get_supported_types_works() {
+            Setup::empty();
+
+            let crypto_types = crypto::get_supported_types().unwrap();
+            let crypto_types: Vec<String> = serde_json::from_str(&crypto_types).unwrap();
+
+            assert!(crypto_types.contains(&"ed25519".to_string()));
+            assert!(!crypto_types.contains(&"secp256k1".to_string()));
+        }
+    }
+
     mod auth_crypt {
         use super::*;
 
@@ -211,6 +476,48 @@ mod high_cases {
         }
     }
 
+    mod derive_shared_secret {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_derive_shared_secret_works_in_both_directions() {
+            let setup = Setup::key();
+
+            let their_vk = crypto::create_key(setup.wallet_handle, Some(MY2_SEED)).unwrap();
+
+            let my_secret = crypto::derive_shared_secret(setup.wallet_handle, &setup.verkey, &their_vk).unwrap();
+            let their_secret = crypto::derive_shared_secret(setup.wallet_handle, &their_vk, &setup.verkey).unwrap();
+
+            assert_eq!(my_secret, their_secret);
+        }
+
+        #[test]
+        fn indy_crypto_derive_shared_secret_works_for_different_pairs() {
+            let setup = Setup::key();
+
+            let my_secret = crypto::derive_shared_secret(setup.wallet_handle, &setup.verkey, VERKEY_MY2).unwrap();
+            let other_secret = crypto::derive_shared_secret(setup.wallet_handle, &setup.verkey, VERKEY_TRUSTEE).unwrap();
+
+            assert_ne!(my_secret, other_secret);
+        }
+
+        #[test]
+        fn indy_crypto_derive_shared_secret_works_for_unknown_my_vk() {
+            let setup = Setup::wallet();
+
+            let res = crypto::derive_shared_secret(setup.wallet_handle, VERKEY_MY1, VERKEY_MY2);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_crypto_derive_shared_secret_works_for_invalid_their_vk() {
+            let setup = Setup::key();
+
+            let res = crypto::derive_shared_secret(setup.wallet_handle, &setup.verkey, INVALID_BASE58_VERKEY);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod pack_message_authcrypt {
         use super::*;
 
@@ -269,6 +576,24 @@ mod high_cases {
             assert_eq!(res_serialized.recipient_verkey, receiver_setup.verkey);
         }
 
+        #[test]
+        fn indy_unpack_message_authcrypt_works_for_single_recipient() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_keys = json!([receiver_setup.verkey]).to_string();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, Some(&sender_setup.verkey)).unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap();
+            let res_serialized: UnpackMessage = serde_json::from_slice(res.as_slice()).unwrap();
+
+            assert_eq!(res_serialized, UnpackMessage {
+                message: AGENT_MESSAGE.to_string(),
+                sender_verkey: sender_setup.verkey.clone(),
+                recipient_verkey: receiver_setup.verkey.clone(),
+            });
+        }
+
         #[test]
         fn indy_unpack_message_authcrypt_fails_no_matching_key() {
             //Test Setup
@@ -310,6 +635,24 @@ mod high_cases {
             assert_eq!(res_serialized.recipient_verkey, receiver_setup.verkey);
         }
 
+        #[test]
+        fn indy_unpack_message_anoncrypt_works_for_each_of_multiple_recipients() {
+            let sender_setup = Setup::wallet();
+            let receiver_setups = vec![Setup::key(), Setup::key(), Setup::key()];
+
+            let receiver_keys: Vec<&str> = receiver_setups.iter().map(|setup| setup.verkey.as_str()).collect();
+            let receiver_keys = serde_json::to_string(&receiver_keys).unwrap();
+            let pack_message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, None).unwrap();
+
+            for receiver_setup in receiver_setups.iter() {
+                let res = crypto::unpack_message(receiver_setup.wallet_handle, pack_message.as_slice()).unwrap();
+                let res_serialized: UnpackMessage = serde_json::from_slice(res.as_slice()).unwrap();
+
+                assert_eq!(res_serialized.message, AGENT_MESSAGE.to_string());
+                assert_eq!(res_serialized.recipient_verkey, receiver_setup.verkey);
+            }
+        }
+
         #[test]
         fn indy_unpack_message_anoncrypt_fails_no_matching_key() {
             //Test Setup
@@ -368,6 +711,13 @@ mod medium_cases {
             let res = crypto::set_key_metadata(setup.wallet_handle, INVALID_BASE58_VERKEY, METADATA);
             assert_code!(ErrorCode::CommonInvalidStructure, res);
         }
+
+        #[test]
+        fn indy_set_key_metadata_works_for_unknown_key() {
+            let setup = Setup::wallet();
+            let res = crypto::set_key_metadata(setup.wallet_handle, VERKEY_MY1, METADATA);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
     }
 
     mod get_key_metadata {
@@ -392,6 +742,13 @@ mod medium_cases {
             let res = crypto::get_key_metadata(INVALID_WALLET_HANDLE, &setup.verkey);
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_get_key_metadata_works_for_unknown_key() {
+            let setup = Setup::wallet();
+            let res = crypto::get_key_metadata(setup.wallet_handle, VERKEY_MY1);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
     }
 
     mod crypto_sign {
@@ -403,6 +760,63 @@ mod medium_cases {
             let res = crypto::sign(INVALID_WALLET_HANDLE, &setup.verkey, MESSAGE.as_bytes());
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_crypto_sign_works_for_whitespace_message() {
+            let setup = Setup::key();
+
+            let signature = crypto::sign(setup.wallet_handle, &setup.verkey, b"  \n").unwrap();
+            assert!(crypto::verify(&setup.verkey, b"  \n", &signature).unwrap());
+        }
+    }
+
+    mod crypto_sign_multiple {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_sign_multiple_works_for_partially_invalid_messages() {
+            let setup = Setup::key();
+
+            let signatures_json = crypto::sign_multiple(setup.wallet_handle, &setup.verkey, r#"["bWVzc2FnZTE=", "not base64!", "bWVzc2FnZTI="]"#).unwrap();
+            let signatures: Vec<serde_json::Value> = serde_json::from_str(&signatures_json).unwrap();
+            assert_eq!(3, signatures.len());
+
+            assert!(signatures[0]["ok"].is_string());
+            assert_eq!(ErrorCode::CommonInvalidStructure as i64, signatures[1]["err"]["code"].as_i64().unwrap());
+            assert!(signatures[2]["ok"].is_string());
+        }
+
+        #[test]
+        fn indy_crypto_sign_multiple_works_for_invalid_json() {
+            let setup = Setup::key();
+            let res = crypto::sign_multiple(setup.wallet_handle, &setup.verkey, r#"{"msg": "bWVzc2FnZTE="}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_crypto_sign_multiple_works_for_unknown_signer() {
+            let setup = Setup::wallet();
+            let res = crypto::sign_multiple(setup.wallet_handle, VERKEY, r#"["bWVzc2FnZTE="]"#);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod crypto_multi_sign {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_multi_sign_works_for_one_unknown_signer() {
+            let setup = Setup::key();
+            let res = crypto::multi_sign(setup.wallet_handle, &json!([setup.verkey, VERKEY]).to_string(), MESSAGE.as_bytes());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_crypto_multi_sign_works_for_empty_signers() {
+            let setup = Setup::key();
+            let res = crypto::multi_sign(setup.wallet_handle, "[]", MESSAGE.as_bytes());
+            assert_code!(ErrorCode::CommonInvalidParam3, res);
+        }
     }
 
     mod crypto_verify {
@@ -415,12 +829,41 @@ mod medium_cases {
             assert!(valid);
         }
 
+        #[test]
+        fn indy_crypto_verify_works_for_other_signer_with_crypto_type() {
+            let verkey = VERKEY_MY2.to_owned() + ":ed25519";
+            let valid = crypto::verify(&verkey, MESSAGE.as_bytes(), SIGNATURE).unwrap();
+            assert!(!valid);
+        }
+
         #[test]
         fn indy_crypto_verify_works_for_verkey_with_invalid_crypto_type() {
             let verkey = VERKEY_MY1.to_owned() + ":unknown_crypto";
             let res = crypto::verify(&verkey, MESSAGE.as_bytes(), SIGNATURE);
             assert_code!(ErrorCode::UnknownCryptoTypeError, res);
         }
+
+        #[test]
+        fn indy_crypto_verify_works_for_abbreviated_verkey() {
+            let res = crypto::verify("~NcYxiDXkpYi6ov5FcYDi1e", MESSAGE.as_bytes(), SIGNATURE);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod crypto_verify_any_key {
+        use super::*;
+
+        #[test]
+        fn indy_crypto_verify_any_key_works_for_empty_verkeys() {
+            let res = crypto::verify_any_key("[]", MESSAGE.as_bytes(), SIGNATURE);
+            assert_code!(ErrorCode::CommonInvalidParam2, res);
+        }
+
+        #[test]
+        fn indy_crypto_verify_any_key_works_for_invalid_verkey() {
+            let res = crypto::verify_any_key(&json!([VERKEY_MY1, INVALID_BASE58_VERKEY]).to_string(), MESSAGE.as_bytes(), SIGNATURE);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
     }
 
     mod auth_crypt {
@@ -451,6 +894,19 @@ mod medium_cases {
     mod auth_decrypt {
         use super::*;
 
+        #[test]
+        fn indy_crypto_auth_decrypt_works_for_corrupted_msg() {
+            let sender_setup = Setup::key();
+            let recipient_setup = Setup::key();
+
+            let mut encrypted_msg = crypto::auth_crypt(sender_setup.wallet_handle, &sender_setup.verkey, &recipient_setup.verkey, MESSAGE.as_bytes()).unwrap();
+            let last = encrypted_msg.len() - 1;
+            encrypted_msg[last] ^= 0xff;
+
+            let res = crypto::auth_decrypt(recipient_setup.wallet_handle, &recipient_setup.verkey, &encrypted_msg);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn indy_crypto_auth_decrypt_works_for_invalid_msg() {
             let sender_setup = Setup::key();
@@ -6,7 +6,7 @@ inject_indy_dependencies!();
 extern crate indyrs as indy;
 extern crate indyrs as api;
 
-use crate::utils::{did, pool, ledger};
+use crate::utils::{crypto, did, non_secrets, pool, ledger, wallet};
 use crate::utils::constants::*;
 use crate::utils::types::ResponseType;
 use crate::utils::Setup;
@@ -56,6 +56,33 @@ mod high_cases {
             assert_eq!(VERKEY_TRUSTEE.to_string(), received_verkey);
         }
 
+        #[test]
+        fn indy_key_for_did_works_for_caching_key_from_ledger() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::key_for_local_did(setup.wallet_handle, DID_TRUSTEE);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            did::key_for_did(setup.pool_handle, setup.wallet_handle, DID_TRUSTEE).unwrap();
+
+            let received_verkey = did::key_for_local_did(setup.wallet_handle, DID_TRUSTEE).unwrap();
+            assert_eq!(VERKEY_TRUSTEE.to_string(), received_verkey);
+        }
+
+        #[test]
+        fn indy_key_for_did_works_for_their_did_overwritten_after_ledger_resolution() {
+            let setup = Setup::wallet_and_pool();
+
+            let received_verkey = did::key_for_did(setup.pool_handle, setup.wallet_handle, DID_TRUSTEE).unwrap();
+            assert_eq!(VERKEY_TRUSTEE.to_string(), received_verkey);
+
+            let identity_json = json!({"did": DID_TRUSTEE, "verkey": VERKEY, "overwrite": true}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
+
+            let received_verkey = did::key_for_did(setup.pool_handle, setup.wallet_handle, DID_TRUSTEE).unwrap();
+            assert_eq!(VERKEY.to_string(), received_verkey);
+        }
+
         #[test]
         fn indy_key_for_did_works_for_unknown_did() {
             let setup = Setup::wallet_and_pool();
@@ -85,6 +112,61 @@ mod high_cases {
         }
     }
 
+    mod key_for_did_detailed {
+        use super::*;
+
+        #[test]
+        fn indy_key_for_did_detailed_works_for_my_did() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let key_json = did::key_for_did_detailed(-1, setup.wallet_handle, &did).unwrap();
+            let key: serde_json::Value = serde_json::from_str(&key_json).unwrap();
+            assert_eq!(json!({"verkey": verkey, "source": "my"}), key);
+        }
+
+        #[test]
+        fn indy_key_for_did_detailed_works_for_their_did() {
+            let setup = Setup::wallet();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID, VERKEY).unwrap();
+
+            let key_json = did::key_for_did_detailed(-1, setup.wallet_handle, DID).unwrap();
+            let key: serde_json::Value = serde_json::from_str(&key_json).unwrap();
+            assert_eq!(json!({"verkey": VERKEY, "source": "their_cached"}), key);
+        }
+
+        #[test]
+        fn indy_key_for_did_detailed_works_for_get_key_from_ledger() {
+            let setup = Setup::wallet_and_pool();
+
+            let key_json = did::key_for_did_detailed(setup.pool_handle, setup.wallet_handle, DID_TRUSTEE).unwrap();
+            let key: serde_json::Value = serde_json::from_str(&key_json).unwrap();
+            assert_eq!(json!({"verkey": VERKEY_TRUSTEE, "source": "ledger"}), key);
+
+            let key_json = did::key_for_did_detailed(setup.pool_handle, setup.wallet_handle, DID_TRUSTEE).unwrap();
+            let key: serde_json::Value = serde_json::from_str(&key_json).unwrap();
+            assert_eq!(json!({"verkey": VERKEY_TRUSTEE, "source": "their_cached"}), key);
+        }
+
+        #[test]
+        fn indy_key_for_did_detailed_works_for_unknown_did() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = did::key_for_did_detailed(setup.pool_handle, setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_key_for_did_detailed_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::key_for_did_detailed(-1, INVALID_WALLET_HANDLE, DID);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
     mod key_for_local_did {
         use super::*;
 
@@ -197,7 +279,7 @@ mod high_cases {
             let setup = Setup::wallet_and_pool();
 
             let res = did::get_endpoint_for_did(setup.wallet_handle, setup.pool_handle, DID);
-            assert_code!(ErrorCode::CommonInvalidState, res);
+            assert_code!(ErrorCode::LedgerNotFound, res);
         }
 
         #[test]
@@ -217,6 +299,99 @@ mod high_cases {
         }
     }
 
+    mod rekey_endpoint_for_did {
+        use super::*;
+
+        #[test]
+        fn indy_rekey_endpoint_for_did_works() {
+            let setup = Setup::wallet();
+
+            did::set_endpoint_for_did(setup.wallet_handle, DID, ENDPOINT, VERKEY).unwrap();
+            did::rekey_endpoint_for_did(setup.wallet_handle, DID, VERKEY_MY2).unwrap();
+
+            let (endpoint, key) = did::get_endpoint_for_did(setup.wallet_handle, -1, DID).unwrap();
+            assert_eq!(ENDPOINT, endpoint);
+            assert_eq!(VERKEY_MY2, key.unwrap());
+        }
+
+        #[test]
+        fn indy_rekey_endpoint_for_did_works_for_missing_endpoint() {
+            let setup = Setup::wallet();
+
+            let res = did::rekey_endpoint_for_did(setup.wallet_handle, DID, VERKEY_MY2);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod connection_request {
+        use super::*;
+
+        #[test]
+        fn indy_connection_request_works_for_round_trip() {
+            let setup = Setup::did();
+            did::set_endpoint_for_did(setup.wallet_handle, &setup.did, ENDPOINT, VERKEY_MY2).unwrap();
+
+            let (their_wallet_handle, their_wallet_config) = wallet::create_and_open_default_wallet("indy_connection_request_works_for_round_trip").unwrap();
+            let (_, their_verkey) = did::create_and_store_my_did(their_wallet_handle, Some(MY2_SEED)).unwrap();
+
+            let request = did::prepare_anoncrypted_connection_request(setup.wallet_handle, &their_verkey, &setup.did).unwrap();
+            let request_json = did::parse_connection_request(their_wallet_handle, &their_verkey, &request).unwrap();
+
+            let request: serde_json::Value = serde_json::from_str(&request_json).unwrap();
+            assert_eq!(json!({"did": setup.did, "verkey": setup.verkey, "endpoint": {"ha": ENDPOINT, "verkey": VERKEY_MY2}}), request);
+
+            let verkey = did::key_for_local_did(their_wallet_handle, &setup.did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+
+            let (endpoint, transport_key) = did::get_endpoint_for_did(their_wallet_handle, -1, &setup.did).unwrap();
+            assert_eq!(ENDPOINT, endpoint);
+            assert_eq!(VERKEY_MY2, transport_key.unwrap());
+
+            wallet::close_and_delete_wallet(their_wallet_handle, &their_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn indy_connection_request_works_without_endpoint() {
+            let setup = Setup::did();
+
+            let (their_wallet_handle, their_wallet_config) = wallet::create_and_open_default_wallet("indy_connection_request_works_without_endpoint").unwrap();
+            let their_verkey = crypto::create_key(their_wallet_handle, None).unwrap();
+
+            let request = did::prepare_anoncrypted_connection_request(setup.wallet_handle, &their_verkey, &setup.did).unwrap();
+            let request_json = did::parse_connection_request(their_wallet_handle, &their_verkey, &request).unwrap();
+
+            let request: serde_json::Value = serde_json::from_str(&request_json).unwrap();
+            assert_eq!(json!({"did": setup.did, "verkey": setup.verkey, "endpoint": null}), request);
+
+            let res = did::get_endpoint_for_did(their_wallet_handle, -1, &setup.did);
+            assert!(res.is_err());
+
+            wallet::close_and_delete_wallet(their_wallet_handle, &their_wallet_config).unwrap();
+        }
+    }
+
+    mod re_encrypt {
+        use super::*;
+
+        #[test]
+        fn indy_re_encrypt_works_for_round_trip() {
+            let setup = Setup::did();
+
+            let (their_wallet_handle, their_wallet_config) = wallet::create_and_open_default_wallet("indy_re_encrypt_works_for_round_trip").unwrap();
+            let sender_verkey = crypto::create_key(their_wallet_handle, None).unwrap();
+            let recipient_verkey = crypto::create_key(their_wallet_handle, None).unwrap();
+
+            let encrypted_msg = crypto::auth_crypt(their_wallet_handle, &sender_verkey, &setup.verkey, MESSAGE.as_bytes()).unwrap();
+
+            let re_encrypted_msg = did::re_encrypt(setup.wallet_handle, &setup.did, &sender_verkey, &recipient_verkey, &encrypted_msg).unwrap();
+
+            let decrypted_msg = crypto::anon_decrypt(their_wallet_handle, &recipient_verkey, &re_encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted_msg);
+
+            wallet::close_and_delete_wallet(their_wallet_handle, &their_wallet_config).unwrap();
+        }
+    }
+
     mod set_did_metadata {
         use super::*;
 
@@ -317,6 +492,19 @@ mod high_cases {
             assert_eq!(METADATA.to_string(), metadata);
         }
 
+        #[test]
+        fn indy_get_did_metadata_works_after_wallet_reopen() {
+            let mut setup = Setup::did();
+
+            did::set_did_metadata(setup.wallet_handle, &setup.did, METADATA).unwrap();
+
+            wallet::close_wallet(setup.wallet_handle).unwrap();
+            setup.wallet_handle = wallet::open_wallet(&setup.wallet_config, WALLET_CREDENTIALS).unwrap();
+
+            let metadata = did::get_did_metadata(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(METADATA.to_string(), metadata);
+        }
+
         #[test]
         fn indy_get_did_metadata_works_for_no_metadata() {
             let setup = Setup::did();
@@ -342,7 +530,12 @@ mod high_cases {
             let setup = Setup::did();
 
             did::set_did_metadata(setup.wallet_handle, &setup.did, METADATA).unwrap();
-            did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+
+            assert_eq!(setup.did, did_with_meta["did"].as_str().unwrap());
+            assert_eq!(setup.verkey, did_with_meta["verkey"].as_str().unwrap());
+            assert_eq!(METADATA, did_with_meta["metadata"].as_str().unwrap());
         }
 
         #[test]
@@ -356,7 +549,11 @@ mod high_cases {
         #[test]
         fn indy_get_my_did_metadata_works_for_no_metadata() {
             let setup = Setup::did();
-            did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+
+            assert_eq!(setup.verkey, did_with_meta["verkey"].as_str().unwrap());
+            assert!(did_with_meta["metadata"].is_null());
         }
 
         #[test]
@@ -375,12 +572,51 @@ mod high_cases {
             let res = did::get_my_did_with_metadata(setup.wallet_handle, DID);
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
+
+        #[test]
+        fn indy_get_my_did_metadata_works_for_their_did() {
+            let setup = Setup::wallet();
+
+            let identity_json = json!({"did": DID_TRUSTEE, "verkey": VERKEY_TRUSTEE}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
+
+            let res = did::get_my_did_with_metadata(setup.wallet_handle, DID_TRUSTEE);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
     }
 
     mod create_my_did {
         use super::*;
         use rust_base58::FromBase58;
 
+        #[test]
+        fn indy_create_my_did_works_for_calls_from_multiple_threads() {
+            const THREADS_CNT: usize = 8;
+            const DIDS_PER_THREAD: usize = 5;
+
+            let setup = Setup::wallet();
+            let wallet_handle = setup.wallet_handle;
+
+            // commands from all threads go through the single command executor queue
+            let threads: Vec<_> = (0..THREADS_CNT)
+                .map(|_| std::thread::spawn(move || {
+                    (0..DIDS_PER_THREAD)
+                        .map(|_| did::create_my_did(wallet_handle, "{}").unwrap())
+                        .collect::<Vec<(String, String)>>()
+                }))
+                .collect();
+
+            let mut dids = Vec::new();
+            for thread in threads {
+                dids.extend(thread.join().unwrap());
+            }
+            assert_eq!(THREADS_CNT * DIDS_PER_THREAD, dids.len());
+
+            for (my_did, my_verkey) in dids {
+                assert_eq!(my_verkey, did::key_for_local_did(wallet_handle, &my_did).unwrap());
+            }
+        }
+
         #[test]
         fn indy_create_my_did_works_for_empty_json() {
             let setup = Setup::wallet();
@@ -434,6 +670,38 @@ mod high_cases {
             assert_eq!(my_verkey, VERKEY_MY1);
         }
 
+        #[test]
+        fn indy_create_my_did_works_for_low_entropy_seed_without_strict_seed() {
+            let setup = Setup::wallet();
+
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &json!({"seed": MY1_SEED, "strict_seed": false}).to_string()).unwrap();
+            assert_eq!(my_did, DID_MY1);
+            assert_eq!(my_verkey, VERKEY_MY1);
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_strict_seed() {
+            let setup = Setup::wallet();
+
+            did::create_my_did(setup.wallet_handle, r#"{"seed":"Ab3dEf6hIj9kLm2nOp5qRs8tUv1wXy4z", "strict_seed":true}"#).unwrap();
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_stored_did_and_key() {
+            let setup = Setup::wallet();
+
+            let (my_did, my_verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            assert_eq!(my_did.from_base58().unwrap(), my_verkey.from_base58().unwrap()[..16].to_vec());
+
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &my_did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+            assert_eq!(my_did, did_with_meta["did"].as_str().unwrap());
+            assert_eq!(my_verkey, did_with_meta["verkey"].as_str().unwrap());
+
+            let signature = crypto::sign(setup.wallet_handle, &my_verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(crypto::verify(&my_verkey, MESSAGE.as_bytes(), &signature).unwrap());
+        }
+
         #[test]
         fn indy_create_my_did_works_with_hex_seed() {
             let setup = Setup::wallet();
@@ -486,6 +754,21 @@ mod high_cases {
             assert_eq!(new_verkey, VERKEY);
             assert_ne!(setup.verkey, new_verkey);
         }
+
+        #[test]
+        fn indy_replace_keys_start_works_without_apply() {
+            let setup = Setup::did();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+            assert_eq!(setup.verkey, did_with_meta["verkey"].as_str().unwrap());
+            assert_eq!(new_verkey, did_with_meta["tempVerkey"].as_str().unwrap());
+        }
     }
 
     mod replace_keys_apply {
@@ -500,6 +783,12 @@ mod high_cases {
             assert_ne!(new_verkey, setup.verkey);
 
             did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(new_verkey, verkey);
+
+            let res = did::replace_keys_apply(setup.wallet_handle, &setup.did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
         }
 
         #[test]
@@ -513,6 +802,27 @@ mod high_cases {
             did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
         }
 
+        #[test]
+        fn indy_replace_keys_apply_works_for_metadata_and_endpoint() {
+            let setup = Setup::did();
+
+            did::set_did_metadata(setup.wallet_handle, &setup.did, METADATA).unwrap();
+            did::set_endpoint_for_did(setup.wallet_handle, &setup.did, ENDPOINT, VERKEY).unwrap();
+
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+            assert_eq!(new_verkey, did_with_meta["verkey"].as_str().unwrap());
+            assert_eq!(METADATA, did_with_meta["metadata"].as_str().unwrap());
+            assert!(did_with_meta["tempVerkey"].is_null());
+
+            let (endpoint, key) = did::get_endpoint_for_did(setup.wallet_handle, -1, &setup.did).unwrap();
+            assert_eq!(ENDPOINT, endpoint);
+            assert_eq!(VERKEY, key.unwrap());
+        }
+
         #[test]
         fn indy_replace_keys_apply_works_without_calling_replace_start() {
             let setup = Setup::did();
@@ -578,88 +888,749 @@ mod high_cases {
 
             let identity_json = json!({"did": DID, "verkey": VERKEY}).to_string();
             did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
-
-            let identity_json = json!({"did": DID, "verkey": VERKEY_TRUSTEE}).to_string();
             did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
 
             let verkey = did::key_for_local_did(setup.wallet_handle, DID).unwrap();
-            assert_eq!(VERKEY_TRUSTEE, verkey);
+            assert_eq!(VERKEY, verkey);
         }
-    }
-
-    mod replace_keys {
-        use super::*;
 
         #[test]
-        fn indy_replace_keys_demo() {
-            // 1. Create and open pool
-            // 2. Create and open wallet
-            // 3. Generate did from Trustee seed
-            // 4. Generate my did
-            // 5. Send Nym request to Ledger
-            let setup = Setup::new_identity();
-
-            // 6. Start replacing of keys
-            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
-
-            // 7. Send Nym request to Ledger with new verkey
-            let nym_request = ledger::build_nym_request(&setup.did, &setup.did, Some(&new_verkey), None, None).unwrap();
-            ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &nym_request).unwrap();
+        fn indy_store_their_did_works_for_verkey_after_did_only() {
+            let setup = Setup::wallet();
 
-            // 8. Send Schema request before apply replacing of keys
-            let schema_request = ledger::build_schema_request(&setup.did, SCHEMA_DATA).unwrap();
-            let response = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &schema_request).unwrap();
-            pool::check_response_type(&response, ResponseType::REQNACK);
+            let identity_json = json!({"did": DID}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
 
-            // 9. Apply replacing of keys
-            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+            let identity_json = json!({"did": DID, "verkey": VERKEY}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
 
-            // 10. Send Schema request
-            ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &schema_request).unwrap();
+            let verkey = did::key_for_local_did(setup.wallet_handle, DID).unwrap();
+            assert_eq!(VERKEY, verkey);
         }
-    }
-
-    mod abbreviate_verkey {
-        use super::*;
 
         #[test]
-        fn indy_abbreviate_verkey_works_for_abbr_key() {
-            let setup = Setup::did();
+        fn indy_store_their_did_works_for_did_only_after_verkey() {
+            let setup = Setup::wallet();
 
-            let abbr_verkey = did::abbreviate_verkey(&setup.did, &setup.verkey).unwrap();
-            assert_ne!(setup.verkey, abbr_verkey);
-        }
+            let identity_json = json!({"did": DID, "verkey": VERKEY}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
 
-        #[test]
-        fn indy_abbreviate_verkey_works_for_abbr_key_for_fully_qualified_did() {
-            let setup = Setup::did_fully_qualified();
+            let identity_json = json!({"did": DID}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
 
-            let abbr_verkey = did::abbreviate_verkey(&setup.did, &setup.verkey).unwrap();
-            assert_ne!(setup.verkey, abbr_verkey);
+            let verkey = did::key_for_local_did(setup.wallet_handle, DID).unwrap();
+            assert_eq!(VERKEY, verkey);
         }
 
         #[test]
-        fn indy_abbreviate_verkey_works_for_not_abbr_key() {
+        fn indy_store_their_did_works_for_overwrite() {
             let setup = Setup::wallet();
 
-            let (did, verkey) = did::create_my_did(setup.wallet_handle, &format!(r#"{{"did":{:?}}}"#, DID_TRUSTEE)).unwrap();
+            let identity_json = json!({"did": DID, "verkey": VERKEY}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
 
-            let full_verkey = did::abbreviate_verkey(&did, &verkey).unwrap();
+            let identity_json = json!({"did": DID, "verkey": VERKEY_TRUSTEE, "overwrite": true}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
 
-            assert_eq!(verkey, full_verkey);
+            let verkey = did::key_for_local_did(setup.wallet_handle, DID).unwrap();
+            assert_eq!(VERKEY_TRUSTEE, verkey);
         }
     }
 
-    mod qualify_did {
+    mod store_their_dids {
         use super::*;
 
-        const CUSTOM_METHOD: &str = "peer";
-
         #[test]
-        fn qualify_did_for_appending_prefix() {
-            let setup = Setup::new_identity();
+        fn indy_store_their_dids_works() {
+            let setup = Setup::wallet();
 
-            let full_qualified_did = did::qualify_did(setup.wallet_handle, &setup.did, DEFAULT_METHOD_NAME).unwrap();
+            let identities_json = json!([
+                {"did": DID, "verkey": VERKEY},
+                {"did": DID_TRUSTEE, "verkey": VERKEY_TRUSTEE},
+            ]).to_string();
+
+            let results = did::store_their_dids(setup.wallet_handle, &identities_json).unwrap();
+            let results: serde_json::Value = serde_json::from_str(&results).unwrap();
+            assert_eq!(json!([{"ok": DID}, {"ok": DID_TRUSTEE}]), results);
+
+            assert_eq!(VERKEY, did::key_for_local_did(setup.wallet_handle, DID).unwrap());
+            assert_eq!(VERKEY_TRUSTEE, did::key_for_local_did(setup.wallet_handle, DID_TRUSTEE).unwrap());
+        }
+
+        #[test]
+        fn indy_store_their_dids_works_for_partially_invalid_entries() {
+            let setup = Setup::wallet();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID_MY1, VERKEY_MY1).unwrap();
+
+            let identities_json = json!([
+                {"did": DID, "verkey": VERKEY},
+                {"did": INVALID_BASE58_DID},
+                {"verkey": VERKEY_TRUSTEE},
+                {"did": DID_MY1, "verkey": VERKEY_MY2},
+                {"did": DID_TRUSTEE, "verkey": VERKEY_TRUSTEE},
+            ]).to_string();
+
+            let results = did::store_their_dids(setup.wallet_handle, &identities_json).unwrap();
+            let results: Vec<serde_json::Value> = serde_json::from_str(&results).unwrap();
+            assert_eq!(5, results.len());
+
+            assert_eq!(json!({"ok": DID}), results[0]);
+            assert_eq!(ErrorCode::CommonInvalidStructure as i64, results[1]["err"]["code"].as_i64().unwrap());
+            assert_eq!(ErrorCode::CommonInvalidStructure as i64, results[2]["err"]["code"].as_i64().unwrap());
+            assert_eq!(ErrorCode::CommonInvalidState as i64, results[3]["err"]["code"].as_i64().unwrap());
+            assert!(results[3]["err"]["message"].is_string());
+            assert_eq!(json!({"ok": DID_TRUSTEE}), results[4]);
+
+            assert_eq!(VERKEY, did::key_for_local_did(setup.wallet_handle, DID).unwrap());
+            assert_eq!(VERKEY_MY1, did::key_for_local_did(setup.wallet_handle, DID_MY1).unwrap());
+            assert_eq!(VERKEY_TRUSTEE, did::key_for_local_did(setup.wallet_handle, DID_TRUSTEE).unwrap());
+        }
+
+        #[test]
+        fn indy_store_their_dids_works_for_empty_list() {
+            let setup = Setup::wallet();
+
+            let results = did::store_their_dids(setup.wallet_handle, "[]").unwrap();
+            assert_eq!("[]", results);
+        }
+    }
+
+    mod list_their_dids {
+        use super::*;
+
+        #[test]
+        fn indy_list_their_dids_works() {
+            let setup = Setup::wallet();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID_MY1, VERKEY_MY1).unwrap();
+            did::store_their_did_from_parts(setup.wallet_handle, DID_MY2, VERKEY_MY2).unwrap();
+            did::set_endpoint_for_did(setup.wallet_handle, DID_MY1, ENDPOINT, VERKEY).unwrap();
+
+            let dids = did::list_their_dids(setup.wallet_handle, None).unwrap();
+            let dids: serde_json::Value = serde_json::from_str(&dids).unwrap();
+            let dids = dids.as_array().unwrap();
+
+            assert_eq!(2, dids.len());
+
+            assert_eq!(DID_MY2, dids[0]["did"].as_str().unwrap());
+            assert_eq!(VERKEY_MY2, dids[0]["verkey"].as_str().unwrap());
+            assert!(dids[0]["endpoint"].is_null());
+
+            assert_eq!(DID_MY1, dids[1]["did"].as_str().unwrap());
+            assert_eq!(VERKEY_MY1, dids[1]["verkey"].as_str().unwrap());
+            assert_eq!(ENDPOINT, dids[1]["endpoint"]["ha"].as_str().unwrap());
+            assert_eq!(VERKEY, dids[1]["endpoint"]["verkey"].as_str().unwrap());
+        }
+
+        #[test]
+        fn indy_list_their_dids_works_for_has_endpoint_filter() {
+            let setup = Setup::wallet();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID_MY1, VERKEY_MY1).unwrap();
+            did::store_their_did_from_parts(setup.wallet_handle, DID_MY2, VERKEY_MY2).unwrap();
+            did::set_endpoint_for_did(setup.wallet_handle, DID_MY1, ENDPOINT, VERKEY).unwrap();
+
+            let dids = did::list_their_dids(setup.wallet_handle, Some(r#"{"has_endpoint": true}"#)).unwrap();
+            let dids: serde_json::Value = serde_json::from_str(&dids).unwrap();
+            let dids = dids.as_array().unwrap();
+            assert_eq!(1, dids.len());
+            assert_eq!(DID_MY1, dids[0]["did"].as_str().unwrap());
+
+            let dids = did::list_their_dids(setup.wallet_handle, Some(r#"{"has_endpoint": false}"#)).unwrap();
+            let dids: serde_json::Value = serde_json::from_str(&dids).unwrap();
+            let dids = dids.as_array().unwrap();
+            assert_eq!(1, dids.len());
+            assert_eq!(DID_MY2, dids[0]["did"].as_str().unwrap());
+        }
+
+        #[test]
+        fn indy_list_their_dids_works_for_empty_wallet() {
+            let setup = Setup::wallet();
+
+            let dids = did::list_their_dids(setup.wallet_handle, None).unwrap();
+            assert_eq!("[]", dids);
+        }
+    }
+
+    mod replace_keys {
+        use super::*;
+
+        #[test]
+        fn indy_replace_keys_demo() {
+            // 1. Create and open pool
+            // 2. Create and open wallet
+            // 3. Generate did from Trustee seed
+            // 4. Generate my did
+            // 5. Send Nym request to Ledger
+            let setup = Setup::new_identity();
+
+            // 6. Start replacing of keys
+            let new_verkey = did::replace_keys_start(setup.wallet_handle, &setup.did, "{}").unwrap();
+
+            // 7. Send Nym request to Ledger with new verkey
+            let nym_request = ledger::build_nym_request(&setup.did, &setup.did, Some(&new_verkey), None, None).unwrap();
+            ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &nym_request).unwrap();
+
+            // 8. Send Schema request before apply replacing of keys
+            let schema_request = ledger::build_schema_request(&setup.did, SCHEMA_DATA).unwrap();
+            let response = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &schema_request).unwrap();
+            pool::check_response_type(&response, ResponseType::REQNACK);
+
+            // 9. Apply replacing of keys
+            did::replace_keys_apply(setup.wallet_handle, &setup.did).unwrap();
+
+            // 10. Send Schema request
+            ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &schema_request).unwrap();
+        }
+    }
+
+    mod rotate_and_publish_key {
+        use super::*;
+
+        #[test]
+        fn indy_rotate_and_publish_key_works() {
+            let setup = Setup::new_identity();
+
+            let new_verkey = did::rotate_and_publish_key(setup.pool_handle, setup.wallet_handle, &setup.did, &setup.did).unwrap();
+            assert_ne!(setup.verkey, new_verkey);
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap();
+            assert_eq!(new_verkey, verkey);
+
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &setup.did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+            assert!(did_with_meta["tempVerkey"].is_null());
+
+            let schema_request = ledger::build_schema_request(&setup.did, SCHEMA_DATA).unwrap();
+            let response = ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &schema_request).unwrap();
+            pool::check_response_type(&response, ResponseType::REPLY);
+        }
+
+        #[test]
+        fn indy_rotate_and_publish_key_keeps_key_pending_for_rejected_nym() {
+            let setup = Setup::new_identity();
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+
+            let res = did::rotate_and_publish_key(setup.pool_handle, setup.wallet_handle, &setup.did, &did);
+            assert_code!(ErrorCode::LedgerInvalidTransaction, res);
+
+            assert_eq!(verkey, did::key_for_local_did(setup.wallet_handle, &did).unwrap());
+
+            let did_with_meta = did::get_my_did_with_metadata(setup.wallet_handle, &did).unwrap();
+            let did_with_meta: serde_json::Value = serde_json::from_str(&did_with_meta).unwrap();
+            assert!(did_with_meta["tempVerkey"].is_string());
+        }
+
+        #[test]
+        fn indy_rotate_and_publish_key_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::rotate_and_publish_key(INVALID_POOL_HANDLE, setup.wallet_handle, DID_TRUSTEE, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod abbreviate_verkey {
+        use super::*;
+
+        #[test]
+        fn indy_abbreviate_verkey_works_for_abbr_key() {
+            let setup = Setup::did();
+
+            let abbr_verkey = did::abbreviate_verkey(&setup.did, &setup.verkey).unwrap();
+            assert_ne!(setup.verkey, abbr_verkey);
+        }
+
+        #[test]
+        fn indy_abbreviate_verkey_works_for_abbr_key_for_fully_qualified_did() {
+            let setup = Setup::did_fully_qualified();
+
+            let abbr_verkey = did::abbreviate_verkey(&setup.did, &setup.verkey).unwrap();
+            assert_ne!(setup.verkey, abbr_verkey);
+        }
+
+        #[test]
+        fn indy_abbreviate_verkey_works_for_not_abbr_key() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_my_did(setup.wallet_handle, &format!(r#"{{"did":{:?}}}"#, DID_TRUSTEE)).unwrap();
+
+            let full_verkey = did::abbreviate_verkey(&did, &verkey).unwrap();
+
+            assert_eq!(verkey, full_verkey);
+        }
+    }
+
+    mod expand_verkey {
+        use super::*;
+
+        #[test]
+        fn indy_expand_verkey_works_for_abbr_key() {
+            let setup = Setup::did();
+
+            let abbr_verkey = did::abbreviate_verkey(&setup.did, &setup.verkey).unwrap();
+
+            let full_verkey = did::expand_verkey(&setup.did, &abbr_verkey).unwrap();
+            assert_eq!(setup.verkey, full_verkey);
+        }
+
+        #[test]
+        fn indy_expand_verkey_works_for_abbr_key_for_fully_qualified_did() {
+            let setup = Setup::did_fully_qualified();
+
+            let abbr_verkey = did::abbreviate_verkey(&setup.did, &setup.verkey).unwrap();
+
+            let full_verkey = did::expand_verkey(&setup.did, &abbr_verkey).unwrap();
+            assert_eq!(setup.verkey, full_verkey);
+        }
+
+        #[test]
+        fn indy_expand_verkey_works_for_full_key() {
+            let full_verkey = did::expand_verkey(DID_TRUSTEE, VERKEY_TRUSTEE).unwrap();
+            assert_eq!(VERKEY_TRUSTEE, full_verkey);
+        }
+    }
+
+    mod did_from_verkey {
+        use super::*;
+
+        #[test]
+        fn indy_did_from_verkey_works() {
+            let did = did::did_from_verkey(VERKEY_MY1).unwrap();
+            assert_eq!(DID_MY1, did);
+        }
+    }
+
+    mod compute_did_fingerprint {
+        use super::*;
+
+        #[test]
+        fn indy_compute_did_fingerprint_works() {
+            let fingerprint = did::compute_did_fingerprint(VERKEY_MY1).unwrap();
+            assert_eq!("4f0740107cccb149", fingerprint);
+
+            let fingerprint = did::compute_did_fingerprint(&format!("{}:ed25519", VERKEY_MY1)).unwrap();
+            assert_eq!("4f0740107cccb149", fingerprint);
+        }
+    }
+
+    mod validate_did_string {
+        use super::*;
+
+        fn validate(did: &str) -> serde_json::Value {
+            let res = did::validate_did_string(did).unwrap();
+            serde_json::from_str(&res).unwrap()
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_short_did() {
+            assert_eq!(json!({"valid": true, "kind": "short"}), validate(DID_MY1));
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_full_did() {
+            assert_eq!(json!({"valid": true, "kind": "full"}), validate(DID));
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_qualified_did() {
+            assert_eq!(json!({"valid": true, "kind": "qualified"}), validate(DID_MY1_V1));
+            assert_eq!(json!({"valid": true, "kind": "qualified"}), validate(DID_V1));
+        }
+    }
+
+    mod verkey_to_curve25519 {
+        use super::*;
+
+        #[test]
+        fn indy_verkey_to_curve25519_works() {
+            let curve25519_key = did::verkey_to_curve25519(VERKEY_MY1).unwrap();
+            assert_eq!("EoxFfhuHD7NkiYnn9CyvB1dJoLVN1257X9fAtxGJdTwL", curve25519_key);
+        }
+    }
+
+    mod signkey_to_curve25519 {
+        use super::*;
+
+        #[test]
+        fn indy_signkey_to_curve25519_works() {
+            let setup = Setup::wallet();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let curve25519_key = did::signkey_to_curve25519(setup.wallet_handle, &did).unwrap();
+            assert_eq!("ADFHwoDNJwnh7eiv1itsmh884e36XLwVPSVPTCFdfcHc", curve25519_key);
+        }
+    }
+
+    mod forget_did {
+        use super::*;
+
+        #[test]
+        fn indy_forget_did_works() {
+            let setup = Setup::did();
+
+            did::forget_did(setup.wallet_handle, &setup.did).unwrap();
+
+            let res = did::get_my_did_with_metadata(setup.wallet_handle, &setup.did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_forget_did_works_for_metadata_and_endpoint() {
+            let setup = Setup::did();
+
+            did::set_did_metadata(setup.wallet_handle, &setup.did, METADATA).unwrap();
+            did::set_endpoint_for_did(setup.wallet_handle, &setup.did, ENDPOINT, VERKEY).unwrap();
+
+            did::forget_did(setup.wallet_handle, &setup.did).unwrap();
+
+            let res = did::get_did_metadata(setup.wallet_handle, &setup.did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            // Without the local endpoint the lookup falls back to the (missing) pool.
+            assert!(did::get_endpoint_for_did(setup.wallet_handle, -1, &setup.did).is_err());
+        }
+
+        #[test]
+        fn indy_forget_did_works_for_key_kept() {
+            let setup = Setup::did();
+
+            did::forget_did(setup.wallet_handle, &setup.did).unwrap();
+
+            crypto::sign(setup.wallet_handle, &setup.verkey, MESSAGE.as_bytes()).unwrap();
+        }
+
+        #[test]
+        fn indy_forget_did_works_for_did_created_again() {
+            let setup = Setup::wallet();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            did::forget_did(setup.wallet_handle, &did).unwrap();
+
+            did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            did::get_my_did_with_metadata(setup.wallet_handle, &did).unwrap();
+        }
+    }
+
+    mod export_did_key {
+        use super::*;
+
+        use indy_utils::crypto::base64;
+
+        pub const PASSPHRASE: &str = "backup passphrase";
+
+        #[test]
+        fn indy_export_import_did_key_works() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let exported = did::export_did_key(setup.wallet_handle, &did, PASSPHRASE).unwrap();
+
+            // neither signkey seed nor any readable part of the DID key is in the output
+            let exported_bytes = base64::decode(&exported).unwrap();
+            assert!(!exported_bytes.windows(MY1_SEED.len()).any(|window| window == MY1_SEED.as_bytes()));
+            assert!(!exported_bytes.windows(verkey.len()).any(|window| window == verkey.as_bytes()));
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet("indy_export_import_did_key_works").unwrap();
+
+            let imported_did = did::import_did_key(wallet_handle, PASSPHRASE, &exported, None).unwrap();
+            assert_eq!(did, imported_did);
+
+            let imported_verkey = did::key_for_local_did(wallet_handle, &imported_did).unwrap();
+            assert_eq!(verkey, imported_verkey);
+
+            let signature = crypto::sign(wallet_handle, &imported_verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(crypto::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
+
+        #[test]
+        fn indy_import_did_key_works_for_wrong_passphrase() {
+            let setup = Setup::did();
+
+            let exported = did::export_did_key(setup.wallet_handle, &setup.did, PASSPHRASE).unwrap();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet("indy_import_did_key_works_for_wrong_passphrase").unwrap();
+
+            let res = did::import_did_key(wallet_handle, "other passphrase", &exported, None);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            let res = did::key_for_local_did(wallet_handle, &setup.did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
+
+        #[test]
+        fn indy_import_did_key_works_for_fail_policy_and_existing_did() {
+            let setup = Setup::did();
+
+            let exported = did::export_did_key(setup.wallet_handle, &setup.did, PASSPHRASE).unwrap();
+
+            let res = did::import_did_key(setup.wallet_handle, PASSPHRASE, &exported, Some("fail"));
+            assert_code!(ErrorCode::DidAlreadyExistsError, res);
+
+            let res = did::import_did_key(setup.wallet_handle, PASSPHRASE, &exported, None);
+            assert_code!(ErrorCode::DidAlreadyExistsError, res);
+        }
+
+        #[test]
+        fn indy_import_did_key_works_for_overwrite_policy() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            let exported = did::export_did_key(setup.wallet_handle, &did, PASSPHRASE).unwrap();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet("indy_import_did_key_works_for_overwrite_policy").unwrap();
+
+            // same DID bound to another key in the target wallet
+            let my_did_json = json!({"did": did, "seed": MY2_SEED}).to_string();
+            let (_, other_verkey) = did::create_my_did(wallet_handle, &my_did_json).unwrap();
+            assert_ne!(verkey, other_verkey);
+
+            did::import_did_key(wallet_handle, PASSPHRASE, &exported, Some("overwrite")).unwrap();
+
+            let imported_verkey = did::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(verkey, imported_verkey);
+
+            // the replaced key is gone
+            let res = crypto::sign(wallet_handle, &other_verkey, MESSAGE.as_bytes());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
+
+        #[test]
+        fn indy_import_did_key_works_for_skip_policy() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            let exported = did::export_did_key(setup.wallet_handle, &did, PASSPHRASE).unwrap();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet("indy_import_did_key_works_for_skip_policy").unwrap();
+
+            let my_did_json = json!({"did": did, "seed": MY2_SEED}).to_string();
+            let (_, other_verkey) = did::create_my_did(wallet_handle, &my_did_json).unwrap();
+
+            let imported_did = did::import_did_key(wallet_handle, PASSPHRASE, &exported, Some("skip")).unwrap();
+            assert_eq!(did, imported_did);
+
+            let stored_verkey = did::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(other_verkey, stored_verkey);
+            assert_ne!(verkey, stored_verkey);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
+    }
+
+    mod check_did_integrity {
+        use super::*;
+
+        #[test]
+        fn indy_check_did_integrity_works() {
+            let setup = Setup::did();
+
+            let report = did::check_did_integrity(setup.wallet_handle, &setup.did).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(json!({"did": setup.did, "verkey_ok": true, "did_ok": true}), report);
+        }
+
+        #[test]
+        fn indy_check_did_integrity_works_for_corrupted_verkey() {
+            let setup = Setup::did();
+
+            // point the DID record to a verkey it was not derived from and has no signkey for
+            let corrupted = json!({"did": setup.did, "verkey": VERKEY_MY2}).to_string();
+            non_secrets::update_wallet_record_value(setup.wallet_handle, "Indy::Did", &setup.did, &corrupted).unwrap();
+
+            let report = did::check_did_integrity(setup.wallet_handle, &setup.did).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(json!({"did": setup.did, "verkey_ok": false, "did_ok": false}), report);
+        }
+
+        #[test]
+        fn indy_check_did_integrity_works_for_corrupted_signkey() {
+            let setup = Setup::did();
+
+            let (_, other_verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY2_SEED)).unwrap();
+            let other_key = non_secrets::get_wallet_record(setup.wallet_handle, "Indy::Key", &other_verkey, "{}").unwrap();
+            let other_key: serde_json::Value = serde_json::from_str(&other_key).unwrap();
+            let other_key: serde_json::Value = serde_json::from_str(other_key["value"].as_str().unwrap()).unwrap();
+
+            let corrupted = json!({"verkey": setup.verkey, "signkey": other_key["signkey"]}).to_string();
+            non_secrets::update_wallet_record_value(setup.wallet_handle, "Indy::Key", &setup.verkey, &corrupted).unwrap();
+
+            let report = did::check_did_integrity(setup.wallet_handle, &setup.did).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(json!({"did": setup.did, "verkey_ok": false, "did_ok": true}), report);
+        }
+    }
+
+    mod generate_mnemonic_seed {
+        use super::*;
+
+        #[test]
+        fn indy_generate_mnemonic_seed_works() {
+            let setup = Setup::wallet();
+            let restore_setup = Setup::wallet();
+
+            let (mnemonic, seed) = did::generate_mnemonic_seed().unwrap();
+            assert_eq!(24, mnemonic.split_whitespace().count());
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(&seed)).unwrap();
+
+            let restored_seed = did::seed_from_mnemonic(&mnemonic).unwrap();
+            assert_eq!(seed, restored_seed);
+
+            let (restored_did, restored_verkey) = did::create_and_store_my_did(restore_setup.wallet_handle, Some(&restored_seed)).unwrap();
+            assert_eq!(did, restored_did);
+            assert_eq!(verkey, restored_verkey);
+        }
+
+        #[test]
+        fn indy_seed_from_mnemonic_works_for_invalid_checksum() {
+            let mnemonic = vec!["abandon"; 24].join(" ");
+
+            let res = did::seed_from_mnemonic(&mnemonic);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod verify_multi_did_signature {
+        use super::*;
+        use rust_base58::ToBase58;
+        use indy::WalletHandle;
+
+        fn _sign_by_dids(wallet_handle: WalletHandle, seeds: &[&str]) -> HashMap<String, String> {
+            seeds.iter()
+                .map(|seed| {
+                    let (did, verkey) = did::create_and_store_my_did(wallet_handle, Some(seed)).unwrap();
+                    let signature = crypto::sign(wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+                    (did, signature.to_base58())
+                })
+                .collect()
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_all_valid() {
+            let setup = Setup::wallet();
+
+            let signatures = _sign_by_dids(setup.wallet_handle, &[MY1_SEED, MY2_SEED, TRUSTEE_SEED]);
+            let signatures_json = serde_json::to_string(&signatures).unwrap();
+
+            assert!(did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 3).unwrap());
+            assert!(did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 2).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_below_threshold() {
+            let setup = Setup::wallet();
+
+            let mut signatures = _sign_by_dids(setup.wallet_handle, &[MY1_SEED, MY2_SEED]);
+
+            // signature of other message doesn't count
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let signature = crypto::sign(setup.wallet_handle, &verkey, b"other message").unwrap();
+            signatures.insert(did, signature.to_base58());
+
+            let signatures_json = serde_json::to_string(&signatures).unwrap();
+
+            assert!(!did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 3).unwrap());
+            assert!(did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 2).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_unresolvable_did() {
+            let setup = Setup::wallet();
+
+            let mut signatures = _sign_by_dids(setup.wallet_handle, &[MY1_SEED, MY2_SEED]);
+            signatures.insert(DID_TRUSTEE.to_string(), SIGNATURE.to_base58());
+            let signatures_json = serde_json::to_string(&signatures).unwrap();
+
+            assert!(did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 2).unwrap());
+            assert!(!did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 3).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_invalid_did() {
+            let setup = Setup::wallet();
+
+            let mut signatures = _sign_by_dids(setup.wallet_handle, &[MY1_SEED]);
+            signatures.insert(INVALID_BASE58_DID.to_string(), SIGNATURE.to_base58());
+            let signatures_json = serde_json::to_string(&signatures).unwrap();
+
+            assert!(did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 1).unwrap());
+            assert!(!did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 2).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_dids_sharing_verkey() {
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+            did::store_their_did_from_parts(setup.wallet_handle, DID_TRUSTEE, &verkey).unwrap();
+
+            let signature = crypto::sign(setup.wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap().to_base58();
+
+            let mut signatures = HashMap::new();
+            signatures.insert(did, signature.clone());
+            signatures.insert(DID_TRUSTEE.to_string(), signature);
+            let signatures_json = serde_json::to_string(&signatures).unwrap();
+
+            assert!(did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 1).unwrap());
+            assert!(!did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 2).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_malformed_signature() {
+            let setup = Setup::wallet();
+
+            let mut signatures = _sign_by_dids(setup.wallet_handle, &[MY1_SEED]);
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(MY2_SEED)).unwrap();
+            signatures.insert(did, "0OIl".to_string());
+            let signatures_json = serde_json::to_string(&signatures).unwrap();
+
+            assert!(did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 1).unwrap());
+            assert!(!did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 2).unwrap());
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_zero_threshold() {
+            let setup = Setup::wallet();
+
+            let signatures_json = serde_json::to_string(&_sign_by_dids(setup.wallet_handle, &[MY1_SEED])).unwrap();
+
+            let res = did::verify_multi_did_signature(setup.wallet_handle, &signatures_json, MESSAGE.as_bytes(), 0);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_verify_multi_did_signature_works_for_invalid_json() {
+            let setup = Setup::wallet();
+
+            let res = did::verify_multi_did_signature(setup.wallet_handle, r#"["not", "a", "map"]"#, MESSAGE.as_bytes(), 1);
+            assert_code!(ErrorCode::CommonInvalidParam3, res);
+        }
+    }
+
+    mod qualify_did {
+        use super::*;
+
+        const CUSTOM_METHOD: &str = "peer";
+
+        #[test]
+        fn qualify_did_for_appending_prefix() {
+            let setup = Setup::new_identity();
+
+            let full_qualified_did = did::qualify_did(setup.wallet_handle, &setup.did, DEFAULT_METHOD_NAME).unwrap();
             assert_eq!(full_qualified_did, format!("{}{}", DEFAULT_PREFIX, setup.did));
         }
 
@@ -671,6 +1642,29 @@ mod high_cases {
 
             let new_full_qualified_did = did::qualify_did(setup.wallet_handle, &full_qualified_did, CUSTOM_METHOD).unwrap();
             assert_eq!(new_full_qualified_did, format!("did:{}:{}", CUSTOM_METHOD, setup.did));
+
+            let res = did::key_for_local_did(setup.wallet_handle, &full_qualified_did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, &new_full_qualified_did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+        }
+
+        #[test]
+        fn qualify_did_for_same_method() {
+            let setup = Setup::did();
+
+            did::set_did_metadata(setup.wallet_handle, &setup.did, METADATA).unwrap();
+
+            let full_qualified_did = did::qualify_did(setup.wallet_handle, &setup.did, DEFAULT_METHOD_NAME).unwrap();
+            let same_full_qualified_did = did::qualify_did(setup.wallet_handle, &full_qualified_did, DEFAULT_METHOD_NAME).unwrap();
+            assert_eq!(full_qualified_did, same_full_qualified_did);
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, &full_qualified_did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+
+            let metadata = did::get_did_metadata(setup.wallet_handle, &full_qualified_did).unwrap();
+            assert_eq!(METADATA, metadata);
         }
 
         #[test]
@@ -718,7 +1712,7 @@ mod high_cases {
             {
                 // check endpoint
                 let res = did::get_endpoint_for_did(setup.wallet_handle, setup.pool_handle, &setup.did);
-                assert_code!(ErrorCode::CommonInvalidState, res); // TODO: IS is correct code WalletItemNotFound LedgerNotFound?
+                assert_code!(ErrorCode::LedgerNotFound, res);
 
                 let (endpoint, verkey) = did::get_endpoint_for_did(setup.wallet_handle, INVALID_POOL_HANDLE, &full_qualified_did).unwrap();
                 assert_eq!(ENDPOINT.to_string(), endpoint);
@@ -748,6 +1742,52 @@ mod high_cases {
             }
         }
     }
+
+    mod two_wallets_flow {
+        use super::*;
+
+        // Alice and Bob only know each other through stored their DIDs, keys are resolved
+        // locally so no pool is needed.
+        #[test]
+        fn did_sign_verify_and_crypt_works_between_two_wallets() {
+            let alice = Setup::wallet();
+            let bob = Setup::wallet();
+
+            let (alice_did, alice_verkey) = did::create_and_store_my_did(alice.wallet_handle, None).unwrap();
+            let (bob_did, bob_verkey) = did::create_and_store_my_did(bob.wallet_handle, None).unwrap();
+
+            did::store_their_did_from_parts(alice.wallet_handle, &bob_did, &bob_verkey).unwrap();
+            did::store_their_did_from_parts(bob.wallet_handle, &alice_did, &alice_verkey).unwrap();
+
+            let bob_verkey_for_alice = did::key_for_did(INVALID_POOL_HANDLE, alice.wallet_handle, &bob_did).unwrap();
+            let alice_verkey_for_bob = did::key_for_did(INVALID_POOL_HANDLE, bob.wallet_handle, &alice_did).unwrap();
+            assert_eq!(bob_verkey, bob_verkey_for_alice);
+            assert_eq!(alice_verkey, alice_verkey_for_bob);
+
+            // Alice -> Bob
+            let signature = crypto::sign(alice.wallet_handle, &alice_verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(crypto::verify(&alice_verkey_for_bob, MESSAGE.as_bytes(), &signature).unwrap());
+
+            let encrypted = crypto::auth_crypt(alice.wallet_handle, &alice_verkey, &bob_verkey_for_alice, MESSAGE.as_bytes()).unwrap();
+            let (sender_verkey, decrypted) = crypto::auth_decrypt(bob.wallet_handle, &bob_verkey, &encrypted).unwrap();
+            assert_eq!(alice_verkey_for_bob, sender_verkey);
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted);
+
+            // Bob -> Alice
+            let signature = crypto::sign(bob.wallet_handle, &bob_verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(crypto::verify(&bob_verkey_for_alice, MESSAGE.as_bytes(), &signature).unwrap());
+            assert!(!crypto::verify(&alice_verkey_for_bob, MESSAGE.as_bytes(), &signature).unwrap());
+
+            let encrypted = crypto::auth_crypt(bob.wallet_handle, &bob_verkey, &alice_verkey_for_bob, MESSAGE.as_bytes()).unwrap();
+            let (sender_verkey, decrypted) = crypto::auth_decrypt(alice.wallet_handle, &alice_verkey, &encrypted).unwrap();
+            assert_eq!(bob_verkey_for_alice, sender_verkey);
+            assert_eq!(MESSAGE.as_bytes().to_vec(), decrypted);
+
+            // Bob can't open a message addressed to Alice
+            let res = crypto::auth_decrypt(bob.wallet_handle, &bob_verkey, &encrypted);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -804,6 +1844,203 @@ mod medium_cases {
             assert_eq!(new_endpoint, updated_endpoint);
             assert_eq!(VERKEY_MY2, updated_key.unwrap());
         }
+
+        #[test]
+        fn indy_set_endpoint_for_did_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::set_endpoint_for_did(INVALID_WALLET_HANDLE, DID, ENDPOINT, VERKEY);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod rekey_endpoint_for_did {
+        use super::*;
+
+        #[test]
+        fn indy_rekey_endpoint_for_did_works_for_invalid_transport_key() {
+            let setup = Setup::wallet();
+
+            did::set_endpoint_for_did(setup.wallet_handle, DID, ENDPOINT, VERKEY).unwrap();
+
+            let res = did::rekey_endpoint_for_did(setup.wallet_handle, DID, INVALID_BASE58_VERKEY);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let (_, key) = did::get_endpoint_for_did(setup.wallet_handle, -1, DID).unwrap();
+            assert_eq!(VERKEY, key.unwrap());
+        }
+
+        #[test]
+        fn indy_rekey_endpoint_for_did_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::rekey_endpoint_for_did(INVALID_WALLET_HANDLE, DID, VERKEY);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod connection_request {
+        use super::*;
+
+        #[test]
+        fn indy_prepare_anoncrypted_connection_request_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::prepare_anoncrypted_connection_request(setup.wallet_handle, VERKEY_MY2, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_prepare_anoncrypted_connection_request_works_for_invalid_their_verkey() {
+            let setup = Setup::did();
+
+            let res = did::prepare_anoncrypted_connection_request(setup.wallet_handle, INVALID_BASE58_VERKEY, &setup.did);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_parse_connection_request_works_for_other_key() {
+            let setup = Setup::did();
+            let other_verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
+
+            let request = did::prepare_anoncrypted_connection_request(setup.wallet_handle, VERKEY_MY2, &setup.did).unwrap();
+
+            let res = did::parse_connection_request(setup.wallet_handle, &other_verkey, &request);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_parse_connection_request_works_for_not_a_request() {
+            let setup = Setup::key();
+
+            let message = crypto::anon_crypt(&setup.verkey, b"{\"hello\": \"world\"}").unwrap();
+
+            let res = did::parse_connection_request(setup.wallet_handle, &setup.verkey, &message);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_parse_connection_request_works_for_did_not_derived_from_verkey() {
+            let setup = Setup::key();
+
+            let request = json!({"did": DID_MY2, "verkey": VERKEY_MY1, "endpoint": {"ha": ENDPOINT, "verkey": VERKEY}}).to_string();
+            let request = crypto::anon_crypt(&setup.verkey, request.as_bytes()).unwrap();
+
+            let res = did::parse_connection_request(setup.wallet_handle, &setup.verkey, &request);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = did::key_for_local_did(setup.wallet_handle, DID_MY2);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_parse_connection_request_works_for_known_did() {
+            let setup = Setup::did();
+            did::set_endpoint_for_did(setup.wallet_handle, &setup.did, ENDPOINT, VERKEY_MY2).unwrap();
+
+            let (their_wallet_handle, their_wallet_config) = wallet::create_and_open_default_wallet("indy_parse_connection_request_works_for_known_did").unwrap();
+            let their_verkey = crypto::create_key(their_wallet_handle, None).unwrap();
+
+            let request = did::prepare_anoncrypted_connection_request(setup.wallet_handle, &their_verkey, &setup.did).unwrap();
+            did::parse_connection_request(their_wallet_handle, &their_verkey, &request).unwrap();
+
+            did::set_endpoint_for_did(setup.wallet_handle, &setup.did, "10.0.0.1:9700", VERKEY_MY1).unwrap();
+
+            let request = did::prepare_anoncrypted_connection_request(setup.wallet_handle, &their_verkey, &setup.did).unwrap();
+            did::parse_connection_request(their_wallet_handle, &their_verkey, &request).unwrap();
+
+            let verkey = did::key_for_local_did(their_wallet_handle, &setup.did).unwrap();
+            assert_eq!(setup.verkey, verkey);
+
+            let (endpoint, transport_key) = did::get_endpoint_for_did(their_wallet_handle, -1, &setup.did).unwrap();
+            assert_eq!(ENDPOINT, endpoint);
+            assert_eq!(VERKEY_MY2, transport_key.unwrap());
+
+            wallet::close_and_delete_wallet(their_wallet_handle, &their_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn indy_parse_connection_request_works_for_known_did_with_other_verkey() {
+            let setup = Setup::key();
+            did::store_their_did_from_parts(setup.wallet_handle, DID_MY1, VERKEY_MY2).unwrap();
+
+            let request = json!({"did": DID_MY1, "verkey": VERKEY_MY1, "endpoint": {"ha": ENDPOINT, "verkey": VERKEY}}).to_string();
+            let request = crypto::anon_crypt(&setup.verkey, request.as_bytes()).unwrap();
+
+            let res = did::parse_connection_request(setup.wallet_handle, &setup.verkey, &request);
+            assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, DID_MY1).unwrap();
+            assert_eq!(VERKEY_MY2, verkey);
+
+            let res = did::get_endpoint_for_did(setup.wallet_handle, -1, DID_MY1);
+            assert!(res.is_err());
+        }
+
+        #[test]
+        fn indy_parse_connection_request_works_for_abbreviated_verkey() {
+            let setup = Setup::key();
+
+            let abbr_verkey = did::abbreviate_verkey(DID_MY1, VERKEY_MY1).unwrap();
+            let request = json!({"did": DID_MY1, "verkey": abbr_verkey}).to_string();
+            let request = crypto::anon_crypt(&setup.verkey, request.as_bytes()).unwrap();
+
+            let request_json = did::parse_connection_request(setup.wallet_handle, &setup.verkey, &request).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request_json).unwrap();
+            assert_eq!(abbr_verkey, request["verkey"].as_str().unwrap());
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, DID_MY1).unwrap();
+            assert_eq!(VERKEY_MY1, verkey);
+        }
+
+        #[test]
+        fn indy_parse_connection_request_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::parse_connection_request(INVALID_WALLET_HANDLE, VERKEY, &[1, 2, 3]);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod re_encrypt {
+        use super::*;
+
+        #[test]
+        fn indy_re_encrypt_works_for_other_sender() {
+            let setup = Setup::did();
+            let sender_verkey = crypto::create_key(setup.wallet_handle, None).unwrap();
+
+            let encrypted_msg = crypto::auth_crypt(setup.wallet_handle, &sender_verkey, &setup.verkey, MESSAGE.as_bytes()).unwrap();
+
+            let res = did::re_encrypt(setup.wallet_handle, &setup.did, VERKEY_MY1, VERKEY_MY2, &encrypted_msg);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_re_encrypt_works_for_anoncrypted_message() {
+            let setup = Setup::did();
+
+            let encrypted_msg = crypto::anon_crypt(&setup.verkey, MESSAGE.as_bytes()).unwrap();
+
+            let res = did::re_encrypt(setup.wallet_handle, &setup.did, VERKEY_MY1, VERKEY_MY2, &encrypted_msg);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_re_encrypt_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::re_encrypt(setup.wallet_handle, DID, VERKEY_MY1, VERKEY_MY2, &[1, 2, 3]);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_re_encrypt_works_for_invalid_recipient_verkey() {
+            let setup = Setup::did();
+
+            let res = did::re_encrypt(setup.wallet_handle, &setup.did, VERKEY_MY1, INVALID_BASE58_VERKEY, &[1, 2, 3]);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
     }
 
     mod get_did_metadata {
@@ -848,6 +2085,15 @@ mod medium_cases {
             assert_eq!(my_verkey, VERKEY);
         }
 
+        #[test]
+        fn indy_create_my_did_works_with_passed_did_and_cid() {
+            let setup = Setup::wallet();
+
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &format!(r#"{{"did":"{}","seed":"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa","cid":true}}"#, DID_MY2)).unwrap();
+            assert_eq!(my_did, DID_MY2);
+            assert_eq!(my_verkey, VERKEY);
+        }
+
         #[test]
         fn indy_create_my_did_works_for_exists_crypto_type() {
             let setup = Setup::wallet();
@@ -862,6 +2108,14 @@ mod medium_cases {
             let res = did::create_my_did(INVALID_WALLET_HANDLE, "{}");
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_create_my_did_works_for_invalid_wallet_handle_checked_before_crypto_type() {
+            Setup::empty();
+
+            let res = did::create_my_did(INVALID_WALLET_HANDLE, r#"{"crypto_type":"type"}"#);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
     }
 
     mod replace_keys_start {
@@ -908,6 +2162,21 @@ mod medium_cases {
             did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
         }
 
+        #[test]
+        fn indy_store_their_did_works_for_other_verkey_without_overwrite() {
+            let setup = Setup::wallet();
+
+            let identity_json = json!({"did": DID, "verkey": VERKEY}).to_string();
+            did::store_their_did(setup.wallet_handle, &identity_json).unwrap();
+
+            let identity_json = json!({"did": DID, "verkey": VERKEY_TRUSTEE}).to_string();
+            let res = did::store_their_did(setup.wallet_handle, &identity_json);
+            assert_code!(ErrorCode::CommonInvalidState, res);
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, DID).unwrap();
+            assert_eq!(VERKEY, verkey);
+        }
+
         #[test]
         fn indy_create_my_did_works_for_invalid_seed() {
             let setup = Setup::wallet();
@@ -916,6 +2185,17 @@ mod medium_cases {
             assert_code!(ErrorCode::CommonInvalidStructure, res);
         }
 
+        #[test]
+        fn indy_create_my_did_works_for_low_entropy_seed_and_strict_seed() {
+            let setup = Setup::wallet();
+
+            let res = did::create_my_did(setup.wallet_handle, r#"{"seed":"00000000000000000000000000000000", "strict_seed":true}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = did::create_my_did(setup.wallet_handle, &json!({"seed": MY1_SEED, "strict_seed": true}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn indy_store_their_did_works_for_invalid_wallet_handle() {
             Setup::empty();
@@ -941,6 +2221,36 @@ mod medium_cases {
             did::store_their_did(setup.wallet_handle, identity_json).unwrap();
         }
 
+        #[test]
+        fn indy_store_their_did_works_for_abbreviated_verkey_expanded_to_full_verkey() {
+            let setup = Setup::wallet();
+
+            let identity_json = r#"{"did":"8wZcEriaNLNKtteJvx7f8i", "verkey":"~NcYxiDXkpYi6ov5FcYDi1e"}"#;
+            did::store_their_did(setup.wallet_handle, identity_json).unwrap();
+
+            let verkey = did::key_for_local_did(setup.wallet_handle, "8wZcEriaNLNKtteJvx7f8i").unwrap();
+            assert_eq!("5L2HBnzbu6Auh2pkDRbFt5f4prvgE2LzknkuYLsKkacp", verkey);
+        }
+
+        #[test]
+        fn indy_store_their_did_works_for_abbreviated_verkey_and_mismatched_did() {
+            let setup = Setup::wallet();
+
+            let identity_json = json!({"did": VERKEY_TRUSTEE, "verkey": "~NcYxiDXkpYi6ov5FcYDi1e"}).to_string();
+            let res = did::store_their_did(setup.wallet_handle, &identity_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_store_their_did_works_for_abbreviated_verkey_and_short_did() {
+            let setup = Setup::wallet();
+
+            // 8 bytes of DID and 24 bytes of abbreviated verkey add up to a 32 byte key
+            let identity_json = r#"{"did":"Ajszg3RAw2", "verkey":"~BcktgV7EjHmxEwQDFFhhztzNqZjB7dmj"}"#;
+            let res = did::store_their_did(setup.wallet_handle, identity_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn indy_create_my_did_works_for_invalid_json() {
             let setup = Setup::wallet();
@@ -976,6 +2286,22 @@ mod medium_cases {
             assert_code!(ErrorCode::UnknownCryptoTypeError, res);
         }
 
+        #[test]
+        fn indy_store_their_dids_works_for_not_array() {
+            let setup = Setup::wallet();
+
+            let res = did::store_their_dids(setup.wallet_handle, &json!({"did": DID}).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_store_their_dids_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::store_their_dids(INVALID_WALLET_HANDLE, &json!([{"did": DID}]).to_string());
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
         #[test]
         fn indy_store_my_did_works_for_is_802() {
             let setup = Setup::wallet();
@@ -1034,6 +2360,261 @@ mod medium_cases {
         }
     }
 
+    mod expand_verkey {
+        use super::*;
+
+        #[test]
+        fn indy_expand_verkey_works_for_invalid_did() {
+            let res = did::expand_verkey(INVALID_BASE58_DID, VERKEY_TRUSTEE);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_expand_verkey_works_for_invalid_abbr_verkey() {
+            let res = did::expand_verkey(DID_TRUSTEE, "~NcYxiDXkpYi6ov5FcYDi10");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod did_from_verkey {
+        use super::*;
+
+        #[test]
+        fn indy_did_from_verkey_works_for_abbr_verkey() {
+            let res = did::did_from_verkey("~NcYxiDXkpYi6ov5FcYDi1e");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_did_from_verkey_works_for_invalid_verkey() {
+            let res = did::did_from_verkey(INVALID_BASE58_VERKEY);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod compute_did_fingerprint {
+        use super::*;
+
+        #[test]
+        fn indy_compute_did_fingerprint_works_for_abbr_verkey() {
+            let res = did::compute_did_fingerprint("~NcYxiDXkpYi6ov5FcYDi1e");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_compute_did_fingerprint_works_for_invalid_verkey() {
+            let res = did::compute_did_fingerprint(INVALID_BASE58_VERKEY);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod validate_did_string {
+        use super::*;
+
+        fn assert_invalid(did: &str) {
+            let res = did::validate_did_string(did).unwrap();
+            let res: serde_json::Value = serde_json::from_str(&res).unwrap();
+            assert_eq!(json!({"valid": false, "kind": null}), res, "DID: {}", did);
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_invalid_base58() {
+            assert_invalid(INVALID_BASE58_DID);
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_invalid_length() {
+            assert_invalid(&DID_MY1[..16]);
+            assert_invalid(&format!("{}{}{}", DID_MY1, DID_MY1, DID_MY1));
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_malformed_prefix() {
+            assert_invalid("did:sov:");
+            assert_invalid(&format!("did:SOV:{}", DID_MY1));
+            assert_invalid(&format!("did::{}", DID_MY1));
+            assert_invalid(&format!("sov:{}", DID_MY1));
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_qualified_did_with_invalid_identifier() {
+            assert_invalid(&format!("did:sov:{}", INVALID_BASE58_DID));
+        }
+
+        #[test]
+        fn indy_validate_did_string_works_for_empty_string() {
+            let res = did::validate_did_string("");
+            assert_code!(ErrorCode::CommonInvalidParam2, res);
+        }
+    }
+
+    mod verkey_to_curve25519 {
+        use super::*;
+
+        #[test]
+        fn indy_verkey_to_curve25519_works_for_abbr_verkey() {
+            let res = did::verkey_to_curve25519("~NcYxiDXkpYi6ov5FcYDi1e");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_verkey_to_curve25519_works_for_invalid_verkey() {
+            let res = did::verkey_to_curve25519(INVALID_BASE58_VERKEY);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod signkey_to_curve25519 {
+        use super::*;
+
+        #[test]
+        fn indy_signkey_to_curve25519_works_for_their_did() {
+            let setup = Setup::wallet();
+
+            did::store_their_did_from_parts(setup.wallet_handle, DID, VERKEY).unwrap();
+
+            let res = did::signkey_to_curve25519(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_signkey_to_curve25519_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::signkey_to_curve25519(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_signkey_to_curve25519_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::signkey_to_curve25519(INVALID_WALLET_HANDLE, DID);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod forget_did {
+        use super::*;
+
+        #[test]
+        fn indy_forget_did_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::forget_did(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_forget_did_works_twice() {
+            let setup = Setup::did();
+
+            did::forget_did(setup.wallet_handle, &setup.did).unwrap();
+
+            let res = did::forget_did(setup.wallet_handle, &setup.did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_forget_did_works_for_invalid_did() {
+            let setup = Setup::wallet();
+
+            let res = did::forget_did(setup.wallet_handle, INVALID_BASE58_DID);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_forget_did_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::forget_did(INVALID_WALLET_HANDLE, DID);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_qualify_did_works_for_invalid_wallet_handle() {
+            Setup::empty();
+
+            let res = did::qualify_did(INVALID_WALLET_HANDLE, DID, DEFAULT_METHOD_NAME);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod export_did_key {
+        use super::*;
+
+        #[test]
+        fn indy_export_did_key_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::export_did_key(setup.wallet_handle, DID, "passphrase");
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_import_did_key_works_for_invalid_blob() {
+            let setup = Setup::wallet();
+
+            let res = did::import_did_key(setup.wallet_handle, "passphrase", "not a base64 blob", None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = did::import_did_key(setup.wallet_handle, "passphrase", "AAAA", None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_import_did_key_works_for_unknown_policy() {
+            let setup = Setup::did();
+
+            let exported = did::export_did_key(setup.wallet_handle, &setup.did, "passphrase").unwrap();
+
+            let res = did::import_did_key(setup.wallet_handle, "passphrase", &exported, Some("replace"));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_import_did_key_works_for_invalid_wallet_handle() {
+            let setup = Setup::did();
+
+            let exported = did::export_did_key(setup.wallet_handle, &setup.did, "passphrase").unwrap();
+
+            let res = did::import_did_key(INVALID_WALLET_HANDLE, "passphrase", &exported, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod check_did_integrity {
+        use super::*;
+
+        #[test]
+        fn indy_check_did_integrity_works_for_unknown_did() {
+            let setup = Setup::wallet();
+
+            let res = did::check_did_integrity(setup.wallet_handle, DID);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod list_their_dids {
+        use super::*;
+
+        #[test]
+        fn indy_list_their_dids_works_for_invalid_filter() {
+            let setup = Setup::wallet();
+
+            let res = did::list_their_dids(setup.wallet_handle, Some(r#"{"has_endpoint": "yes"}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_list_their_dids_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = did::list_their_dids(INVALID_WALLET_HANDLE, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
     mod list_my_dids_with_meta{
         use super::*;
 
@@ -1067,6 +2648,35 @@ mod medium_cases {
             }
         }
 
+        #[test]
+        fn indy_list_dids_works_for_sorted_output() {
+            let setup = Setup::wallet();
+
+            let (did_1, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            let (did_2, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            let (did_3, _) = did::create_my_did(setup.wallet_handle, "{}").unwrap();
+            did::set_did_metadata(setup.wallet_handle, &did_2, METADATA).unwrap();
+            did::store_their_did_from_parts(setup.wallet_handle, DID, VERKEY).unwrap();
+
+            let dids = did::list_my_dids_with_meta(setup.wallet_handle).unwrap();
+            let info_list: serde_json::Value = serde_json::from_str(&dids).unwrap();
+            let info_list = info_list.as_array().unwrap();
+
+            let mut expected_dids = vec![did_1, did_2.clone(), did_3];
+            expected_dids.sort();
+
+            let listed_dids: Vec<String> = info_list.iter().map(|info| info["did"].as_str().unwrap().to_string()).collect();
+            assert_eq!(expected_dids, listed_dids);
+
+            for info in info_list {
+                if info["did"].as_str().unwrap() == did_2 {
+                    assert_eq!(METADATA, info["metadata"].as_str().unwrap());
+                } else {
+                    assert!(info["metadata"].is_null());
+                }
+            }
+        }
+
         #[test]
         fn indy_list_dids_after_replace_keys_start(){
             let setup = Setup::wallet();
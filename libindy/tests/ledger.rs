@@ -192,6 +192,27 @@ mod high_cases {
             assert_eq!(request["signature"].as_str().unwrap(), "65hzs4nsdQsTUqLCLy2qisbKLfwYKZSWoyh1C6CU59p5pfG3EHQXGAsjW4Qw4QdwkrvjSgQuyv8qyABcXRBznFKW");
         }
 
+        #[test]
+        fn indy_sign_request_works_for_signature_over_serialized_request() {
+            use rust_base58::FromBase58;
+
+            let setup = Setup::wallet();
+
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let request = ledger::sign_request(setup.wallet_handle, &did, REQUEST).unwrap();
+            let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+            let signature = request["signature"].as_str().unwrap().from_base58().unwrap();
+
+            // signing input: keys sorted, nested objects flattened, signature fields skipped
+            let serialized_request = "identifier:GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL|\
+                                      operation:dest:VsKV7grR1BUE29mG2Fm2kX|type:1|verkey:GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa|\
+                                      reqId:1496822211362017764";
+
+            assert!(utils::crypto::verify(&verkey, serialized_request.as_bytes(), &signature).unwrap());
+            assert!(!utils::crypto::verify(&verkey, REQUEST.as_bytes(), &signature).unwrap());
+        }
+
         #[test]
         fn indy_sign_works_for_unknown_signer() {
             let setup = Setup::wallet();
@@ -288,6 +309,52 @@ mod high_cases {
             assert_eq!(signatures[DID_MY1], r#"4EyvSFPoeQCJLziGVqjuMxrbuoWjAWUGPd6LdxeZuG9w3Bcbt7cSvhjrv8SX5e8mGf8jrf3K6xd9kEhXsQLqUg45"#);
         }
 
+        #[test]
+        fn indy_multi_sign_request_works_for_signing_twice_with_same_did() {
+            use rust_base58::FromBase58;
+
+            let setup = Setup::wallet();
+
+            let (did1, verkey1) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+            let (did2, verkey2) = did::create_and_store_my_did(setup.wallet_handle, Some(MY1_SEED)).unwrap();
+
+            let message = ledger::multi_sign_request(setup.wallet_handle, &did1, REQUEST).unwrap();
+            let message = ledger::multi_sign_request(setup.wallet_handle, &did2, &message).unwrap();
+            let message = ledger::multi_sign_request(setup.wallet_handle, &did1, &message).unwrap();
+
+            let msg: serde_json::Value = serde_json::from_str(&message).unwrap();
+            let signatures = msg["signatures"].as_object().unwrap();
+            assert_eq!(2, signatures.len());
+
+            // "signatures" is not part of the signing input, so both signatures stay valid for the final request
+            let serialized_request = "identifier:GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL|\
+                                      operation:dest:VsKV7grR1BUE29mG2Fm2kX|type:1|verkey:GjZWsBLgZCR18aL468JAT7w9CZRiBnpxUPPgyQxh4voa|\
+                                      reqId:1496822211362017764";
+
+            let signature1 = signatures[&did1].as_str().unwrap().from_base58().unwrap();
+            let signature2 = signatures[&did2].as_str().unwrap().from_base58().unwrap();
+            assert!(utils::crypto::verify(&verkey1, serialized_request.as_bytes(), &signature1).unwrap());
+            assert!(utils::crypto::verify(&verkey2, serialized_request.as_bytes(), &signature2).unwrap());
+        }
+
+        #[test]
+        fn indy_multi_sign_request_works_for_preserving_existing_signatures() {
+            let setup = Setup::wallet();
+
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, Some(TRUSTEE_SEED)).unwrap();
+
+            let mut request: serde_json::Value = serde_json::from_str(REQUEST).unwrap();
+            request["signatures"] = json!({DID_MY2: "existing_signature"});
+
+            let message = ledger::multi_sign_request(setup.wallet_handle, &did, &request.to_string()).unwrap();
+
+            let msg: serde_json::Value = serde_json::from_str(&message).unwrap();
+            let signatures = msg["signatures"].as_object().unwrap();
+            assert_eq!(2, signatures.len());
+            assert_eq!("existing_signature", signatures[DID_MY2]);
+            assert_eq!(signatures[DID_TRUSTEE], r#"65hzs4nsdQsTUqLCLy2qisbKLfwYKZSWoyh1C6CU59p5pfG3EHQXGAsjW4Qw4QdwkrvjSgQuyv8qyABcXRBznFKW"#);
+        }
+
         #[test]
         fn indy_multi_sign_request_works_for_unknown_signer() {
             let setup = Setup::wallet();
@@ -23,10 +23,46 @@ pub fn sign(wallet_handle: WalletHandle, my_vk: &str, msg: &[u8]) -> Result<Vec<
     crypto::sign(wallet_handle, my_vk, msg).wait()
 }
 
+pub fn sign_multiple(wallet_handle: WalletHandle, my_vk: &str, msgs_json: &str) -> Result<String, IndyError> {
+    crypto::sign_multiple(wallet_handle, my_vk, msgs_json).wait()
+}
+
+pub fn multi_sign(wallet_handle: WalletHandle, my_vks_json: &str, msg: &[u8]) -> Result<String, IndyError> {
+    crypto::multi_sign(wallet_handle, my_vks_json, msg).wait()
+}
+
 pub fn verify(their_vk: &str, msg: &[u8], signature: &[u8]) -> Result<bool, IndyError> {
     crypto::verify(their_vk, msg, signature).wait()
 }
 
+pub fn sign_json(wallet_handle: WalletHandle, my_vk: &str, msg_json: &str) -> Result<Vec<u8>, IndyError> {
+    crypto::sign_json(wallet_handle, my_vk, msg_json).wait()
+}
+
+pub fn verify_json(their_vk: &str, msg_json: &str, signature: &[u8]) -> Result<bool, IndyError> {
+    crypto::verify_json(their_vk, msg_json, signature).wait()
+}
+
+pub fn verify_detailed(their_vk: &str, msg: &[u8], signature: &[u8]) -> Result<String, IndyError> {
+    crypto::verify_detailed(their_vk, msg, signature).wait()
+}
+
+pub fn verify_any_key(their_vks_json: &str, msg: &[u8], signature: &[u8]) -> Result<String, IndyError> {
+    crypto::verify_any_key(their_vks_json, msg, signature).wait()
+}
+
+pub fn get_nonce() -> Result<String, IndyError> {
+    crypto::get_nonce().wait()
+}
+
+pub fn self_test() -> Result<String, IndyError> {
+    crypto::self_test().wait()
+}
+
+pub fn get_supported_types() -> Result<String, IndyError> {
+    crypto::get_supported_types().wait()
+}
+
 pub fn auth_crypt(wallet_handle: WalletHandle, my_vk: &str, their_vk: &str, msg: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::auth_crypt(wallet_handle, my_vk, their_vk, msg).wait()
 }
@@ -43,6 +79,10 @@ pub fn anon_decrypt(wallet_handle: WalletHandle, my_vk: &str, encrypted_msg: &[u
     crypto::anon_decrypt(wallet_handle, my_vk, encrypted_msg).wait()
 }
 
+pub fn derive_shared_secret(wallet_handle: WalletHandle, my_vk: &str, their_vk: &str) -> Result<String, IndyError> {
+    crypto::derive_shared_secret(wallet_handle, my_vk, their_vk).wait()
+}
+
 pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>) -> Result<Vec<u8>, IndyError> {
     crypto::pack_message(wallet_handle, message, receiver_keys, sender).wait()
 }
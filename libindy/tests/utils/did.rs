@@ -50,6 +50,10 @@ pub fn store_their_did(wallet_handle: WalletHandle, identity_json: &str) -> Resu
     did::store_their_did(wallet_handle, identity_json).wait()
 }
 
+pub fn store_their_dids(wallet_handle: WalletHandle, identities_json: &str) -> Result<String, IndyError> {
+    did::store_their_dids(wallet_handle, identities_json).wait()
+}
+
 pub fn store_their_did_from_parts(wallet_handle: WalletHandle, their_did: &str, their_verkey: &str) -> Result<(), IndyError> {
     let their_identity_json = json!({"did": their_did, "verkey": their_verkey}).to_string();
     did::store_their_did(wallet_handle, &their_identity_json).wait()
@@ -63,6 +67,10 @@ pub fn replace_keys_apply(wallet_handle: WalletHandle, did: &str) -> Result<(),
     did::replace_keys_apply(wallet_handle, did).wait()
 }
 
+pub fn rotate_and_publish_key(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, did: &str) -> Result<String, IndyError> {
+    did::rotate_and_publish_key(pool_handle, wallet_handle, submitter_did, did).wait()
+}
+
 pub fn replace_keys(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
     let verkey = did::replace_keys_start(wallet_handle, did, "{}").wait().unwrap();
 
@@ -78,6 +86,10 @@ pub fn key_for_did(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &s
     did::key_for_did(pool_handle, wallet_handle, did).wait()
 }
 
+pub fn key_for_did_detailed(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::key_for_did_detailed(pool_handle, wallet_handle, did).wait()
+}
+
 pub fn key_for_local_did(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
     did::key_for_local_did(wallet_handle, did).wait()
 }
@@ -90,6 +102,22 @@ pub fn get_endpoint_for_did(wallet_handle: WalletHandle, pool_handle: PoolHandle
     did::get_endpoint_for_did(wallet_handle, pool_handle, did).wait()
 }
 
+pub fn rekey_endpoint_for_did(wallet_handle: WalletHandle, did: &str, transport_key: &str) -> Result<(), IndyError> {
+    did::rekey_endpoint_for_did(wallet_handle, did, transport_key).wait()
+}
+
+pub fn prepare_anoncrypted_connection_request(wallet_handle: WalletHandle, their_vk: &str, my_did: &str) -> Result<Vec<u8>, IndyError> {
+    did::prepare_anoncrypted_connection_request(wallet_handle, their_vk, my_did).wait()
+}
+
+pub fn parse_connection_request(wallet_handle: WalletHandle, my_vk: &str, request: &[u8]) -> Result<String, IndyError> {
+    did::parse_connection_request(wallet_handle, my_vk, request).wait()
+}
+
+pub fn re_encrypt(wallet_handle: WalletHandle, my_did: &str, sender_vk: &str, recipient_vk: &str, encrypted_msg: &[u8]) -> Result<Vec<u8>, IndyError> {
+    did::re_encrypt(wallet_handle, my_did, sender_vk, recipient_vk, encrypted_msg).wait()
+}
+
 pub fn set_did_metadata(wallet_handle: WalletHandle, did: &str, metadata: &str) -> Result<(), IndyError> {
     did::set_did_metadata(wallet_handle, did, metadata).wait()
 }
@@ -106,10 +134,66 @@ pub fn list_my_dids_with_meta(wallet_handle: WalletHandle) -> Result<String, Ind
     did::list_my_dids_with_metadata(wallet_handle).wait()
 }
 
+pub fn list_their_dids(wallet_handle: WalletHandle, filter_json: Option<&str>) -> Result<String, IndyError> {
+    did::list_their_dids(wallet_handle, filter_json).wait()
+}
+
 pub fn abbreviate_verkey(did: &str, verkey: &str) -> Result<String, IndyError> {
     did::abbreviate_verkey(did, verkey).wait()
 }
 
+pub fn expand_verkey(did: &str, verkey: &str) -> Result<String, IndyError> {
+    did::expand_verkey(did, verkey).wait()
+}
+
+pub fn did_from_verkey(verkey: &str) -> Result<String, IndyError> {
+    did::did_from_verkey(verkey).wait()
+}
+
+pub fn compute_did_fingerprint(verkey: &str) -> Result<String, IndyError> {
+    did::compute_did_fingerprint(verkey).wait()
+}
+
+pub fn validate_did_string(did: &str) -> Result<String, IndyError> {
+    did::validate_did_string(did).wait()
+}
+
+pub fn verkey_to_curve25519(verkey: &str) -> Result<String, IndyError> {
+    did::verkey_to_curve25519(verkey).wait()
+}
+
+pub fn signkey_to_curve25519(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::signkey_to_curve25519(wallet_handle, did).wait()
+}
+
+pub fn forget_did(wallet_handle: WalletHandle, did: &str) -> Result<(), IndyError> {
+    did::forget_did(wallet_handle, did).wait()
+}
+
+pub fn export_did_key(wallet_handle: WalletHandle, did: &str, passphrase: &str) -> Result<String, IndyError> {
+    did::export_did_key(wallet_handle, did, passphrase).wait()
+}
+
+pub fn import_did_key(wallet_handle: WalletHandle, passphrase: &str, exported: &str, policy: Option<&str>) -> Result<String, IndyError> {
+    did::import_did_key(wallet_handle, passphrase, exported, policy).wait()
+}
+
+pub fn check_did_integrity(wallet_handle: WalletHandle, did: &str) -> Result<String, IndyError> {
+    did::check_did_integrity(wallet_handle, did).wait()
+}
+
+pub fn verify_multi_did_signature(wallet_handle: WalletHandle, signatures_json: &str, msg: &[u8], threshold: u32) -> Result<bool, IndyError> {
+    did::verify_multi_did_signature(wallet_handle, signatures_json, msg, threshold).wait()
+}
+
+pub fn generate_mnemonic_seed() -> Result<(String, String), IndyError> {
+    did::generate_mnemonic_seed().wait()
+}
+
+pub fn seed_from_mnemonic(mnemonic: &str) -> Result<String, IndyError> {
+    did::seed_from_mnemonic(mnemonic).wait()
+}
+
 pub fn qualify_did(wallet_handle: WalletHandle, did: &str, prefix: &str) -> Result<String, IndyError> {
     did::qualify_did(wallet_handle, did, prefix).wait()
 }
@@ -31,6 +31,21 @@ extern {
                             message_len: u32,
                             cb: Option<ResponseSliceCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_crypto_sign_multiple(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     signer_vk: CString,
+                                     messages_json: CString,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_multi_sign(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  signer_vks_json: CString,
+                                  message_raw: BString,
+                                  message_len: u32,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_crypto_verify(command_handle: CommandHandle,
                               signer_vk: CString,
@@ -40,6 +55,51 @@ extern {
                               signature_len: u32,
                               cb: Option<ResponseBoolCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_crypto_verify_detailed(command_handle: CommandHandle,
+                                       signer_vk: CString,
+                                       message_raw: BString,
+                                       message_len: u32,
+                                       signature_raw: BString,
+                                       signature_len: u32,
+                                       cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_verify_any_key(command_handle: CommandHandle,
+                                      signer_vks_json: CString,
+                                      message_raw: BString,
+                                      message_len: u32,
+                                      signature_raw: BString,
+                                      signature_len: u32,
+                                      cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_sign_json(command_handle: CommandHandle,
+                                 wallet_handle: WalletHandle,
+                                 signer_vk: CString,
+                                 message_json: CString,
+                                 cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_verify_json(command_handle: CommandHandle,
+                                   signer_vk: CString,
+                                   message_json: CString,
+                                   signature_raw: BString,
+                                   signature_len: u32,
+                                   cb: Option<ResponseBoolCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_get_nonce(command_handle: CommandHandle,
+                                 cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_self_test(command_handle: CommandHandle,
+                                 cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_crypto_get_supported_types(command_handle: CommandHandle,
+                                           cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_crypto_auth_crypt(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
@@ -72,6 +132,13 @@ extern {
                                     encrypted_len: u32,
                                     cb: Option<ResponseSliceCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_crypto_derive_shared_secret(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            my_vk: CString,
+                                            their_vk: CString,
+                                            cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_pack_message(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error, CommandHandle, WalletHandle, PoolHandle};
+use {BString, CString, Error, CommandHandle, WalletHandle, PoolHandle};
 
 extern {
     #[no_mangle]
@@ -22,12 +22,26 @@ extern {
                                    did: CString,
                                    cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_rotate_and_publish_key(command_handle: CommandHandle,
+                                       pool_handle: PoolHandle,
+                                       wallet_handle: WalletHandle,
+                                       submitter_did: CString,
+                                       target_did: CString,
+                                       cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_store_their_did(command_handle: CommandHandle,
                                 wallet_handle: WalletHandle,
                                 identity_json: CString,
                                 cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_store_their_dids(command_handle: CommandHandle,
+                                 wallet_handle: WalletHandle,
+                                 identities_json: CString,
+                                 cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_key_for_did(command_handle: CommandHandle,
                             pool_handle: PoolHandle,
@@ -35,6 +49,13 @@ extern {
                             did: CString,
                             cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_key_for_did_detailed(command_handle: CommandHandle,
+                                     pool_handle: PoolHandle,
+                                     wallet_handle: WalletHandle,
+                                     did: CString,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_key_for_local_did(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
@@ -56,6 +77,38 @@ extern {
                                      did: CString,
                                      cb: Option<ResponseStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_rekey_endpoint_for_did(command_handle: CommandHandle,
+                                       wallet_handle: WalletHandle,
+                                       did: CString,
+                                       transport_key: CString,
+                                       cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_prepare_anoncrypted_connection_request(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       their_vk: CString,
+                                                       my_did: CString,
+                                                       cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_parse_connection_request(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         my_vk: CString,
+                                         request_raw: BString,
+                                         request_len: u32,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_re_encrypt(command_handle: CommandHandle,
+                           wallet_handle: WalletHandle,
+                           my_did: CString,
+                           sender_vk: CString,
+                           recipient_vk: CString,
+                           encrypted_msg_raw: BString,
+                           encrypted_msg_len: u32,
+                           cb: Option<ResponseSliceCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_set_did_metadata(command_handle: CommandHandle,
                                  wallet_handle: WalletHandle,
@@ -80,12 +133,95 @@ extern {
                                        wallet_handle: WalletHandle,
                                        cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_list_their_dids(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                filter_json: CString,
+                                cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_abbreviate_verkey(command_handle: CommandHandle,
                                   did: CString,
                                   full_verkey: CString,
                                   cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_expand_verkey(command_handle: CommandHandle,
+                              did: CString,
+                              verkey: CString,
+                              cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_did_from_verkey(command_handle: CommandHandle,
+                                verkey: CString,
+                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_compute_did_fingerprint(command_handle: CommandHandle,
+                                        verkey: CString,
+                                        cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_validate_did_string(command_handle: CommandHandle,
+                                    did: CString,
+                                    cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verkey_to_curve25519(command_handle: CommandHandle,
+                                     verkey: CString,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_signkey_to_curve25519(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      did: CString,
+                                      cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_forget_did(command_handle: CommandHandle,
+                           wallet_handle: WalletHandle,
+                           did: CString,
+                           cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_export_did_key(command_handle: CommandHandle,
+                               wallet_handle: WalletHandle,
+                               did: CString,
+                               passphrase: CString,
+                               cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_import_did_key(command_handle: CommandHandle,
+                               wallet_handle: WalletHandle,
+                               passphrase: CString,
+                               exported: CString,
+                               policy: CString,
+                               cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_check_did_integrity(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    did: CString,
+                                    cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_verify_multi_did_signature(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           signatures_json: CString,
+                                           message_raw: BString,
+                                           message_len: u32,
+                                           threshold: u32,
+                                           cb: Option<ResponseBoolCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_generate_mnemonic_seed(command_handle: CommandHandle,
+                                       cb: Option<ResponseStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_seed_from_mnemonic(command_handle: CommandHandle,
+                                   mnemonic: CString,
+                                   cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_qualify_did(command_handle: CommandHandle,
                             wallet_handle: WalletHandle,
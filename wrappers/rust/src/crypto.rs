@@ -107,6 +107,54 @@ fn _sign(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vk:
     })
 }
 
+/// Signs several messages with the same key
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `signer_vk` - key id or verkey of my key. The key must be created by calling create_key or Did::new
+/// * `messages_json` - json array of base64 encoded messages to be signed
+/// # Returns
+/// json array with one entry per message, in the same order as messages:
+///   [{"ok": "<base58 signature>"}, {"err": {"code": <error code>, "message": "<string>"}}]
+pub fn sign_multiple(wallet_handle: WalletHandle, signer_vk: &str, messages_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _sign_multiple(command_handle, wallet_handle, signer_vk, messages_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _sign_multiple(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vk: &str, messages_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let signer_vk = c_str!(signer_vk);
+    let messages_json = c_str!(messages_json);
+
+    ErrorCode::from(unsafe { crypto::indy_crypto_sign_multiple(command_handle, wallet_handle, signer_vk.as_ptr(), messages_json.as_ptr(), cb) })
+}
+
+/// Signs a message with several keys
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `signer_vks_json` - json array of key ids or verkeys of my keys. The keys must be created by calling create_key or Did::new
+/// * `message` - the data to be signed
+/// # Returns
+/// json object that maps each signer verkey to its base58 encoded signature
+pub fn multi_sign(wallet_handle: WalletHandle, signer_vks_json: &str, message: &[u8]) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _multi_sign(command_handle, wallet_handle, signer_vks_json, message, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _multi_sign(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vks_json: &str, message: &[u8], cb: Option<ResponseStringCB>) -> ErrorCode {
+    let signer_vks_json = c_str!(signer_vks_json);
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_multi_sign(command_handle, wallet_handle, signer_vks_json.as_ptr(),
+                                       message.as_ptr() as *const u8,
+                                       message.len() as u32,
+                                       cb)
+    })
+}
+
 /// Verify a signature with a verkey
 /// # Arguments
 /// * `wallet_handle` - wallet handle (created by Wallet::open)
@@ -133,6 +181,150 @@ fn _verify(command_handle: CommandHandle, signer_vk: &str, message: &[u8], signa
     })
 }
 
+/// Verify a signature with a verkey and report why verification failed
+/// # Arguments
+/// * `signer_vk` - verkey of the message signer
+/// * `message` - the data that was signed
+/// * `signature` - the signature to verify
+/// # Returns
+/// json {"verified": bool, "reason": optional<string>}, reason is one of
+/// "key not resolved", "malformed signature", "signature mismatch"
+pub fn verify_detailed(signer_vk: &str, message: &[u8], signature: &[u8]) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verify_detailed(command_handle, signer_vk, message, signature, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verify_detailed(command_handle: CommandHandle, signer_vk: &str, message: &[u8], signature: &[u8], cb: Option<ResponseStringCB>) -> ErrorCode {
+    let signer_vk = c_str!(signer_vk);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_verify_detailed(command_handle, signer_vk.as_ptr(),
+                                            message.as_ptr() as *const u8, message.len() as u32,
+                                            signature.as_ptr() as *const u8, signature.len() as u32, cb)
+    })
+}
+
+/// Verify a signature against a list of verkeys and report which one matched
+/// # Arguments
+/// * `signer_vks_json` - json array of verkeys to try, in order of preference
+/// * `message` - the data that was signed
+/// * `signature` - the signature to verify
+/// # Returns
+/// json {"verified": bool, "verkey": optional<string>}, verkey is the first key the signature is valid for
+pub fn verify_any_key(signer_vks_json: &str, message: &[u8], signature: &[u8]) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verify_any_key(command_handle, signer_vks_json, message, signature, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verify_any_key(command_handle: CommandHandle, signer_vks_json: &str, message: &[u8], signature: &[u8], cb: Option<ResponseStringCB>) -> ErrorCode {
+    let signer_vks_json = c_str!(signer_vks_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_verify_any_key(command_handle, signer_vks_json.as_ptr(),
+                                           message.as_ptr() as *const u8, message.len() as u32,
+                                           signature.as_ptr() as *const u8, signature.len() as u32, cb)
+    })
+}
+
+/// Signs a JSON message with a key. The message is canonicalized (sorted keys, no insignificant whitespace)
+/// before signing, so logically equal JSON objects produce the same signature.
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by Wallet::open)
+/// * `signer_vk` - key id or verkey of my key. The key must be created by calling create_key or Did::new
+/// * `message_json` - the JSON to be signed
+/// # Returns
+/// the signature
+pub fn sign_json(wallet_handle: WalletHandle, signer_vk: &str, message_json: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _sign_json(command_handle, wallet_handle, signer_vk, message_json, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _sign_json(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vk: &str, message_json: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let signer_vk = c_str!(signer_vk);
+    let message_json = c_str!(message_json);
+
+    ErrorCode::from(unsafe { crypto::indy_crypto_sign_json(command_handle, wallet_handle, signer_vk.as_ptr(), message_json.as_ptr(), cb) })
+}
+
+/// Verify a signature made by `sign_json`
+/// # Arguments
+/// * `signer_vk` - verkey of the message signer
+/// * `message_json` - the JSON that was signed, in any formatting or key order
+/// * `signature` - the signature to verify
+/// # Returns
+/// true if signature is valid, false otherwise
+pub fn verify_json(signer_vk: &str, message_json: &str, signature: &[u8]) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_json(command_handle, signer_vk, message_json, signature, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_json(command_handle: CommandHandle, signer_vk: &str, message_json: &str, signature: &[u8], cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let signer_vk = c_str!(signer_vk);
+    let message_json = c_str!(message_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_crypto_verify_json(command_handle, signer_vk.as_ptr(), message_json.as_ptr(),
+                                        signature.as_ptr() as *const u8, signature.len() as u32, cb)
+    })
+}
+
+/// Generates a fresh random nonce without touching any wallet or DID
+/// # Returns
+/// base58 encoded 24-byte nonce
+pub fn get_nonce() -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_nonce(command_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_nonce(command_handle: CommandHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { crypto::indy_crypto_get_nonce(command_handle, cb) })
+}
+
+/// Runs known-answer tests of the crypto backend (seed derivation, sign/verify, box/unbox)
+/// # Returns
+/// JSON report with pass/fail result per primitive. Fails with CommonInvalidState if any test fails
+pub fn self_test() -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _self_test(command_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _self_test(command_handle: CommandHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { crypto::indy_crypto_self_test(command_handle, cb) })
+}
+
+/// Lists the crypto types this build supports
+/// # Returns
+/// JSON array of supported crypto type names, e.g. ["ed25519"]
+pub fn get_supported_types() -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_supported_types(command_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_supported_types(command_handle: CommandHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { crypto::indy_crypto_get_supported_types(command_handle, cb) })
+}
+
 /// **** THIS FUNCTION WILL BE DEPRECATED USE pack_message INSTEAD ****
 /// Encrypt a message by authenticated-encryption scheme.
 ///
@@ -282,6 +474,33 @@ fn _anon_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, rec
     })
 }
 
+/// Derives a raw Curve25519 shared secret between my key and their key.
+///
+/// The same pair of keys gives the same secret from both sides. The secret is not hashed,
+/// so run it through a KDF before using it as a symmetric key.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `my_vk`: key id or verkey of my key. The key must be created by calling create_key or Did::new
+/// * `their_vk`: verkey of the other party
+///
+/// # Returns
+/// base58 encoded shared secret
+pub fn derive_shared_secret(wallet_handle: WalletHandle, my_vk: &str, their_vk: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _derive_shared_secret(command_handle, wallet_handle, my_vk, their_vk, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _derive_shared_secret(command_handle: CommandHandle, wallet_handle: WalletHandle, my_vk: &str, their_vk: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let my_vk = c_str!(my_vk);
+    let their_vk = c_str!(their_vk);
+
+    ErrorCode::from(unsafe { crypto::indy_crypto_derive_shared_secret(command_handle, wallet_handle, my_vk.as_ptr(), their_vk.as_ptr(), cb) })
+}
+
 /// Unpacks a message packed using indy_pack_message which follows the wire message format HIPE
 /// (Experimental)
 ///
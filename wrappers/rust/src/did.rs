@@ -5,7 +5,9 @@ use std::ffi::CString;
 use futures::Future;
 
 use ffi::did;
-use ffi::{ResponseEmptyCB,
+use ffi::{ResponseBoolCB,
+          ResponseEmptyCB,
+          ResponseSliceCB,
           ResponseStringCB,
           ResponseStringStringCB};
 
@@ -35,6 +37,8 @@ use {CommandHandle, WalletHandle, PoolHandle};
 ///               currently only 'ed25519' value is supported for this field)
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///     "strict_seed": bool, (optional; if not set then false is used;
+///             if true then a seed with too low entropy, e.g. of one repeated character, is rejected)
 /// }
 ///
 /// # Returns
@@ -106,9 +110,37 @@ fn _replace_keys_apply(command_handle: CommandHandle, wallet_handle: WalletHandl
     ErrorCode::from(unsafe { did::indy_replace_keys_apply(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
 }
 
+/// Generates a new key for an existing DID, publishes it to the ledger with a NYM transaction
+/// and applies it as main key once the ledger has accepted the transaction.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by Pool::open_ledger).
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `submitter_did` - DID of the NYM transaction submitter
+/// * `tgt_did` - DID stored in the wallet whose key is rotated
+///
+/// # Returns
+/// * `verkey` - The new verkey applied for the DID.
+pub fn rotate_and_publish_key(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, tgt_did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _rotate_and_publish_key(command_handle, pool_handle, wallet_handle, submitter_did, tgt_did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _rotate_and_publish_key(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, tgt_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let tgt_did = c_str!(tgt_did);
+
+    ErrorCode::from(unsafe { did::indy_rotate_and_publish_key(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), tgt_did.as_ptr(), cb) })
+}
+
 /// Saves their DID for a pairwise connection in a secured Wallet,
 /// so that it can be used to verify transaction.
-/// Updates DID associated verkey in case DID already exists in the Wallet.
+/// Storing the same DID with the same verkey again is a no-op.
+/// A verkey can be added later to a DID stored without one.
+/// Storing the same DID with another verkey fails with CommonInvalidState unless "overwrite" is set.
 ///
 /// # Arguments
 /// * `wallet_handle` - wallet handler (created by Wallet::open).
@@ -120,6 +152,7 @@ fn _replace_keys_apply(command_handle: CommandHandle, wallet_handle: WalletHandl
 ///        "verkey": string
 ///             - optional is case of adding a new DID, and DID is cryptonym: did == verkey,
 ///             - mandatory in case of updating an existing DID
+///        "overwrite": bool (optional, false by default) - replace verkey of already stored DID
 ///     }
 pub fn store_their_did(wallet_handle: WalletHandle, identity_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -135,6 +168,33 @@ fn _store_their_did(command_handle: CommandHandle, wallet_handle: WalletHandle,
     ErrorCode::from(unsafe { did::indy_store_their_did(command_handle, wallet_handle, identity_json.as_ptr(), cb) })
 }
 
+/// Saves several their DIDs in a secured Wallet at once.
+/// A malformed or conflicting entry doesn't abort the batch, its slot in the result reports the error.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `identities_json` - JSON array of identities in the `store_their_did` format.
+///
+/// # Returns
+/// JSON array with one entry per identity, in the same order:
+///     [
+///        {"ok": string}, - did of the stored entry
+///        {"err": {"code": int, "message": string}}
+///     ]
+pub fn store_their_dids(wallet_handle: WalletHandle, identities_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _store_their_dids(command_handle, wallet_handle, identities_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _store_their_dids(command_handle: CommandHandle, wallet_handle: WalletHandle, identities_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let identities_json = c_str!(identities_json);
+
+    ErrorCode::from(unsafe { did::indy_store_their_dids(command_handle, wallet_handle, identities_json.as_ptr(), cb) })
+}
+
 /// Returns ver key (key id) for the given DID.
 ///
 /// "get_ver_key" call follow the idea that we resolve information about their DID from
@@ -169,6 +229,29 @@ fn _key_for_did(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_h
     ErrorCode::from(unsafe { did::indy_key_for_did(command_handle, pool_handle, wallet_handle, did.as_ptr(), cb) })
 }
 
+/// Returns ver key (key id) for the given DID together with the place it was resolved from.
+///
+/// # Arguments
+/// * `pool_handle` - Pool handle (created by Pool::open).
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `did` - The DID to resolve key.
+///
+/// # Returns
+/// * `key_json` - {"verkey": string, "source": "my" | "their_cached" | "ledger"}
+pub fn key_for_did_detailed(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _key_for_did_detailed(command_handle, pool_handle, wallet_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _key_for_did_detailed(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_key_for_did_detailed(command_handle, pool_handle, wallet_handle, did.as_ptr(), cb) })
+}
+
 /// Returns ver key (key id) for the given DID.
 ///
 /// "get_ver_key_did" call looks data stored in the local wallet only and skips freshness
@@ -246,6 +329,101 @@ fn _get_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHan
     ErrorCode::from(unsafe { did::indy_get_endpoint_for_did(command_handle, wallet_handle, pool_handle, did.as_ptr(), cb) })
 }
 
+/// Replaces the transport key of the endpoint stored for the given DID, keeping its address.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `did` - The DID whose endpoint must be rekeyed.
+/// * `transport_key` - The new transport key (ver key, key id).
+pub fn rekey_endpoint_for_did(wallet_handle: WalletHandle, did: &str, transport_key: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _rekey_endpoint_for_did(command_handle, wallet_handle, did, transport_key, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _rekey_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, transport_key: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let did = c_str!(did);
+    let transport_key = c_str!(transport_key);
+
+    ErrorCode::from(unsafe { did::indy_rekey_endpoint_for_did(command_handle, wallet_handle, did.as_ptr(), transport_key.as_ptr(), cb) })
+}
+
+/// Builds a connection request carrying my DID, verkey and endpoint, anoncrypted to their verkey.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `their_vk` - verkey of the agent the request is sent to.
+/// * `my_did` - my DID stored in the wallet.
+///
+/// # Returns
+/// * `request` - the anoncrypted connection request.
+pub fn prepare_anoncrypted_connection_request(wallet_handle: WalletHandle, their_vk: &str, my_did: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _prepare_anoncrypted_connection_request(command_handle, wallet_handle, their_vk, my_did, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _prepare_anoncrypted_connection_request(command_handle: CommandHandle, wallet_handle: WalletHandle, their_vk: &str, my_did: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let their_vk = c_str!(their_vk);
+    let my_did = c_str!(my_did);
+
+    ErrorCode::from(unsafe { did::indy_prepare_anoncrypted_connection_request(command_handle, wallet_handle, their_vk.as_ptr(), my_did.as_ptr(), cb) })
+}
+
+/// Opens a connection request and stores the sender's DID, verkey and endpoint in the wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `my_vk` - verkey the request was anoncrypted to.
+/// * `request` - the anoncrypted connection request.
+///
+/// # Returns
+/// * `request_json` - the opened request.
+pub fn parse_connection_request(wallet_handle: WalletHandle, my_vk: &str, request: &[u8]) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _parse_connection_request(command_handle, wallet_handle, my_vk, request, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _parse_connection_request(command_handle: CommandHandle, wallet_handle: WalletHandle, my_vk: &str, request: &[u8], cb: Option<ResponseStringCB>) -> ErrorCode {
+    let my_vk = c_str!(my_vk);
+
+    ErrorCode::from(unsafe { did::indy_parse_connection_request(command_handle, wallet_handle, my_vk.as_ptr(), request.as_ptr() as *const u8, request.len() as u32, cb) })
+}
+
+/// Re-encrypts a message authcrypted to my DID and anoncrypts it to a new recipient.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `my_did` - my DID stored in the wallet, the message was authcrypted to its verkey.
+/// * `sender_vk` - verkey of the original sender of the message.
+/// * `recipient_vk` - verkey of the new recipient.
+/// * `encrypted_msg` - the authcrypted message.
+///
+/// # Returns
+/// * `encrypted_msg` - the message anoncrypted to the new recipient.
+pub fn re_encrypt(wallet_handle: WalletHandle, my_did: &str, sender_vk: &str, recipient_vk: &str, encrypted_msg: &[u8]) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _re_encrypt(command_handle, wallet_handle, my_did, sender_vk, recipient_vk, encrypted_msg, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _re_encrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, my_did: &str, sender_vk: &str, recipient_vk: &str, encrypted_msg: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let my_did = c_str!(my_did);
+    let sender_vk = c_str!(sender_vk);
+    let recipient_vk = c_str!(recipient_vk);
+
+    ErrorCode::from(unsafe { did::indy_re_encrypt(command_handle, wallet_handle, my_did.as_ptr(), sender_vk.as_ptr(), recipient_vk.as_ptr(), encrypted_msg.as_ptr() as *const u8, encrypted_msg.len() as u32, cb) })
+}
+
 /// Saves/replaces the meta information for the giving DID in the wallet.
 ///
 /// # Arguments
@@ -339,6 +517,32 @@ fn _list_my_dids_with_metadata(command_handle: CommandHandle, wallet_handle: Wal
     ErrorCode::from(unsafe { did::indy_list_my_dids_with_meta(command_handle, wallet_handle, cb) })
 }
 
+/// Retrieves their DIDs stored in the wallet together with their endpoints.
+///
+/// # Arguments
+/// * `wallet_handle` - Wallet handle (created by Wallet::open).
+/// * `filter_json` - (optional) {"has_endpoint": Option<bool>}
+///
+/// # Returns
+///  * `dids` - array sorted by DID [{
+///     "did": string - their DID stored in the wallet,
+///     "verkey": string - their verkey,
+///     "endpoint": Option<{"ha": string, "verkey": Option<string>}> - endpoint stored for their DID
+///   }]
+pub fn list_their_dids(wallet_handle: WalletHandle, filter_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _list_their_dids(command_handle, wallet_handle, filter_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _list_their_dids(command_handle: CommandHandle, wallet_handle: WalletHandle, filter_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let filter_json_str = opt_c_str!(filter_json);
+
+    ErrorCode::from(unsafe { did::indy_list_their_dids(command_handle, wallet_handle, opt_c_ptr!(filter_json, filter_json_str), cb) })
+}
+
 /// Retrieves abbreviated verkey if it is possible otherwise return full verkey.
 ///
 /// # Arguments
@@ -362,6 +566,294 @@ fn _abbreviate_verkey(command_handle: CommandHandle, tgt_did: &str, verkey: &str
     ErrorCode::from(unsafe { did::indy_abbreviate_verkey(command_handle, tgt_did.as_ptr(), verkey.as_ptr(), cb) })
 }
 
+/// Retrieves full verkey for the given DID and verkey in either abbreviated or full form.
+///
+/// # Arguments
+/// * `tgt_did` - DID.
+/// * `verkey` - The DIDs verification key in either abbreviated or full form,
+///
+/// #Returns
+///  * `full_verkey` - The DIDs verification key in full form
+pub fn expand_verkey(tgt_did: &str, verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _expand_verkey(command_handle, tgt_did, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _expand_verkey(command_handle: CommandHandle, tgt_did: &str, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { did::indy_expand_verkey(command_handle, tgt_did.as_ptr(), verkey.as_ptr(), cb) })
+}
+
+/// Derives DID from the full verkey without storing anything in the wallet.
+///
+/// # Arguments
+/// * `verkey` - The verification key in full form,
+///
+/// #Returns
+///  * `did` - DID derived from the verkey
+pub fn did_from_verkey(verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _did_from_verkey(command_handle, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _did_from_verkey(command_handle: CommandHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { did::indy_did_from_verkey(command_handle, verkey.as_ptr(), cb) })
+}
+
+/// Computes a short, stable fingerprint of the full verkey for display and out-of-band key confirmation.
+///
+/// # Arguments
+/// * `verkey` - The verification key in full form,
+///
+/// #Returns
+///  * `fingerprint` - 16 hex chars fingerprint of the verkey
+pub fn compute_did_fingerprint(verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _compute_did_fingerprint(command_handle, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _compute_did_fingerprint(command_handle: CommandHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { did::indy_compute_did_fingerprint(command_handle, verkey.as_ptr(), cb) })
+}
+
+/// Checks that a DID string is well-formed: a base58 encoded 16 or 32 byte number,
+/// optionally prefixed with `did:<method>:`.
+///
+/// # Arguments
+/// * `did` - DID string to check.
+///
+/// #Returns
+///  * `result_json` - {"valid": bool, "kind": Option<"short" | "full" | "qualified">}
+pub fn validate_did_string(did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _validate_did_string(command_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _validate_did_string(command_handle: CommandHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_validate_did_string(command_handle, did.as_ptr(), cb) })
+}
+
+/// Converts the full Ed25519 verkey to the Curve25519 public key used for encryption.
+///
+/// # Arguments
+/// * `verkey` - The verification key in full form,
+///
+/// #Returns
+///  * `curve25519_key` - base58 encoded Curve25519 public key
+pub fn verkey_to_curve25519(verkey: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verkey_to_curve25519(command_handle, verkey, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verkey_to_curve25519(command_handle: CommandHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let verkey = c_str!(verkey);
+
+    ErrorCode::from(unsafe { did::indy_verkey_to_curve25519(command_handle, verkey.as_ptr(), cb) })
+}
+
+/// Converts the signing key of a DID owned by the caller to the Curve25519 private key used for encryption.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `did` - DID stored in the wallet
+///
+/// #Returns
+///  * `curve25519_key` - base58 encoded Curve25519 private key
+pub fn signkey_to_curve25519(wallet_handle: WalletHandle, did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _signkey_to_curve25519(command_handle, wallet_handle, did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _signkey_to_curve25519(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let did = c_str!(did);
+
+    ErrorCode::from(unsafe { did::indy_signkey_to_curve25519(command_handle, wallet_handle, did.as_ptr(), cb) })
+}
+
+/// Removes DID owned by the caller from the wallet together with its metadata and endpoint.
+/// Keys stored for the DID stay in the wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `tgt_did` - DID stored in the wallet
+pub fn forget_did(wallet_handle: WalletHandle, tgt_did: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _forget_did(command_handle, wallet_handle, tgt_did, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _forget_did(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+
+    ErrorCode::from(unsafe { did::indy_forget_did(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
+}
+
+/// Exports keys of DID owned by the caller encrypted with a key derived from passphrase.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `tgt_did` - DID stored in the wallet
+/// * `passphrase` - passphrase used to derive the encryption key
+///
+/// # Returns
+/// base64 encoded encrypted DID key
+pub fn export_did_key(wallet_handle: WalletHandle, tgt_did: &str, passphrase: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _export_did_key(command_handle, wallet_handle, tgt_did, passphrase, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _export_did_key(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, passphrase: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+    let passphrase = c_str!(passphrase);
+
+    ErrorCode::from(unsafe { did::indy_export_did_key(command_handle, wallet_handle, tgt_did.as_ptr(), passphrase.as_ptr(), cb) })
+}
+
+/// Imports DID keys exported by `export_did_key` and stores DID as owned by the caller.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `passphrase` - passphrase used on export
+/// * `exported` - base64 encoded encrypted DID key
+/// * `policy` - (optional) what to do if the DID is already stored: "fail" (default), "overwrite" or "skip"
+///
+/// # Returns
+/// imported DID
+pub fn import_did_key(wallet_handle: WalletHandle, passphrase: &str, exported: &str, policy: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _import_did_key(command_handle, wallet_handle, passphrase, exported, policy, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _import_did_key(command_handle: CommandHandle, wallet_handle: WalletHandle, passphrase: &str, exported: &str, policy: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let passphrase = c_str!(passphrase);
+    let exported = c_str!(exported);
+    let policy_str = opt_c_str!(policy);
+
+    ErrorCode::from(unsafe { did::indy_import_did_key(command_handle, wallet_handle, passphrase.as_ptr(), exported.as_ptr(), opt_c_ptr!(policy, policy_str), cb) })
+}
+
+/// Checks that keys of DID owned by the caller are consistent.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `tgt_did` - DID stored in the wallet
+///
+/// # Returns
+/// report json {"did": string, "verkey_ok": bool, "did_ok": bool}
+pub fn check_did_integrity(wallet_handle: WalletHandle, tgt_did: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _check_did_integrity(command_handle, wallet_handle, tgt_did, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _check_did_integrity(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let tgt_did = c_str!(tgt_did);
+
+    ErrorCode::from(unsafe { did::indy_check_did_integrity(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
+}
+
+/// Verifies signatures made by several DIDs over the same message.
+/// DIDs that can't be resolved from the wallet and malformed signatures count as invalid.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handler (created by Wallet::open).
+/// * `signatures_json` - map of signer DIDs to base58 encoded signatures: {"<did>": "<signature>"}
+/// * `message` - the message that was signed
+/// * `threshold` - minimal number of valid signatures (at least 1)
+///
+/// # Returns
+/// true if at least `threshold` signatures are valid, false otherwise
+pub fn verify_multi_did_signature(wallet_handle: WalletHandle, signatures_json: &str, message: &[u8], threshold: u32) -> Box<dyn Future<Item=bool, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = _verify_multi_did_signature(command_handle, wallet_handle, signatures_json, message, threshold, cb);
+
+    ResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _verify_multi_did_signature(command_handle: CommandHandle, wallet_handle: WalletHandle, signatures_json: &str, message: &[u8], threshold: u32, cb: Option<ResponseBoolCB>) -> ErrorCode {
+    let signatures_json = c_str!(signatures_json);
+
+    ErrorCode::from(unsafe {
+        did::indy_verify_multi_did_signature(command_handle, wallet_handle, signatures_json.as_ptr(),
+                                             message.as_ptr() as *const u8, message.len() as u32, threshold, cb)
+    })
+}
+
+/// Generates a random seed together with its BIP39 mnemonic (24 English words).
+///
+/// # Returns
+/// * `mnemonic` - 24 words mnemonic phrase
+/// * `seed` - 32 bytes seed as HEX string, usable as `seed` for `create_and_store_my_did`
+pub fn generate_mnemonic_seed() -> Box<dyn Future<Item=(String, String), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = _generate_mnemonic_seed(command_handle, cb);
+
+    ResultHandler::str_str(command_handle, err, receiver)
+}
+
+fn _generate_mnemonic_seed(command_handle: CommandHandle, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { did::indy_generate_mnemonic_seed(command_handle, cb) })
+}
+
+/// Reconstructs the seed from a mnemonic generated by `generate_mnemonic_seed`.
+///
+/// # Arguments
+/// * `mnemonic` - 24 words mnemonic phrase
+///
+/// # Returns
+/// 32 bytes seed as HEX string
+pub fn seed_from_mnemonic(mnemonic: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _seed_from_mnemonic(command_handle, mnemonic, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _seed_from_mnemonic(command_handle: CommandHandle, mnemonic: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let mnemonic = c_str!(mnemonic);
+
+    ErrorCode::from(unsafe { did::indy_seed_from_mnemonic(command_handle, mnemonic.as_ptr(), cb) })
+}
+
 /// Update DID stored in the wallet to make fully qualified, or to do other DID maintenance.
 ///     - If the DID has no method, a method will be appended (prepend did:peer to a legacy did)
 ///     - If the DID has a method, a method will be updated (migrate did:peer to did:peer-new)